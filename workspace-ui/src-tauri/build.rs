@@ -1,3 +1,76 @@
+// App-Commands für das ACL-Manifest. Tauri erzeugt daraus die Permissions
+// `allow-<command>`/`deny-<command>`; sobald es ein App-Manifest gibt, braucht
+// jeder Command eine Freigabe in capabilities/. Muss zu `app_commands![...]`
+// in src/lib.rs passen (geprüft in `ipc_access::tests`).
+const APP_COMMANDS: &[&str] = &[
+    // Legacy
+    "greet",
+    "open_webview_window",
+    "close_webview_window",
+    // Web Tab API
+    "create_web_tab",
+    "update_web_tab_bounds",
+    "focus_web_tab",
+    "unfocus_web_tabs",
+    "set_web_tab_fullscreen",
+    "close_web_tab",
+    "close_all_web_tabs",
+    "close_all_orphaned_webviews",
+    "set_web_tab_visible",
+    "navigate_web_tab",
+    "is_web_tab_fullscreen",
+    "restore_web_tabs",
+    // Canvas Layout
+    "set_canvas_viewport",
+    "set_web_tab_canvas_rect",
+    "set_canvas_exclusion",
+    "remove_canvas_exclusion",
+    // Web Tab History
+    "web_tab_go_back",
+    "web_tab_go_forward",
+    "web_tab_reload",
+    "web_tab_stop",
+    "get_web_tab_history",
+    // URL Policy
+    "get_url_policy",
+    "set_url_policy",
+    "set_url_policy_board",
+    "check_web_tab_url",
+    // Content Blocking
+    "reload_content_filters",
+    "set_web_tab_content_blocking",
+    "get_web_tab_content_blocking",
+    // Web Profiles
+    "list_web_profiles",
+    "delete_web_profile",
+    // Web Tab Thumbnails
+    "capture_web_tab_thumbnail",
+    "get_cached_web_tab_thumbnail",
+    // Web Tab Backends
+    "list_web_tab_backends",
+    "get_web_tab_backend",
+    "set_web_tab_backend",
+    // AI Content Extraction
+    "extract_webview_content",
+    "extract_all_webview_contents",
+    "report_webview_content",
+    // Board Persistence
+    "save_board",
+    "load_board",
+    "list_boards",
+    "delete_board",
+    "rename_board",
+    "migrate_local_storage_boards",
+    // Window Management
+    "bring_webviews_to_front",
+    "focus_main_window",
+    "bring_web_tab_to_front",
+];
+
 fn main() {
-    tauri_build::build()
+    tauri_build::try_build(
+        tauri_build::Attributes::new()
+            .app_manifest(tauri_build::AppManifest::new().commands(APP_COMMANDS)),
+    )
+    .expect("failed to run tauri-build");
 }
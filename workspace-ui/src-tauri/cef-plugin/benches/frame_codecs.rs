@@ -0,0 +1,70 @@
+//! Frame codec benchmark - encode time and bytes/frame on page-like frames
+//!
+//! Run with `cargo bench -p cef-plugin`. Sizes are printed once per scenario.
+
+use cef_plugin::FrameCodec;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+const WIDTH: u32 = 1280;
+const HEIGHT: u32 = 800;
+
+/// White page with text-like lines, scrolled down by `scroll` pixels
+fn page(scroll: u32, caret_visible: bool) -> Vec<u8> {
+    let mut data = Vec::with_capacity((WIDTH * HEIGHT * 4) as usize);
+    for y in 0..HEIGHT {
+        let line_y = y + scroll;
+        for x in 0..WIDTH {
+            let in_text = x > 80 && x < 900 && line_y % 24 < 14;
+            let ink = in_text && (x.wrapping_mul(7) ^ line_y.wrapping_mul(13)) % 9 < 3;
+            let caret = caret_visible && x == 400 && (300..316).contains(&y);
+            let pixel = if ink || caret {
+                [40, 40, 40, 255]
+            } else if x < 60 {
+                [245, 240, 235, 255] // Sidebar
+            } else {
+                [255, 255, 255, 255]
+            };
+            data.extend_from_slice(&pixel);
+        }
+    }
+    data
+}
+
+fn bench_codecs(c: &mut Criterion) {
+    let base = page(0, false);
+    let scenarios = [
+        ("caret_blink", page(0, true)),
+        ("scroll_24px", page(24, false)),
+        ("first_frame", base.clone()),
+    ];
+
+    let mut group = c.benchmark_group("frame_codecs");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(base.len() as u64));
+
+    for (name, frame) in &scenarios {
+        // The first frame has nothing to diff against
+        let previous = (*name != "first_frame").then_some(base.as_slice());
+
+        for codec in FrameCodec::ALL {
+            let encoded = codec.encode(frame, previous);
+            println!(
+                "{:<12} {:<14} {:>9} bytes/frame ({:.2}% of raw)",
+                name,
+                codec.format(),
+                encoded.len(),
+                encoded.len() as f64 * 100.0 / frame.len() as f64
+            );
+
+            group.bench_with_input(BenchmarkId::new(codec.format(), name), frame, |b, frame| {
+                b.iter(|| codec.encode(black_box(frame), previous))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_codecs);
+criterion_main!(benches);
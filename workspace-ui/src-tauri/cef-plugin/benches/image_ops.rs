@@ -0,0 +1,94 @@
+//! Image tooling benchmark - resize filters, PNG/JPEG encoding and cropping
+//!
+//! Run with `cargo bench -p cef-plugin --bench image_ops`. Encoded sizes are
+//! printed once per format.
+
+use cef_plugin::{
+    crop_frame, encode_image, resize_bgra, DirtyRect, ImageFormat, ResizeFilter,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::hint::black_box;
+
+const WIDTH: u32 = 1280;
+const HEIGHT: u32 = 800;
+
+/// Page-like frame: white background, text-like lines and a colored header
+fn page() -> Vec<u8> {
+    let mut data = Vec::with_capacity((WIDTH * HEIGHT * 4) as usize);
+    for y in 0..HEIGHT {
+        for x in 0..WIDTH {
+            let in_text = x > 80 && x < 900 && y % 24 < 14;
+            let ink = in_text && (x.wrapping_mul(7) ^ y.wrapping_mul(13)) % 9 < 3;
+            let pixel = if y < 64 {
+                [180, 90, 30, 255] // Header
+            } else if ink {
+                [40, 40, 40, 255]
+            } else {
+                [255, 255, 255, 255]
+            };
+            data.extend_from_slice(&pixel);
+        }
+    }
+    data
+}
+
+fn bench_resize(c: &mut Criterion) {
+    let frame = page();
+
+    let mut group = c.benchmark_group("resize");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(frame.len() as u64));
+
+    for filter in [ResizeFilter::Box, ResizeFilter::Lanczos3] {
+        for (tw, th) in [(480, 300), (160, 100)] {
+            let id = BenchmarkId::new(format!("{:?}", filter), format!("{}x{}", tw, th));
+            group.bench_with_input(id, &frame, |b, frame| {
+                b.iter(|| resize_bgra(black_box(frame), WIDTH, HEIGHT, tw, th, filter))
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_encode(c: &mut Criterion) {
+    let frame = page();
+    let thumbnail = resize_bgra(&frame, WIDTH, HEIGHT, 480, 300, ResizeFilter::Box);
+    let formats = [
+        ("png", ImageFormat::Png),
+        ("jpeg_q80", ImageFormat::Jpeg { quality: 80 }),
+    ];
+
+    let mut group = c.benchmark_group("encode");
+    group.sample_size(20);
+
+    for (name, format) in formats {
+        for (size, pixels, w, h) in [("full", &frame, WIDTH, HEIGHT), ("thumbnail", &thumbnail, 480, 300)] {
+            let encoded = encode_image(pixels, w, h, format).unwrap();
+            println!("{:<9} {:<9} {:>8} bytes", name, size, encoded.len());
+
+            group.throughput(Throughput::Bytes(pixels.len() as u64));
+            group.bench_with_input(BenchmarkId::new(name, size), pixels, |b, pixels| {
+                b.iter(|| encode_image(black_box(pixels), w, h, format).unwrap())
+            });
+        }
+    }
+
+    group.finish();
+}
+
+fn bench_crop(c: &mut Criterion) {
+    let frame = cef_plugin::FrameData {
+        browser_id: "bench".to_string(),
+        width: WIDTH,
+        height: HEIGHT,
+        format: cef_plugin::FrameCodec::Raw.format().to_string(),
+        data: page(),
+    };
+    let rect = DirtyRect { x: 100, y: 64, width: 800, height: 600 };
+
+    c.bench_function("crop_800x600", |b| b.iter(|| crop_frame(black_box(&frame), &rect).unwrap()));
+}
+
+criterion_group!(benches, bench_resize, bench_encode, bench_crop);
+criterion_main!(benches);
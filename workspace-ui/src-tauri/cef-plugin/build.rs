@@ -0,0 +1,32 @@
+const COMMANDS: &[&str] = &[
+    "cef_init",
+    "cef_shutdown",
+    "cef_create_browser",
+    "cef_close_browser",
+    "cef_update_bounds",
+    "cef_navigate",
+    "cef_go_back",
+    "cef_go_forward",
+    "cef_reload",
+    "cef_stop",
+    "cef_get_frame",
+    "cef_get_frame_delta",
+    "cef_get_all_frames",
+    "cef_subscribe_frames",
+    "cef_unsubscribe_frames",
+    "cef_ack_frame",
+    "cef_send_mouse_event",
+    "cef_send_key_event",
+    "cef_send_dom_mouse_event",
+    "cef_send_dom_key_event",
+    "cef_focus_browser",
+    "cef_set_visibility",
+    "cef_set_viewport",
+    "cef_set_frame_rate",
+    "cef_capture_thumbnail",
+    "cef_list_browsers",
+];
+
+fn main() {
+    tauri_plugin::Builder::new(COMMANDS).build();
+}
@@ -0,0 +1,559 @@
+//! Browser Backends - Pluggable rendering implementations for OSR browsers
+//!
+//! `BrowserManager` is generic over a `BrowserBackend`. The real CEF backend
+//! will live behind this trait; until then `PlaceholderBackend` draws a
+//! gradient and `TestBackend` produces deterministic, scriptable output so the
+//! manager and transfer code can run on a headless machine.
+
+use crate::navigation_filter::{NavigationFilterSlot, NavigationKind, NavigationRequest};
+use crate::request_filter::{RequestFilterSlot, ResourceRequest, ResourceType};
+use crate::transfer::DirtyRect;
+use crate::{KeyEvent, MouseEvent};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// A rendered frame (or part of one) reported by the backend
+#[derive(Debug, Clone)]
+pub struct PaintEvent {
+    pub width: u32,
+    pub height: u32,
+    /// Regions that changed since the previous paint
+    pub dirty_rects: Vec<DirtyRect>,
+    /// Full BGRA buffer of `width * height * 4` bytes
+    pub buffer: Vec<u8>,
+}
+
+/// Lifecycle notifications reported by the backend.
+/// Emitted to the frontend as `cef://<type>`, e.g. `cef://load-end`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum BackendEvent {
+    LoadStart { url: String },
+    LoadEnd { url: String, http_status: i32 },
+    LoadError { url: String, error_code: i32, error_text: String },
+    TitleChanged { title: String },
+    UrlChanged { url: String },
+    FaviconChanged { urls: Vec<String> },
+    /// Back/forward availability changed (CEF `OnLoadingStateChange`)
+    HistoryChanged { can_go_back: bool, can_go_forward: bool },
+    /// The render process terminated
+    Crashed { reason: String },
+}
+
+impl BackendEvent {
+    /// Event name without the `cef://` prefix
+    pub fn name(&self) -> &'static str {
+        match self {
+            BackendEvent::LoadStart { .. } => "load-start",
+            BackendEvent::LoadEnd { .. } => "load-end",
+            BackendEvent::LoadError { .. } => "load-error",
+            BackendEvent::TitleChanged { .. } => "title-changed",
+            BackendEvent::UrlChanged { .. } => "url-changed",
+            BackendEvent::FaviconChanged { .. } => "favicon-changed",
+            BackendEvent::HistoryChanged { .. } => "history-changed",
+            BackendEvent::Crashed { .. } => "crashed",
+        }
+    }
+}
+
+/// Rendering backend for off-screen browsers
+pub trait BrowserBackend: Send + Sync + 'static {
+    /// Per-browser state owned by the backend (e.g. the CEF browser handle)
+    type Handle: Send + Sync + 'static;
+
+    fn create(&self, id: &str, url: &str, width: u32, height: u32) -> Result<Self::Handle, String>;
+    fn navigate(&self, handle: &mut Self::Handle, url: &str) -> Result<(), String>;
+    fn go_back(&self, handle: &mut Self::Handle) -> Result<(), String>;
+    fn go_forward(&self, handle: &mut Self::Handle) -> Result<(), String>;
+    /// `ignore_cache` revalidates every resource (hard reload)
+    fn reload(&self, handle: &mut Self::Handle, ignore_cache: bool) -> Result<(), String>;
+    fn stop(&self, handle: &mut Self::Handle) -> Result<(), String>;
+    fn resize(&self, handle: &mut Self::Handle, width: u32, height: u32) -> Result<(), String>;
+    fn send_mouse_event(&self, handle: &mut Self::Handle, event: &MouseEvent) -> Result<(), String>;
+    fn send_key_event(&self, handle: &mut Self::Handle, event: &KeyEvent) -> Result<(), String>;
+    fn set_focus(&self, handle: &mut Self::Handle, focused: bool) -> Result<(), String>;
+    /// Maximum paints per second while the browser is visible
+    fn set_frame_rate(&self, handle: &mut Self::Handle, fps: u32) -> Result<(), String>;
+    /// Hidden browsers stop painting until shown again
+    fn set_hidden(&self, handle: &mut Self::Handle, hidden: bool) -> Result<(), String>;
+    /// Returns the next pending paint, if the browser repainted since the last poll
+    fn poll_paint(&self, handle: &mut Self::Handle) -> Option<PaintEvent>;
+    /// Drains pending lifecycle events
+    fn poll_events(&self, handle: &mut Self::Handle) -> Vec<BackendEvent>;
+    fn close(&self, handle: &mut Self::Handle) -> Result<(), String>;
+    /// Host filter to consult before each resource load. Backends that
+    /// load nothing (placeholder) can ignore it.
+    fn set_request_filter(&self, _filter: RequestFilterSlot) {}
+    /// Host filter to consult before navigations started by the page itself
+    /// (links, redirects, frames). Host-requested loads are checked by the manager.
+    fn set_navigation_filter(&self, _filter: NavigationFilterSlot) {}
+    /// Called once when the manager shuts down
+    fn shutdown(&self) {}
+}
+
+// ============================================================================
+// PLACEHOLDER BACKEND
+// ============================================================================
+
+/// Draws a static gradient (used until the CEF bindings are wired up)
+#[derive(Default)]
+pub struct PlaceholderBackend;
+
+pub struct PlaceholderHandle {
+    width: u32,
+    height: u32,
+    needs_paint: bool,
+    events: Vec<BackendEvent>,
+}
+
+impl PlaceholderBackend {
+    fn gradient(width: u32, height: u32) -> Vec<u8> {
+        let mut buffer = vec![0u8; (width * height * 4) as usize];
+        for y in 0..height {
+            for x in 0..width {
+                let idx = ((y * width + x) * 4) as usize;
+                // BGRA format
+                buffer[idx] = (x * 255 / width) as u8; // B
+                buffer[idx + 1] = (y * 255 / height) as u8; // G
+                buffer[idx + 2] = 100; // R
+                buffer[idx + 3] = 255; // A
+            }
+        }
+        buffer
+    }
+}
+
+impl BrowserBackend for PlaceholderBackend {
+    type Handle = PlaceholderHandle;
+
+    fn create(&self, _id: &str, url: &str, width: u32, height: u32) -> Result<Self::Handle, String> {
+        // TODO: Create actual CEF browser with off-screen rendering
+        // 1. Create WindowInfo with windowless_rendering_enabled = true
+        // 2. Create BrowserSettings with windowless_frame_rate = 60
+        // 3. Create RenderHandler that captures OnPaint callbacks
+        // 4. Create browser via browser_host_create_browser_sync
+        Ok(PlaceholderHandle {
+            width,
+            height,
+            needs_paint: true,
+            events: vec![BackendEvent::LoadStart { url: url.to_string() }],
+        })
+    }
+
+    fn navigate(&self, handle: &mut Self::Handle, url: &str) -> Result<(), String> {
+        // TODO: Call browser.get_main_frame().load_url(url)
+        handle.events.push(BackendEvent::LoadStart { url: url.to_string() });
+        Ok(())
+    }
+
+    fn go_back(&self, _handle: &mut Self::Handle) -> Result<(), String> {
+        // TODO: Call browser.go_back() if browser.can_go_back()
+        Ok(())
+    }
+
+    fn go_forward(&self, _handle: &mut Self::Handle) -> Result<(), String> {
+        // TODO: Call browser.go_forward() if browser.can_go_forward()
+        Ok(())
+    }
+
+    fn reload(&self, _handle: &mut Self::Handle, _ignore_cache: bool) -> Result<(), String> {
+        // TODO: Call browser.reload() or browser.reload_ignore_cache()
+        Ok(())
+    }
+
+    fn stop(&self, _handle: &mut Self::Handle) -> Result<(), String> {
+        // TODO: Call browser.stop_load()
+        Ok(())
+    }
+
+    fn resize(&self, handle: &mut Self::Handle, width: u32, height: u32) -> Result<(), String> {
+        // TODO: Notify CEF browser of resize via host.was_resized()
+        handle.width = width;
+        handle.height = height;
+        handle.needs_paint = true;
+        Ok(())
+    }
+
+    fn send_mouse_event(&self, _handle: &mut Self::Handle, event: &MouseEvent) -> Result<(), String> {
+        // TODO: Forward to CEF via browser.host():
+        // Move/Leave -> send_mouse_move_event(mouse_leave), Down/Up ->
+        // send_mouse_click_event(button, mouse_up, click_count), Wheel ->
+        // send_mouse_wheel_event(delta_x, delta_y); modifiers are cef_event_flags_t
+        log::trace!(
+            "Mouse event: {:?} {:?} at ({}, {}) x{}",
+            event.event_type, event.button, event.x, event.y, event.click_count
+        );
+        Ok(())
+    }
+
+    fn send_key_event(&self, _handle: &mut Self::Handle, event: &KeyEvent) -> Result<(), String> {
+        // TODO: Forward to CEF via browser.host().send_key_event()
+        // (Down -> KEYEVENT_RAWKEYDOWN, key_code is the windows_key_code)
+        log::trace!("Key event: {:?} {:#x}", event.event_type, event.key_code);
+        Ok(())
+    }
+
+    fn set_focus(&self, _handle: &mut Self::Handle, _focused: bool) -> Result<(), String> {
+        // TODO: Call browser.host().set_focus(focused)
+        Ok(())
+    }
+
+    fn set_frame_rate(&self, _handle: &mut Self::Handle, _fps: u32) -> Result<(), String> {
+        // TODO: Call browser.host().set_windowless_frame_rate(fps)
+        Ok(())
+    }
+
+    fn set_hidden(&self, _handle: &mut Self::Handle, _hidden: bool) -> Result<(), String> {
+        // TODO: Call browser.host().was_hidden(hidden)
+        Ok(())
+    }
+
+    fn poll_paint(&self, handle: &mut Self::Handle) -> Option<PaintEvent> {
+        if !handle.needs_paint {
+            return None;
+        }
+        handle.needs_paint = false;
+
+        Some(PaintEvent {
+            width: handle.width,
+            height: handle.height,
+            dirty_rects: vec![DirtyRect::full(handle.width, handle.height)],
+            buffer: Self::gradient(handle.width, handle.height),
+        })
+    }
+
+    fn poll_events(&self, handle: &mut Self::Handle) -> Vec<BackendEvent> {
+        // The placeholder "finishes" loading immediately
+        let mut events = std::mem::take(&mut handle.events);
+        let finished: Vec<BackendEvent> = events
+            .iter()
+            .filter_map(|event| match event {
+                BackendEvent::LoadStart { url } => Some(BackendEvent::LoadEnd {
+                    url: url.clone(),
+                    http_status: 200,
+                }),
+                _ => None,
+            })
+            .collect();
+        events.extend(finished);
+        events
+    }
+
+    fn close(&self, _handle: &mut Self::Handle) -> Result<(), String> {
+        // TODO: Close actual CEF browser
+        Ok(())
+    }
+
+    // TODO: Keep the slot and check it in CefResourceRequestHandler::OnBeforeResourceLoad
+    // (return RV_CANCEL when `should_block` is true)
+
+    fn shutdown(&self) {
+        // TODO: Call cef::shutdown()
+    }
+}
+
+// ============================================================================
+// TEST BACKEND
+// ============================================================================
+
+/// Calls recorded by `TestBackend`, in order
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestCall {
+    Create { id: String, url: String, width: u32, height: u32 },
+    Navigate { id: String, url: String },
+    GoBack { id: String },
+    GoForward { id: String },
+    Reload { id: String, ignore_cache: bool },
+    Stop { id: String },
+    Resize { id: String, width: u32, height: u32 },
+    MouseEvent { id: String, event: MouseEvent },
+    KeyEvent { id: String, event: KeyEvent },
+    Focus { id: String, focused: bool },
+    FrameRate { id: String, fps: u32 },
+    Hidden { id: String, hidden: bool },
+    Close { id: String },
+}
+
+#[derive(Default)]
+struct TestScript {
+    calls: Vec<TestCall>,
+    /// Extra events to emit per browser id, queued by tests
+    queued_events: HashMap<String, VecDeque<BackendEvent>>,
+    request_filter: RequestFilterSlot,
+    navigation_filter: NavigationFilterSlot,
+}
+
+/// Deterministic backend for tests.
+///
+/// Every navigation (including back/forward and reload) emits `LoadStart`,
+/// `UrlChanged`, `HistoryChanged` and `LoadEnd` (status 200) on the next poll,
+/// and every paint produces a frame whose pixels depend only on the frame
+/// number and position, so tests can assert exact bytes.
+#[derive(Default, Clone)]
+pub struct TestBackend {
+    script: Arc<Mutex<TestScript>>,
+}
+
+pub struct TestHandle {
+    id: String,
+    width: u32,
+    height: u32,
+    frame_number: u32,
+    needs_paint: bool,
+    pending: VecDeque<BackendEvent>,
+    /// Visited URLs, `history[history_index]` is current
+    history: Vec<String>,
+    history_index: usize,
+}
+
+impl TestHandle {
+    /// New navigation: drops forward entries
+    fn load(&mut self, url: &str) {
+        self.history.truncate(self.history_index + 1);
+        self.history.push(url.to_string());
+        self.history_index = self.history.len() - 1;
+        self.emit_load();
+    }
+
+    /// Move within the history; `false` if there is no entry at the target
+    fn go(&mut self, delta: isize) -> bool {
+        match self.history_index.checked_add_signed(delta) {
+            Some(index) if index < self.history.len() => {
+                self.history_index = index;
+                self.emit_load();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn emit_load(&mut self) {
+        let url = self.history[self.history_index].clone();
+        self.pending.push_back(BackendEvent::LoadStart { url: url.clone() });
+        self.pending.push_back(BackendEvent::UrlChanged { url: url.clone() });
+        self.pending.push_back(BackendEvent::HistoryChanged {
+            can_go_back: self.history_index > 0,
+            can_go_forward: self.history_index + 1 < self.history.len(),
+        });
+        self.pending.push_back(BackendEvent::LoadEnd { url, http_status: 200 });
+        self.needs_paint = true;
+    }
+}
+
+impl TestBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All backend calls so far
+    pub fn calls(&self) -> Vec<TestCall> {
+        self.script.lock().calls.clone()
+    }
+
+    /// Queue an event to be returned by the next `poll_events` for `id`
+    pub fn queue_event(&self, id: &str, event: BackendEvent) {
+        self.script
+            .lock()
+            .queued_events
+            .entry(id.to_string())
+            .or_default()
+            .push_back(event);
+    }
+
+    /// The exact BGRA frame the backend paints for a given frame number
+    pub fn expected_frame(frame_number: u32, width: u32, height: u32) -> Vec<u8> {
+        let mut buffer = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                buffer.push((x + frame_number) as u8); // B
+                buffer.push((y + frame_number) as u8); // G
+                buffer.push(frame_number as u8); // R
+                buffer.push(255); // A
+            }
+        }
+        buffer
+    }
+
+    /// Simulate a resource load; `false` if the request filter cancelled it
+    pub fn load_resource(
+        &self,
+        id: &str,
+        url: &str,
+        frame_url: Option<&str>,
+        resource_type: ResourceType,
+    ) -> bool {
+        let filter = self.script.lock().request_filter.clone();
+        !filter.should_block(&ResourceRequest {
+            browser_id: id,
+            url,
+            frame_url,
+            resource_type,
+        })
+    }
+
+    /// Simulate a navigation started by the page; `false` if the navigation
+    /// filter cancelled it
+    pub fn navigate_in_page(&self, id: &str, url: &str, kind: NavigationKind) -> bool {
+        let filter = self.script.lock().navigation_filter.clone();
+        filter.allow(&NavigationRequest {
+            browser_id: id,
+            url,
+            kind,
+        })
+    }
+
+    fn record(&self, call: TestCall) {
+        self.script.lock().calls.push(call);
+    }
+}
+
+impl BrowserBackend for TestBackend {
+    type Handle = TestHandle;
+
+    fn create(&self, id: &str, url: &str, width: u32, height: u32) -> Result<Self::Handle, String> {
+        self.record(TestCall::Create {
+            id: id.to_string(),
+            url: url.to_string(),
+            width,
+            height,
+        });
+
+        let mut handle = TestHandle {
+            id: id.to_string(),
+            width,
+            height,
+            frame_number: 0,
+            needs_paint: true,
+            pending: VecDeque::new(),
+            history: Vec::new(),
+            history_index: 0,
+        };
+        handle.load(url);
+        Ok(handle)
+    }
+
+    fn navigate(&self, handle: &mut Self::Handle, url: &str) -> Result<(), String> {
+        self.record(TestCall::Navigate {
+            id: handle.id.clone(),
+            url: url.to_string(),
+        });
+        handle.load(url);
+        Ok(())
+    }
+
+    fn go_back(&self, handle: &mut Self::Handle) -> Result<(), String> {
+        self.record(TestCall::GoBack { id: handle.id.clone() });
+        handle.go(-1);
+        Ok(())
+    }
+
+    fn go_forward(&self, handle: &mut Self::Handle) -> Result<(), String> {
+        self.record(TestCall::GoForward { id: handle.id.clone() });
+        handle.go(1);
+        Ok(())
+    }
+
+    fn reload(&self, handle: &mut Self::Handle, ignore_cache: bool) -> Result<(), String> {
+        self.record(TestCall::Reload {
+            id: handle.id.clone(),
+            ignore_cache,
+        });
+        handle.go(0);
+        Ok(())
+    }
+
+    fn stop(&self, handle: &mut Self::Handle) -> Result<(), String> {
+        self.record(TestCall::Stop { id: handle.id.clone() });
+        Ok(())
+    }
+
+    fn resize(&self, handle: &mut Self::Handle, width: u32, height: u32) -> Result<(), String> {
+        self.record(TestCall::Resize {
+            id: handle.id.clone(),
+            width,
+            height,
+        });
+        handle.width = width;
+        handle.height = height;
+        handle.needs_paint = true;
+        Ok(())
+    }
+
+    fn send_mouse_event(&self, handle: &mut Self::Handle, event: &MouseEvent) -> Result<(), String> {
+        self.record(TestCall::MouseEvent {
+            id: handle.id.clone(),
+            event: event.clone(),
+        });
+        Ok(())
+    }
+
+    fn send_key_event(&self, handle: &mut Self::Handle, event: &KeyEvent) -> Result<(), String> {
+        self.record(TestCall::KeyEvent {
+            id: handle.id.clone(),
+            event: event.clone(),
+        });
+        Ok(())
+    }
+
+    fn set_focus(&self, handle: &mut Self::Handle, focused: bool) -> Result<(), String> {
+        self.record(TestCall::Focus {
+            id: handle.id.clone(),
+            focused,
+        });
+        Ok(())
+    }
+
+    fn set_frame_rate(&self, handle: &mut Self::Handle, fps: u32) -> Result<(), String> {
+        self.record(TestCall::FrameRate {
+            id: handle.id.clone(),
+            fps,
+        });
+        Ok(())
+    }
+
+    fn set_hidden(&self, handle: &mut Self::Handle, hidden: bool) -> Result<(), String> {
+        self.record(TestCall::Hidden {
+            id: handle.id.clone(),
+            hidden,
+        });
+        Ok(())
+    }
+
+    fn poll_paint(&self, handle: &mut Self::Handle) -> Option<PaintEvent> {
+        if !handle.needs_paint {
+            return None;
+        }
+        handle.needs_paint = false;
+        handle.frame_number += 1;
+
+        Some(PaintEvent {
+            width: handle.width,
+            height: handle.height,
+            dirty_rects: vec![DirtyRect::full(handle.width, handle.height)],
+            buffer: Self::expected_frame(handle.frame_number, handle.width, handle.height),
+        })
+    }
+
+    fn poll_events(&self, handle: &mut Self::Handle) -> Vec<BackendEvent> {
+        let mut events: Vec<BackendEvent> = handle.pending.drain(..).collect();
+        if let Some(queued) = self.script.lock().queued_events.get_mut(&handle.id) {
+            events.extend(queued.drain(..));
+        }
+        events
+    }
+
+    fn close(&self, handle: &mut Self::Handle) -> Result<(), String> {
+        self.record(TestCall::Close { id: handle.id.clone() });
+        Ok(())
+    }
+
+    fn set_request_filter(&self, filter: RequestFilterSlot) {
+        self.script.lock().request_filter = filter;
+    }
+
+    fn set_navigation_filter(&self, filter: NavigationFilterSlot) {
+        self.script.lock().navigation_filter = filter;
+    }
+}
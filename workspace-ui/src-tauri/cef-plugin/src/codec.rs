@@ -0,0 +1,261 @@
+//! Frame Codecs - Compression of BGRA pixel data for transfer
+//!
+//! `Raw` sends pixels untouched, `Lz4` compresses them with LZ4 (block format,
+//! size prepended) and `XorRle` XORs the pixels against what the client
+//! already shows and run-length encodes the result. Unchanged pixels XOR to
+//! zero, so caret blinks or a few changed lines of text shrink to a few bytes.
+
+use serde::{Deserialize, Serialize};
+
+/// Pixel encoding negotiated per subscription
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FrameCodec {
+    #[default]
+    Raw,
+    Lz4,
+    XorRle,
+}
+
+/// Runs shorter than this are cheaper to send as literals
+const MIN_RUN: usize = 3;
+
+/// RLE token tags
+const TAG_RUN: u8 = 0;
+const TAG_LITERAL: u8 = 1;
+
+impl FrameCodec {
+    pub const ALL: [FrameCodec; 3] = [FrameCodec::Raw, FrameCodec::Lz4, FrameCodec::XorRle];
+
+    /// Format string reported in `FrameData.format` / `FrameDelta.format`
+    pub fn format(&self) -> &'static str {
+        match self {
+            FrameCodec::Raw => "BGRA8",
+            FrameCodec::Lz4 => "BGRA8+LZ4",
+            FrameCodec::XorRle => "BGRA8+XOR-RLE",
+        }
+    }
+
+    pub fn from_format(format: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|codec| codec.format() == format)
+    }
+
+    /// Format byte used in binary frame packets
+    pub fn code(&self) -> u8 {
+        match self {
+            FrameCodec::Raw => 0,
+            FrameCodec::Lz4 => 1,
+            FrameCodec::XorRle => 2,
+        }
+    }
+
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|codec| codec.code() == code)
+    }
+
+    /// Pick the first format the client listed that we support (`Raw` otherwise)
+    pub fn negotiate(accepted: &[String]) -> Self {
+        accepted
+            .iter()
+            .find_map(|format| Self::from_format(format))
+            .unwrap_or_default()
+    }
+
+    /// Encode BGRA pixels. `previous` is the client's copy of the same region;
+    /// it is only used by `XorRle` and ignored if the length does not match.
+    pub fn encode(&self, pixels: &[u8], previous: Option<&[u8]>) -> Vec<u8> {
+        match self {
+            FrameCodec::Raw => pixels.to_vec(),
+            FrameCodec::Lz4 => lz4_flex::block::compress_prepend_size(pixels),
+            FrameCodec::XorRle => match previous.filter(|p| p.len() == pixels.len()) {
+                Some(previous) => rle_encode(&xor(pixels, previous)),
+                None => rle_encode(pixels),
+            },
+        }
+    }
+
+    /// Inverse of `encode`, given the same `previous`
+    pub fn decode(&self, data: &[u8], previous: Option<&[u8]>) -> Result<Vec<u8>, String> {
+        match self {
+            FrameCodec::Raw => Ok(data.to_vec()),
+            FrameCodec::Lz4 => lz4_flex::block::decompress_size_prepended(data)
+                .map_err(|e| format!("LZ4 decode failed: {}", e)),
+            FrameCodec::XorRle => {
+                let decoded = rle_decode(data)?;
+                Ok(match previous.filter(|p| p.len() == decoded.len()) {
+                    Some(previous) => xor(&decoded, previous),
+                    None => decoded,
+                })
+            }
+        }
+    }
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(x, y)| x ^ y).collect()
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: usize) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(data: &[u8], pos: &mut usize) -> Result<usize, String> {
+    let mut value = 0usize;
+    let mut shift = 0;
+    loop {
+        let byte = *data.get(*pos).ok_or("RLE stream truncated")?;
+        *pos += 1;
+        if shift >= usize::BITS {
+            return Err("RLE length overflow".to_string());
+        }
+        value |= ((byte & 0x7F) as usize) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+/// Run-length encode whole pixels.
+///
+/// Tokens: `TAG_RUN count pixel` repeats one pixel `count` times,
+/// `TAG_LITERAL count pixels...` copies `count` pixels. Counts are LEB128.
+fn rle_encode(pixels: &[u8]) -> Vec<u8> {
+    let px: Vec<u32> = pixels
+        .chunks_exact(4)
+        .map(|p| u32::from_le_bytes([p[0], p[1], p[2], p[3]]))
+        .collect();
+    let mut out = Vec::new();
+    let mut literal_start = 0;
+    let mut i = 0;
+
+    let flush_literal = |out: &mut Vec<u8>, from: usize, to: usize| {
+        if to > from {
+            out.push(TAG_LITERAL);
+            write_varint(out, to - from);
+            out.extend_from_slice(&pixels[from * 4..to * 4]);
+        }
+    };
+
+    while i < px.len() {
+        let run = px[i..].iter().take_while(|p| **p == px[i]).count();
+
+        if run >= MIN_RUN {
+            flush_literal(&mut out, literal_start, i);
+            out.push(TAG_RUN);
+            write_varint(&mut out, run);
+            out.extend_from_slice(&px[i].to_le_bytes());
+            literal_start = i + run;
+        }
+        i += run;
+    }
+    flush_literal(&mut out, literal_start, px.len());
+
+    out
+}
+
+fn rle_decode(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut pos = 0;
+
+    while pos < data.len() {
+        let tag = data[pos];
+        pos += 1;
+        let count = read_varint(data, &mut pos)?;
+
+        match tag {
+            TAG_RUN => {
+                let pixel = data.get(pos..pos + 4).ok_or("RLE run truncated")?;
+                pos += 4;
+                for _ in 0..count {
+                    out.extend_from_slice(pixel);
+                }
+            }
+            TAG_LITERAL => {
+                let len = count.checked_mul(4).ok_or("RLE length overflow")?;
+                let end = pos.checked_add(len).ok_or("RLE length overflow")?;
+                let pixels = data.get(pos..end).ok_or("RLE literal truncated")?;
+                out.extend_from_slice(pixels);
+                pos = end;
+            }
+            other => return Err(format!("Unknown RLE tag {}", other)),
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// White page with a few dark "text" pixels
+    fn page(width: u32, height: u32, shift: u32) -> Vec<u8> {
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let ink = y % 12 < 8 && (x * 7 + y * 3 + shift) % 11 < 3;
+                let v = if ink { 30 } else { 255 };
+                data.extend_from_slice(&[v, v, v, 255]);
+            }
+        }
+        data
+    }
+
+    #[test]
+    fn test_roundtrip_all_codecs() {
+        let previous = page(64, 48, 0);
+        let current = page(64, 48, 5);
+
+        for codec in FrameCodec::ALL {
+            let encoded = codec.encode(&current, Some(&previous));
+            let decoded = codec.decode(&encoded, Some(&previous)).unwrap();
+            assert_eq!(decoded, current, "{:?}", codec);
+        }
+    }
+
+    #[test]
+    fn test_xor_rle_without_previous() {
+        let current = page(32, 32, 1);
+        let encoded = FrameCodec::XorRle.encode(&current, None);
+        assert_eq!(FrameCodec::XorRle.decode(&encoded, None).unwrap(), current);
+    }
+
+    #[test]
+    fn test_xor_rle_unchanged_frame_is_tiny() {
+        let frame = page(200, 100, 0);
+        let encoded = FrameCodec::XorRle.encode(&frame, Some(&frame));
+        // One run token: tag + varint(20000) + pixel
+        assert_eq!(encoded.len(), 1 + 3 + 4);
+    }
+
+    #[test]
+    fn test_rle_edge_cases() {
+        for pixels in [
+            vec![],
+            vec![1, 2, 3, 4],
+            vec![1, 2, 3, 4, 1, 2, 3, 4],
+            [[9u8; 4]; 300].concat(),
+        ] {
+            assert_eq!(rle_decode(&rle_encode(&pixels)).unwrap(), pixels);
+        }
+        assert!(rle_decode(&[TAG_LITERAL, 2, 0, 0]).is_err());
+        assert!(rle_decode(&[7, 1]).is_err());
+    }
+
+    #[test]
+    fn test_negotiate() {
+        let accepted = vec!["BGRA8+ZSTD".to_string(), "BGRA8+LZ4".to_string()];
+        assert_eq!(FrameCodec::negotiate(&accepted), FrameCodec::Lz4);
+        assert_eq!(FrameCodec::negotiate(&[]), FrameCodec::Raw);
+
+        for codec in FrameCodec::ALL {
+            assert_eq!(FrameCodec::from_code(codec.code()), Some(codec));
+            assert_eq!(FrameCodec::from_format(codec.format()), Some(codec));
+        }
+    }
+}
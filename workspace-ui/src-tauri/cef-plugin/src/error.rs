@@ -0,0 +1,124 @@
+//! Command errors - serialized as `{ code, message, context }`, the same
+//! shape the host app uses for its own commands
+//!
+//! Backend and renderer internals keep returning `String`; those convert into
+//! `Error::Backend` at the manager boundary.
+
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+
+/// Stable error code the frontend can match on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NotInitialized,
+    NotFound,
+    AlreadyExists,
+    InvalidInput,
+    /// The host's navigation filter rejected the URL
+    Blocked,
+    Internal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// `cef_init` has not been called (or CEF was shut down)
+    NotInitialized,
+    BrowserNotFound(String),
+    BrowserExists(String),
+    SubscriptionNotFound(u32),
+    /// Arguments the command can't work with (codec, event data, ...)
+    InvalidInput(String),
+    /// Navigation to this URL rejected by the host's `NavigationFilter`
+    NavigationBlocked(String),
+    /// Failure inside the browser backend or renderer
+    Backend(String),
+}
+
+impl Error {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Error::NotInitialized => ErrorCode::NotInitialized,
+            Error::BrowserNotFound(_) | Error::SubscriptionNotFound(_) => ErrorCode::NotFound,
+            Error::BrowserExists(_) => ErrorCode::AlreadyExists,
+            Error::InvalidInput(_) => ErrorCode::InvalidInput,
+            Error::NavigationBlocked(_) => ErrorCode::Blocked,
+            Error::Backend(_) => ErrorCode::Internal,
+        }
+    }
+
+    /// Machine-readable details, e.g. which browser was missing
+    pub fn context(&self) -> serde_json::Value {
+        match self {
+            Error::BrowserNotFound(id) | Error::BrowserExists(id) => {
+                serde_json::json!({ "browser_id": id })
+            }
+            Error::SubscriptionNotFound(id) => serde_json::json!({ "subscription_id": id }),
+            Error::NavigationBlocked(url) => serde_json::json!({ "url": url }),
+            _ => serde_json::Value::Null,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotInitialized => write!(f, "CEF not initialized"),
+            Error::BrowserNotFound(id) => write!(f, "Browser '{}' not found", id),
+            Error::BrowserExists(id) => write!(f, "Browser '{}' already exists", id),
+            Error::SubscriptionNotFound(id) => write!(f, "Subscription {} not found", id),
+            Error::NavigationBlocked(url) => write!(f, "Navigation to {} blocked", url),
+            Error::InvalidInput(message) | Error::Backend(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<String> for Error {
+    fn from(message: String) -> Self {
+        Error::Backend(message)
+    }
+}
+
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("Error", 3)?;
+        error.serialize_field("code", &self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.serialize_field("context", &self.context())?;
+        error.end()
+    }
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_code_message_and_context() {
+        let value = serde_json::to_value(Error::BrowserNotFound("b1".into())).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "code": "not_found",
+                "message": "Browser 'b1' not found",
+                "context": { "browser_id": "b1" },
+            })
+        );
+
+        let value = serde_json::to_value(Error::NotInitialized).unwrap();
+        assert_eq!(value["code"], "not_initialized");
+        assert_eq!(value["context"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_backend_strings_become_internal_errors() {
+        let error: Error = "Renderer crashed".to_string().into();
+        assert_eq!(error.code(), ErrorCode::Internal);
+        assert_eq!(error.to_string(), "Renderer crashed");
+    }
+}
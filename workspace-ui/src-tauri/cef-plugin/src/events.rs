@@ -0,0 +1,81 @@
+//! Lifecycle Events - Forward browser events to the frontend
+//!
+//! A background thread pumps all browsers every `EVENT_INTERVAL` and hands
+//! each `BrowserEvent` to an emitter. The plugin emits them as
+//! `cef://<name>` (e.g. `cef://title-changed`) with the event as payload.
+
+use crate::backend::BrowserBackend;
+use crate::manager::BrowserManager;
+use crate::BrowserEvent;
+use parking_lot::RwLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Prefix of all event names emitted by the plugin
+pub const EVENT_PREFIX: &str = "cef://";
+
+/// How often browsers are polled for lifecycle events
+const EVENT_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Full event name, e.g. `cef://load-end`
+pub fn event_name(event: &BrowserEvent) -> String {
+    format!("{}{}", EVENT_PREFIX, event.event.name())
+}
+
+/// Pump events on a background thread until `active` is cleared or the
+/// manager shuts down
+pub fn spawn_event_pump<B, F>(
+    manager: Arc<RwLock<Option<BrowserManager<B>>>>,
+    active: Arc<AtomicBool>,
+    mut emit: F,
+) where
+    B: BrowserBackend,
+    F: FnMut(&BrowserEvent) + Send + 'static,
+{
+    std::thread::spawn(move || {
+        while active.load(Ordering::Relaxed) {
+            let events = {
+                let guard = manager.read();
+                let Some(manager) = guard.as_ref() else {
+                    break;
+                };
+                manager.take_events()
+            };
+
+            // Emit without holding the manager lock
+            for event in &events {
+                emit(event);
+            }
+            std::thread::sleep(EVENT_INTERVAL);
+        }
+
+        log::info!("Event pump stopped");
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::TestBackend;
+    use crate::CefBounds;
+    use std::sync::mpsc;
+
+    #[test]
+    fn test_pump_emits_prefixed_events() {
+        let manager = BrowserManager::with_backend(TestBackend::new());
+        let bounds = CefBounds { x: 0, y: 0, width: 100, height: 100 };
+        manager.create_browser("b1", "https://example.com", bounds).unwrap();
+
+        let manager = Arc::new(RwLock::new(Some(manager)));
+        let active = Arc::new(AtomicBool::new(true));
+        let (sender, receiver) = mpsc::channel();
+        spawn_event_pump(manager, active.clone(), move |event| {
+            sender.send((event.browser_id.clone(), event_name(event))).ok();
+        });
+
+        let first = receiver.recv_timeout(Duration::from_secs(2)).unwrap();
+        active.store(false, Ordering::Relaxed);
+        assert_eq!(first, ("b1".to_string(), "cef://load-start".to_string()));
+    }
+}
@@ -0,0 +1,165 @@
+//! Frame Rate Governor - Per-browser frame rate and visibility throttling
+//!
+//! Each browser has a target frame rate. Browsers outside the canvas viewport
+//! or behind a fullscreen browser are throttled to `THROTTLED_FRAME_RATE`,
+//! hidden browsers are suspended and don't paint at all. The governor only
+//! decides; `OsrBrowserInstance` forwards the result to the backend and skips
+//! paint polls that come too early.
+
+use crate::CefBounds;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Frame rate for new browsers
+pub const DEFAULT_FRAME_RATE: u32 = 60;
+
+/// Upper limit for `set_frame_rate` (CEF's windowless maximum)
+pub const MAX_FRAME_RATE: u32 = 60;
+
+/// Frame rate for browsers that are open but not visible on the canvas
+pub const THROTTLED_FRAME_RATE: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderState {
+    /// Painting at the target frame rate
+    Active,
+    /// Outside the viewport or occluded: ~1 fps
+    Throttled,
+    /// Hidden: no painting
+    Suspended,
+}
+
+#[derive(Debug, Clone)]
+pub struct FrameGovernor {
+    target_fps: u32,
+    visible: bool,
+    in_viewport: bool,
+    occluded: bool,
+    last_frame: Option<Instant>,
+}
+
+impl Default for FrameGovernor {
+    fn default() -> Self {
+        Self {
+            target_fps: DEFAULT_FRAME_RATE,
+            visible: true,
+            in_viewport: true,
+            occluded: false,
+            last_frame: None,
+        }
+    }
+}
+
+impl FrameGovernor {
+    pub fn state(&self) -> RenderState {
+        if !self.visible {
+            RenderState::Suspended
+        } else if !self.in_viewport || self.occluded {
+            RenderState::Throttled
+        } else {
+            RenderState::Active
+        }
+    }
+
+    /// Frame rate the backend should paint at (0 while suspended)
+    pub fn effective_fps(&self) -> u32 {
+        match self.state() {
+            RenderState::Active => self.target_fps,
+            RenderState::Throttled => THROTTLED_FRAME_RATE.min(self.target_fps),
+            RenderState::Suspended => 0,
+        }
+    }
+
+    pub fn target_fps(&self) -> u32 {
+        self.target_fps
+    }
+
+    pub fn set_target_fps(&mut self, fps: u32) {
+        self.target_fps = fps.clamp(1, MAX_FRAME_RATE);
+    }
+
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    /// Recompute viewport placement. `occluded` is true while another browser
+    /// is fullscreen.
+    pub fn set_placement(&mut self, bounds: &CefBounds, viewport: &CefBounds, occluded: bool) {
+        self.in_viewport = intersects(bounds, viewport);
+        self.occluded = occluded;
+    }
+
+    /// Whether a paint may be taken at `now`; records it if so.
+    ///
+    /// Active browsers are paced by the backend itself, so only throttled
+    /// browsers are rate-limited here.
+    pub fn frame_due(&mut self, now: Instant) -> bool {
+        let due = match self.state() {
+            RenderState::Active => true,
+            RenderState::Suspended => false,
+            RenderState::Throttled => self.last_frame.is_none_or(|last| {
+                now.duration_since(last) >= Duration::from_secs(1) / self.effective_fps()
+            }),
+        };
+
+        if due {
+            self.last_frame = Some(now);
+        }
+        due
+    }
+}
+
+fn intersects(a: &CefBounds, b: &CefBounds) -> bool {
+    a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> CefBounds {
+        CefBounds { x, y, width, height }
+    }
+
+    #[test]
+    fn test_state_from_visibility_and_viewport() {
+        let viewport = rect(0, 0, 1000, 800);
+        let mut governor = FrameGovernor::default();
+        assert_eq!(governor.state(), RenderState::Active);
+
+        governor.set_placement(&rect(1200, 0, 300, 200), &viewport, false);
+        assert_eq!(governor.state(), RenderState::Throttled);
+        assert_eq!(governor.effective_fps(), THROTTLED_FRAME_RATE);
+
+        governor.set_placement(&rect(900, 700, 300, 200), &viewport, true);
+        assert_eq!(governor.state(), RenderState::Throttled);
+
+        governor.set_placement(&rect(900, 700, 300, 200), &viewport, false);
+        assert_eq!(governor.state(), RenderState::Active);
+
+        governor.set_visible(false);
+        assert_eq!(governor.state(), RenderState::Suspended);
+        assert_eq!(governor.effective_fps(), 0);
+    }
+
+    #[test]
+    fn test_throttled_frames_are_paced() {
+        let mut governor = FrameGovernor::default();
+        governor.set_placement(&rect(-500, 0, 100, 100), &rect(0, 0, 100, 100), false);
+
+        let start = Instant::now();
+        assert!(governor.frame_due(start));
+        assert!(!governor.frame_due(start + Duration::from_millis(500)));
+        assert!(governor.frame_due(start + Duration::from_millis(1000)));
+    }
+
+    #[test]
+    fn test_target_fps_is_clamped() {
+        let mut governor = FrameGovernor::default();
+        governor.set_target_fps(240);
+        assert_eq!(governor.target_fps(), MAX_FRAME_RATE);
+        governor.set_target_fps(0);
+        assert_eq!(governor.target_fps(), 1);
+    }
+}
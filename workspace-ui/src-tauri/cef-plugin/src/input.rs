@@ -0,0 +1,599 @@
+//! Input Translation - DOM events from the canvas to browser input events
+//!
+//! The frontend forwards raw DOM mouse/keyboard events together with the
+//! canvas transform. This module maps them into browser-local coordinates,
+//! CEF event flags and Windows virtual key codes (which CEF expects on every
+//! platform), and derives click counts for double/triple clicks.
+
+use crate::{CefBounds, KeyEvent, MouseEvent};
+use bitflags::bitflags;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// ============================================================================
+// EVENT TYPES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MouseEventType {
+    Move,
+    Down,
+    Up,
+    Wheel,
+    /// Pointer left the browser area
+    Leave,
+}
+
+/// Mouse button, serialized with DOM numbering (0=left, 1=middle, 2=right)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "i32", into = "i32")]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+impl TryFrom<i32> for MouseButton {
+    type Error = String;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(MouseButton::Left),
+            1 => Ok(MouseButton::Middle),
+            2 => Ok(MouseButton::Right),
+            other => Err(format!("Unsupported mouse button {}", other)),
+        }
+    }
+}
+
+impl From<MouseButton> for i32 {
+    fn from(button: MouseButton) -> Self {
+        match button {
+            MouseButton::Left => 0,
+            MouseButton::Middle => 1,
+            MouseButton::Right => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyEventType {
+    /// Key pressed (CEF `KEYEVENT_RAWKEYDOWN`)
+    Down,
+    Up,
+    /// Character input following a key press
+    Char,
+}
+
+bitflags! {
+    /// Modifier and button state, bit-compatible with CEF's `cef_event_flags_t`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Modifiers: u32 {
+        const CAPS_LOCK = 1 << 0;
+        const SHIFT = 1 << 1;
+        const CONTROL = 1 << 2;
+        const ALT = 1 << 3;
+        const LEFT_MOUSE_BUTTON = 1 << 4;
+        const MIDDLE_MOUSE_BUTTON = 1 << 5;
+        const RIGHT_MOUSE_BUTTON = 1 << 6;
+        /// Cmd on macOS, Windows key elsewhere
+        const COMMAND = 1 << 7;
+        const NUM_LOCK = 1 << 8;
+        const IS_KEY_PAD = 1 << 9;
+        const IS_LEFT = 1 << 10;
+        const IS_RIGHT = 1 << 11;
+        const IS_REPEAT = 1 << 13;
+    }
+}
+
+// Modifiers travel as the plain CEF bit mask
+impl Serialize for Modifiers {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.bits().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Modifiers {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Modifiers::from_bits_truncate(u32::deserialize(deserializer)?))
+    }
+}
+
+// ============================================================================
+// DOM EVENTS
+// ============================================================================
+
+/// How the canvas is drawn: `screen = canvas * scale + offset`
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanvasTransform {
+    pub offset_x: f64,
+    pub offset_y: f64,
+    pub scale: f64,
+}
+
+impl Default for CanvasTransform {
+    fn default() -> Self {
+        Self {
+            offset_x: 0.0,
+            offset_y: 0.0,
+            scale: 1.0,
+        }
+    }
+}
+
+impl CanvasTransform {
+    /// Map a client (screen) point into the coordinate space of `bounds`
+    pub fn to_browser_local(&self, client_x: f64, client_y: f64, bounds: &CefBounds) -> (i32, i32) {
+        let scale = if self.scale > 0.0 { self.scale } else { 1.0 };
+        let canvas_x = (client_x - self.offset_x) / scale;
+        let canvas_y = (client_y - self.offset_y) / scale;
+
+        (
+            (canvas_x - bounds.x as f64).floor() as i32,
+            (canvas_y - bounds.y as f64).floor() as i32,
+        )
+    }
+}
+
+/// Subset of a DOM `MouseEvent` / `WheelEvent`, with DOM property names
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DomMouseEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub client_x: f64,
+    pub client_y: f64,
+    pub button: i32,
+    pub buttons: u32,
+    pub delta_x: f64,
+    pub delta_y: f64,
+    pub delta_mode: u32,
+    pub shift_key: bool,
+    pub ctrl_key: bool,
+    pub alt_key: bool,
+    pub meta_key: bool,
+    /// `event.timeStamp` in milliseconds
+    pub time_stamp: f64,
+}
+
+/// Subset of a DOM `KeyboardEvent`, with DOM property names
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DomKeyEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub key: String,
+    pub code: String,
+    /// Legacy `keyCode`, used when `code` is unknown
+    pub key_code: u32,
+    pub location: u32,
+    pub repeat: bool,
+    pub shift_key: bool,
+    pub ctrl_key: bool,
+    pub alt_key: bool,
+    pub meta_key: bool,
+    pub caps_lock: bool,
+}
+
+/// Pixels per wheel "line" (`deltaMode` 1)
+const WHEEL_LINE_HEIGHT: f64 = 40.0;
+
+fn dom_modifiers(shift: bool, ctrl: bool, alt: bool, meta: bool) -> Modifiers {
+    let mut modifiers = Modifiers::empty();
+    modifiers.set(Modifiers::SHIFT, shift);
+    modifiers.set(Modifiers::CONTROL, ctrl);
+    modifiers.set(Modifiers::ALT, alt);
+    modifiers.set(Modifiers::COMMAND, meta);
+    modifiers
+}
+
+/// Translate a DOM mouse event into a browser-local `MouseEvent`.
+/// `click_count` is left at 0; `ClickTracker` fills it in.
+pub fn translate_mouse_event(
+    dom: &DomMouseEvent,
+    transform: &CanvasTransform,
+    bounds: &CefBounds,
+) -> Result<MouseEvent, String> {
+    let event_type = match dom.event_type.as_str() {
+        "mousemove" | "pointermove" => MouseEventType::Move,
+        "mousedown" | "pointerdown" => MouseEventType::Down,
+        "mouseup" | "pointerup" => MouseEventType::Up,
+        "wheel" => MouseEventType::Wheel,
+        "mouseleave" | "pointerleave" => MouseEventType::Leave,
+        other => return Err(format!("Unsupported mouse event type '{}'", other)),
+    };
+
+    // Only down/up carry a meaningful button; default to left otherwise
+    let button = MouseButton::try_from(dom.button).unwrap_or(MouseButton::Left);
+
+    let mut modifiers = dom_modifiers(dom.shift_key, dom.ctrl_key, dom.alt_key, dom.meta_key);
+    // DOM `buttons`: 1=left, 2=right, 4=middle
+    modifiers.set(Modifiers::LEFT_MOUSE_BUTTON, dom.buttons & 1 != 0);
+    modifiers.set(Modifiers::RIGHT_MOUSE_BUTTON, dom.buttons & 2 != 0);
+    modifiers.set(Modifiers::MIDDLE_MOUSE_BUTTON, dom.buttons & 4 != 0);
+
+    let (x, y) = transform.to_browser_local(dom.client_x, dom.client_y, bounds);
+
+    // DOM deltas are positive when scrolling down, CEF's when scrolling up
+    let line = match dom.delta_mode {
+        1 => WHEEL_LINE_HEIGHT,
+        2 => bounds.height as f64,
+        _ => 1.0,
+    };
+
+    Ok(MouseEvent {
+        x,
+        y,
+        button,
+        event_type,
+        delta_x: (-dom.delta_x * line) as f32,
+        delta_y: (-dom.delta_y * line) as f32,
+        modifiers,
+        click_count: 0,
+    })
+}
+
+/// Translate a DOM keyboard event. A `keydown` of a printable key yields a
+/// `Down` followed by a `Char` event, like a native key press does in CEF.
+pub fn translate_key_event(dom: &DomKeyEvent) -> Result<Vec<KeyEvent>, String> {
+    let mut modifiers = dom_modifiers(dom.shift_key, dom.ctrl_key, dom.alt_key, dom.meta_key);
+    modifiers.set(Modifiers::CAPS_LOCK, dom.caps_lock);
+    modifiers.set(Modifiers::IS_REPEAT, dom.repeat);
+    // DOM `location`: 1=left, 2=right, 3=numpad
+    modifiers.set(Modifiers::IS_LEFT, dom.location == 1);
+    modifiers.set(Modifiers::IS_RIGHT, dom.location == 2);
+    modifiers.set(Modifiers::IS_KEY_PAD, dom.location == 3);
+
+    let key_code = windows_key_code(&dom.code).unwrap_or(dom.key_code) as i32;
+
+    let key_event = |event_type| KeyEvent {
+        key_code,
+        char_code: 0,
+        event_type,
+        modifiers,
+    };
+
+    match dom.event_type.as_str() {
+        "keydown" => {
+            let mut events = vec![key_event(KeyEventType::Down)];
+            // Shortcuts (Ctrl+C, Cmd+V, ...) must not type a character
+            let shortcut = dom.ctrl_key && !dom.alt_key || dom.meta_key;
+            if let Some(ch) = typed_char(&dom.key).filter(|_| !shortcut) {
+                events.push(KeyEvent {
+                    key_code: ch as i32,
+                    char_code: ch,
+                    event_type: KeyEventType::Char,
+                    modifiers,
+                });
+            }
+            Ok(events)
+        }
+        "keyup" => Ok(vec![key_event(KeyEventType::Up)]),
+        // Deprecated, the character is already sent with keydown
+        "keypress" => Ok(Vec::new()),
+        other => Err(format!("Unsupported key event type '{}'", other)),
+    }
+}
+
+/// Character produced by a DOM `key` value, if any
+fn typed_char(key: &str) -> Option<u32> {
+    let mut chars = key.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Some(ch as u32),
+        _ if key == "Enter" => Some(0x0D),
+        _ => None,
+    }
+}
+
+/// Windows virtual key code for a layout-independent DOM `code`
+pub fn windows_key_code(code: &str) -> Option<u32> {
+    if let Some(letter) = code.strip_prefix("Key") {
+        let ch = single_char(letter)?;
+        return ch.is_ascii_uppercase().then_some(ch as u32);
+    }
+    if let Some(digit) = code.strip_prefix("Digit") {
+        let ch = single_char(digit)?;
+        return ch.is_ascii_digit().then_some(ch as u32);
+    }
+    if let Some(digit) = code.strip_prefix("Numpad").and_then(single_char) {
+        if digit.is_ascii_digit() {
+            return Some(0x60 + (digit as u32 - '0' as u32));
+        }
+    }
+    if let Some(n) = code.strip_prefix('F').and_then(|n| n.parse::<u32>().ok()) {
+        return (1..=24).contains(&n).then_some(0x6F + n);
+    }
+
+    Some(match code {
+        "Backspace" => 0x08,
+        "Tab" => 0x09,
+        "Enter" | "NumpadEnter" => 0x0D,
+        "ShiftLeft" | "ShiftRight" => 0x10,
+        "ControlLeft" | "ControlRight" => 0x11,
+        "AltLeft" | "AltRight" => 0x12,
+        "Pause" => 0x13,
+        "CapsLock" => 0x14,
+        "Escape" => 0x1B,
+        "Space" => 0x20,
+        "PageUp" => 0x21,
+        "PageDown" => 0x22,
+        "End" => 0x23,
+        "Home" => 0x24,
+        "ArrowLeft" => 0x25,
+        "ArrowUp" => 0x26,
+        "ArrowRight" => 0x27,
+        "ArrowDown" => 0x28,
+        "PrintScreen" => 0x2C,
+        "Insert" => 0x2D,
+        "Delete" => 0x2E,
+        "MetaLeft" => 0x5B,
+        "MetaRight" => 0x5C,
+        "ContextMenu" => 0x5D,
+        "NumpadMultiply" => 0x6A,
+        "NumpadAdd" => 0x6B,
+        "NumpadSubtract" => 0x6D,
+        "NumpadDecimal" => 0x6E,
+        "NumpadDivide" => 0x6F,
+        "NumLock" => 0x90,
+        "ScrollLock" => 0x91,
+        "Semicolon" => 0xBA,
+        "Equal" => 0xBB,
+        "Comma" => 0xBC,
+        "Minus" => 0xBD,
+        "Period" => 0xBE,
+        "Slash" => 0xBF,
+        "Backquote" => 0xC0,
+        "BracketLeft" => 0xDB,
+        "Backslash" => 0xDC,
+        "BracketRight" => 0xDD,
+        "Quote" => 0xDE,
+        "IntlBackslash" => 0xE2,
+        _ => return None,
+    })
+}
+
+fn single_char(s: &str) -> Option<char> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Some(ch),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// CLICK COUNT
+// ============================================================================
+
+/// Max time between presses of a multi-click (ms)
+const MULTI_CLICK_TIME: f64 = 500.0;
+
+/// Max distance between presses of a multi-click (px, browser-local)
+const MULTI_CLICK_DISTANCE: i32 = 4;
+
+#[derive(Debug, Clone, Copy)]
+struct LastPress {
+    button: MouseButton,
+    x: i32,
+    y: i32,
+    time: f64,
+    count: u32,
+}
+
+/// Derives click counts (1 = single, 2 = double, 3 = triple) per browser
+#[derive(Debug, Default)]
+pub struct ClickTracker {
+    last: Option<LastPress>,
+}
+
+impl ClickTracker {
+    /// Set `event.click_count` for presses and releases; `time` in ms
+    pub fn apply(&mut self, event: &mut MouseEvent, time: f64) {
+        match event.event_type {
+            MouseEventType::Down => {
+                let count = match self.last {
+                    Some(last)
+                        if last.button == event.button
+                            && time - last.time <= MULTI_CLICK_TIME
+                            && (event.x - last.x).abs() <= MULTI_CLICK_DISTANCE
+                            && (event.y - last.y).abs() <= MULTI_CLICK_DISTANCE =>
+                    {
+                        // Quadruple clicks start over as a single click
+                        last.count % 3 + 1
+                    }
+                    _ => 1,
+                };
+                self.last = Some(LastPress {
+                    button: event.button,
+                    x: event.x,
+                    y: event.y,
+                    time,
+                    count,
+                });
+                event.click_count = count;
+            }
+            MouseEventType::Up => {
+                event.click_count = self
+                    .last
+                    .filter(|last| last.button == event.button)
+                    .map_or(1, |last| last.count);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(x: i32, y: i32) -> CefBounds {
+        CefBounds { x, y, width: 800, height: 600 }
+    }
+
+    fn mouse(event_type: &str, client_x: f64, client_y: f64) -> DomMouseEvent {
+        DomMouseEvent {
+            event_type: event_type.to_string(),
+            client_x,
+            client_y,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_identity_transform() {
+        let transform = CanvasTransform::default();
+        assert_eq!(transform.to_browser_local(150.0, 80.0, &bounds(100, 50)), (50, 30));
+    }
+
+    #[test]
+    fn test_zoom_and_pan() {
+        // Canvas zoomed to 200% and panned by (30, -20)
+        let transform = CanvasTransform { offset_x: 30.0, offset_y: -20.0, scale: 2.0 };
+        // Canvas point (110, 60) -> local (10, 10)
+        assert_eq!(transform.to_browser_local(250.0, 100.0, &bounds(100, 50)), (10, 10));
+
+        // Zoomed out to 50%
+        let transform = CanvasTransform { offset_x: 0.0, offset_y: 0.0, scale: 0.5 };
+        assert_eq!(transform.to_browser_local(75.0, 50.0, &bounds(100, 50)), (50, 50));
+    }
+
+    #[test]
+    fn test_points_left_of_browser_are_negative() {
+        let transform = CanvasTransform { offset_x: 0.0, offset_y: 0.0, scale: 3.0 };
+        // 299.5 / 3 = 99.83 -> floor to -1, not truncate to 0
+        assert_eq!(transform.to_browser_local(299.5, 150.0, &bounds(100, 50)), (-1, 0));
+    }
+
+    #[test]
+    fn test_invalid_scale_falls_back_to_identity() {
+        let transform = CanvasTransform { offset_x: 0.0, offset_y: 0.0, scale: 0.0 };
+        assert_eq!(transform.to_browser_local(10.0, 10.0, &bounds(0, 0)), (10, 10));
+    }
+
+    #[test]
+    fn test_translate_mouse_event() {
+        let mut dom = mouse("mousedown", 220.0, 120.0);
+        dom.button = 2;
+        dom.buttons = 2;
+        dom.shift_key = true;
+
+        let transform = CanvasTransform { offset_x: 20.0, offset_y: 20.0, scale: 2.0 };
+        let event = translate_mouse_event(&dom, &transform, &bounds(50, 20)).unwrap();
+
+        assert_eq!((event.x, event.y), (50, 30));
+        assert_eq!(event.event_type, MouseEventType::Down);
+        assert_eq!(event.button, MouseButton::Right);
+        assert_eq!(event.modifiers, Modifiers::SHIFT | Modifiers::RIGHT_MOUSE_BUTTON);
+    }
+
+    #[test]
+    fn test_wheel_delta_modes() {
+        let mut dom = mouse("wheel", 0.0, 0.0);
+        dom.delta_y = 3.0;
+        dom.delta_mode = 1;
+
+        let event = translate_mouse_event(&dom, &CanvasTransform::default(), &bounds(0, 0)).unwrap();
+        assert_eq!(event.delta_y, -120.0);
+
+        dom.delta_mode = 2;
+        dom.delta_y = 1.0;
+        let event = translate_mouse_event(&dom, &CanvasTransform::default(), &bounds(0, 0)).unwrap();
+        assert_eq!(event.delta_y, -600.0);
+    }
+
+    #[test]
+    fn test_unknown_mouse_event_is_rejected() {
+        let dom = mouse("dblclick", 0.0, 0.0);
+        assert!(translate_mouse_event(&dom, &CanvasTransform::default(), &bounds(0, 0)).is_err());
+    }
+
+    #[test]
+    fn test_click_counts() {
+        let mut tracker = ClickTracker::default();
+        let transform = CanvasTransform::default();
+        let mut press = |x: f64, time: f64| {
+            let mut event = translate_mouse_event(&mouse("mousedown", x, 0.0), &transform, &bounds(0, 0)).unwrap();
+            tracker.apply(&mut event, time);
+            event.click_count
+        };
+
+        assert_eq!(press(10.0, 0.0), 1);
+        assert_eq!(press(11.0, 200.0), 2);
+        assert_eq!(press(12.0, 400.0), 3);
+        assert_eq!(press(12.0, 600.0), 1);
+        // Too slow
+        assert_eq!(press(12.0, 1200.0), 1);
+        // Too far away
+        assert_eq!(press(30.0, 1300.0), 1);
+    }
+
+    #[test]
+    fn test_release_reports_press_count() {
+        let mut tracker = ClickTracker::default();
+        let transform = CanvasTransform::default();
+
+        for (event_type, time) in [("mousedown", 0.0), ("mouseup", 50.0), ("mousedown", 150.0)] {
+            let mut event = translate_mouse_event(&mouse(event_type, 5.0, 5.0), &transform, &bounds(0, 0)).unwrap();
+            tracker.apply(&mut event, time);
+        }
+
+        let mut up = translate_mouse_event(&mouse("mouseup", 5.0, 5.0), &transform, &bounds(0, 0)).unwrap();
+        tracker.apply(&mut up, 200.0);
+        assert_eq!(up.click_count, 2);
+    }
+
+    #[test]
+    fn test_translate_printable_key() {
+        let dom = DomKeyEvent {
+            event_type: "keydown".to_string(),
+            key: "A".to_string(),
+            code: "KeyA".to_string(),
+            shift_key: true,
+            ..Default::default()
+        };
+
+        let events = translate_key_event(&dom).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].event_type, KeyEventType::Down);
+        assert_eq!(events[0].key_code, 0x41);
+        assert_eq!(events[1].event_type, KeyEventType::Char);
+        assert_eq!(events[1].char_code, 'A' as u32);
+        assert_eq!(events[1].modifiers, Modifiers::SHIFT);
+    }
+
+    #[test]
+    fn test_shortcut_does_not_type() {
+        let dom = DomKeyEvent {
+            event_type: "keydown".to_string(),
+            key: "c".to_string(),
+            code: "KeyC".to_string(),
+            ctrl_key: true,
+            ..Default::default()
+        };
+
+        let events = translate_key_event(&dom).unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].modifiers, Modifiers::CONTROL);
+    }
+
+    #[test]
+    fn test_windows_key_codes() {
+        assert_eq!(windows_key_code("KeyZ"), Some(0x5A));
+        assert_eq!(windows_key_code("Digit7"), Some(0x37));
+        assert_eq!(windows_key_code("Numpad3"), Some(0x63));
+        assert_eq!(windows_key_code("F12"), Some(0x7B));
+        assert_eq!(windows_key_code("ArrowDown"), Some(0x28));
+        assert_eq!(windows_key_code("F25"), None);
+        assert_eq!(windows_key_code("Keyboard"), None);
+    }
+
+    #[test]
+    fn test_modifiers_serialize_as_bits() {
+        let json = serde_json::to_string(&(Modifiers::SHIFT | Modifiers::CONTROL)).unwrap();
+        assert_eq!(json, "6");
+        let parsed: Modifiers = serde_json::from_str("6").unwrap();
+        assert_eq!(parsed, Modifiers::SHIFT | Modifiers::CONTROL);
+    }
+}
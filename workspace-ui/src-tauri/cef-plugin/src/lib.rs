@@ -2,17 +2,47 @@
 //!
 //! Renders web content to GPU textures that can be displayed in a WebGL canvas.
 
+mod backend;
+mod codec;
+mod error;
+mod events;
+mod governor;
+mod input;
 mod manager;
+mod navigation_filter;
 mod renderer;
+mod request_filter;
+mod stream;
+mod thumbnail;
 mod transfer;
 
+pub use backend::{BackendEvent, BrowserBackend, PaintEvent, PlaceholderBackend, TestBackend};
+pub use codec::FrameCodec;
+pub use error::{Error, ErrorCode};
+pub use governor::RenderState;
+pub use navigation_filter::{NavigationFilter, NavigationKind, NavigationRequest};
+pub use request_filter::{RequestFilter, ResourceRequest, ResourceType};
+pub use thumbnail::Thumbnail;
+pub use transfer::{
+    crop_frame, encode_frame, encode_image, resize_bgra, DirtyRect, ImageFormat, ResizeFilter,
+};
+pub use input::{
+    CanvasTransform, DomKeyEvent, DomMouseEvent, KeyEventType, Modifiers, MouseButton,
+    MouseEventType,
+};
+use dashmap::DashMap;
 use manager::BrowserManager;
-use parking_lot::RwLock;
+use navigation_filter::NavigationFilterSlot;
+use request_filter::RequestFilterSlot;
+use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 use std::sync::Arc;
+use stream::FrameSubscription;
 use tauri::{
+    ipc::{Channel, InvokeResponseBody},
     plugin::{Builder, TauriPlugin},
-    AppHandle, Manager, Runtime, State,
+    AppHandle, Emitter, Manager, Runtime, State,
 };
 
 // ============================================================================
@@ -31,8 +61,13 @@ pub struct CefBounds {
 pub struct BrowserInfo {
     pub id: String,
     pub url: String,
+    pub title: Option<String>,
     pub bounds: CefBounds,
     pub is_loading: bool,
+    pub can_go_back: bool,
+    pub can_go_forward: bool,
+    pub render_state: RenderState,
+    pub frame_rate: u32, // Effective rate, 0 while suspended
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,32 +79,86 @@ pub struct FrameData {
     pub data: Vec<u8>,  // Raw pixel data
 }
 
+/// A changed sub-rectangle of a frame, with its own pixel data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameRegion {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>, // Raw pixel data of this region only
+}
+
+/// Changes since the client's last acknowledged frame (`seq`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameDelta {
+    pub browser_id: String,
+    pub seq: u64,
+    pub width: u32,
+    pub height: u32,
+    pub format: String,
+    pub full: bool, // true: single region covering the whole frame
+    pub regions: Vec<FrameRegion>,
+}
+
+/// Payload of the `cef://*` events: browser id plus the event fields
+#[derive(Debug, Clone, Serialize)]
+pub struct BrowserEvent {
+    pub browser_id: String,
+    #[serde(flatten)]
+    pub event: BackendEvent,
+}
+
+/// Returned by `cef_subscribe_frames`
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameSubscriptionInfo {
+    pub subscription_id: u32,
+    pub format: String, // Negotiated codec, see `FrameCodec::format`
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MouseEvent {
-    pub x: i32,
+    pub x: i32, // Browser-local coordinates
     pub y: i32,
-    pub button: i32,       // 0=left, 1=middle, 2=right
-    pub event_type: String, // "move", "down", "up", "wheel"
-    pub delta_x: f32,      // For wheel events
+    pub button: MouseButton,
+    pub event_type: MouseEventType,
+    pub delta_x: f32, // For wheel events
     pub delta_y: f32,
-    pub modifiers: u32,    // Ctrl, Shift, Alt flags
+    pub modifiers: Modifiers,
+    #[serde(default)]
+    pub click_count: u32, // For down/up: 1=single, 2=double, 3=triple
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct KeyEvent {
-    pub key_code: i32,
+    pub key_code: i32, // Windows virtual key code
     pub char_code: u32,
-    pub event_type: String, // "down", "up", "char"
-    pub modifiers: u32,
+    pub event_type: KeyEventType,
+    pub modifiers: Modifiers,
 }
 
 // ============================================================================
 // PLUGIN STATE
 // ============================================================================
 
+/// Backend used by the plugin commands
+#[cfg(not(feature = "test-backend"))]
+type ActiveBackend = PlaceholderBackend;
+/// Deterministic frames and load events for headless testing
+#[cfg(feature = "test-backend")]
+type ActiveBackend = TestBackend;
+
 pub struct CefPluginState {
-    manager: Arc<RwLock<Option<BrowserManager>>>,
+    manager: Arc<RwLock<Option<BrowserManager<ActiveBackend>>>>,
     initialized: Arc<RwLock<bool>>,
+    subscriptions: DashMap<u32, Arc<FrameSubscription>>,
+    next_subscription_id: AtomicU32,
+    /// Run flag of the current lifecycle event pump
+    event_pump: Mutex<Arc<AtomicBool>>,
+    /// Host filter for resource requests, handed to every manager
+    request_filter: RequestFilterSlot,
+    /// Host filter for navigations, handed to every manager
+    navigation_filter: NavigationFilterSlot,
 }
 
 impl Default for CefPluginState {
@@ -77,34 +166,91 @@ impl Default for CefPluginState {
         Self {
             manager: Arc::new(RwLock::new(None)),
             initialized: Arc::new(RwLock::new(false)),
+            subscriptions: DashMap::new(),
+            next_subscription_id: AtomicU32::new(1),
+            event_pump: Mutex::new(Arc::new(AtomicBool::new(false))),
+            request_filter: RequestFilterSlot::default(),
+            navigation_filter: NavigationFilterSlot::default(),
         }
     }
 }
 
+impl CefPluginState {
+    /// Block resource requests of all OSR browsers with the host's filter
+    /// (`None` loads everything). Applies to running browsers as well.
+    pub fn set_request_filter(&self, filter: Option<Arc<dyn RequestFilter>>) {
+        self.request_filter.set(filter);
+    }
+
+    /// Veto page loads of all OSR browsers with the host's filter (`None`
+    /// allows everything). Applies to running browsers as well.
+    pub fn set_navigation_filter(&self, filter: Option<Arc<dyn NavigationFilter>>) {
+        self.navigation_filter.set(filter);
+    }
+
+    /// Downscaled PNG of a browser's current frame (for host-side caching)
+    pub fn capture_thumbnail(
+        &self,
+        id: &str,
+        max_width: Option<u32>,
+        max_height: Option<u32>,
+    ) -> Result<Thumbnail, Error> {
+        let manager = self.manager.read();
+        let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+        manager.capture_thumbnail(
+            id,
+            max_width.unwrap_or(thumbnail::DEFAULT_THUMBNAIL_WIDTH),
+            max_height.unwrap_or(thumbnail::DEFAULT_THUMBNAIL_HEIGHT),
+        )
+    }
+}
+
 // ============================================================================
 // COMMANDS
 // ============================================================================
 
-/// Initialize CEF - must be called before creating browsers
+/// Initialize CEF - must be called before creating browsers.
+/// Starts emitting `cef://*` lifecycle events.
 #[tauri::command]
-async fn cef_init(state: State<'_, CefPluginState>) -> Result<(), String> {
+async fn cef_init<R: Runtime>(
+    app: AppHandle<R>,
+    state: State<'_, CefPluginState>,
+) -> Result<(), Error> {
     let mut initialized = state.initialized.write();
     if *initialized {
         return Ok(());
     }
 
-    let manager = BrowserManager::new().map_err(|e| format!("Failed to init CEF: {}", e))?;
+    let mut manager = BrowserManager::<ActiveBackend>::new()?;
+    manager.set_request_filter(state.request_filter.clone());
+    manager.set_navigation_filter(state.navigation_filter.clone());
 
     *state.manager.write() = Some(manager);
     *initialized = true;
 
+    // Fresh flag per init so a pump from a previous session can't resume
+    let active = Arc::new(AtomicBool::new(true));
+    std::mem::replace(&mut *state.event_pump.lock(), active.clone()).store(false, Ordering::Relaxed);
+    events::spawn_event_pump(state.manager.clone(), active.clone(), move |event| {
+        if let Err(e) = app.emit(&events::event_name(event), event) {
+            log::warn!("Failed to emit {}: {}", event.event.name(), e);
+        }
+    });
+
     log::info!("CEF initialized successfully");
     Ok(())
 }
 
 /// Shutdown CEF - cleanup all resources
 #[tauri::command]
-async fn cef_shutdown(state: State<'_, CefPluginState>) -> Result<(), String> {
+async fn cef_shutdown(state: State<'_, CefPluginState>) -> Result<(), Error> {
+    state.event_pump.lock().store(false, Ordering::Relaxed);
+    for entry in state.subscriptions.iter() {
+        entry.value().stop();
+    }
+    state.subscriptions.clear();
+
     let mut manager = state.manager.write();
     if let Some(m) = manager.take() {
         m.shutdown();
@@ -122,19 +268,12 @@ async fn cef_create_browser(
     id: String,
     url: String,
     bounds: CefBounds,
-) -> Result<BrowserInfo, String> {
+) -> Result<BrowserInfo, Error> {
     let manager = state.manager.read();
-    let manager = manager.as_ref().ok_or("CEF not initialized")?;
-
-    manager.create_browser(&id, &url, bounds.clone())
-        .map_err(|e| format!("Failed to create browser: {}", e))?;
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
 
-    Ok(BrowserInfo {
-        id,
-        url,
-        bounds,
-        is_loading: true,
-    })
+    manager.create_browser(&id, &url, bounds)?;
+    manager.browser_info(&id).ok_or(Error::BrowserNotFound(id))
 }
 
 /// Close a browser instance
@@ -142,12 +281,11 @@ async fn cef_create_browser(
 async fn cef_close_browser(
     state: State<'_, CefPluginState>,
     id: String,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     let manager = state.manager.read();
-    let manager = manager.as_ref().ok_or("CEF not initialized")?;
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
 
     manager.close_browser(&id)
-        .map_err(|e| format!("Failed to close browser: {}", e))
 }
 
 /// Update browser bounds (position/size)
@@ -156,12 +294,11 @@ async fn cef_update_bounds(
     state: State<'_, CefPluginState>,
     id: String,
     bounds: CefBounds,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     let manager = state.manager.read();
-    let manager = manager.as_ref().ok_or("CEF not initialized")?;
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
 
     manager.update_bounds(&id, bounds)
-        .map_err(|e| format!("Failed to update bounds: {}", e))
 }
 
 /// Navigate to a URL
@@ -170,35 +307,170 @@ async fn cef_navigate(
     state: State<'_, CefPluginState>,
     id: String,
     url: String,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     let manager = state.manager.read();
-    let manager = manager.as_ref().ok_or("CEF not initialized")?;
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
 
     manager.navigate(&id, &url)
-        .map_err(|e| format!("Failed to navigate: {}", e))
 }
 
-/// Get the latest frame data for a browser (raw pixels)
+/// Go back one history entry
+#[tauri::command]
+async fn cef_go_back(state: State<'_, CefPluginState>, id: String) -> Result<(), Error> {
+    let manager = state.manager.read();
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    manager.go_back(&id)
+}
+
+/// Go forward one history entry
+#[tauri::command]
+async fn cef_go_forward(state: State<'_, CefPluginState>, id: String) -> Result<(), Error> {
+    let manager = state.manager.read();
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    manager.go_forward(&id)
+}
+
+/// Reload the page; `bypass_cache` performs a hard reload
+#[tauri::command]
+async fn cef_reload(
+    state: State<'_, CefPluginState>,
+    id: String,
+    bypass_cache: Option<bool>,
+) -> Result<(), Error> {
+    let manager = state.manager.read();
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    manager.reload(&id, bypass_cache.unwrap_or(false))
+}
+
+/// Stop loading the current page
+#[tauri::command]
+async fn cef_stop(state: State<'_, CefPluginState>, id: String) -> Result<(), Error> {
+    let manager = state.manager.read();
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    manager.stop(&id)
+}
+
+/// Codec for the polling commands - they don't know which frame the client
+/// holds, so the delta codec is only available on subscriptions
+fn polling_codec(codec: Option<FrameCodec>) -> Result<FrameCodec, Error> {
+    match codec.unwrap_or_default() {
+        FrameCodec::XorRle => Err(Error::InvalidInput(
+            "XOR-RLE requires a frame subscription".to_string(),
+        )),
+        codec => Ok(codec),
+    }
+}
+
+/// Get the latest frame data for a browser, encoded with `codec` (default raw)
 #[tauri::command]
 async fn cef_get_frame(
     state: State<'_, CefPluginState>,
     id: String,
-) -> Result<Option<FrameData>, String> {
+    codec: Option<FrameCodec>,
+) -> Result<Option<FrameData>, Error> {
+    let codec = polling_codec(codec)?;
     let manager = state.manager.read();
-    let manager = manager.as_ref().ok_or("CEF not initialized")?;
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
 
-    Ok(manager.get_frame(&id))
+    Ok(manager
+        .get_frame(&id)
+        .map(|frame| transfer::compress_frame(&frame, codec, None)))
+}
+
+/// Get only the regions that changed since `since_seq` (0 = full frame)
+#[tauri::command]
+async fn cef_get_frame_delta(
+    state: State<'_, CefPluginState>,
+    id: String,
+    since_seq: u64,
+) -> Result<Option<FrameDelta>, Error> {
+    let manager = state.manager.read();
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    Ok(manager.get_frame_delta(&id, since_seq))
 }
 
 /// Get frame data for all browsers (batch operation for efficiency)
 #[tauri::command]
 async fn cef_get_all_frames(
     state: State<'_, CefPluginState>,
-) -> Result<Vec<FrameData>, String> {
+    codec: Option<FrameCodec>,
+) -> Result<Vec<FrameData>, Error> {
+    let codec = polling_codec(codec)?;
     let manager = state.manager.read();
-    let manager = manager.as_ref().ok_or("CEF not initialized")?;
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    Ok(manager
+        .get_all_frames()
+        .iter()
+        .map(|frame| transfer::compress_frame(frame, codec, None))
+        .collect())
+}
+
+/// Subscribe to binary frame packets pushed over `channel`.
+/// Streams all browsers if `ids` is `None`. `formats` lists the codecs the
+/// client can decode, most preferred first; the chosen one is returned.
+#[tauri::command]
+async fn cef_subscribe_frames(
+    state: State<'_, CefPluginState>,
+    ids: Option<Vec<String>>,
+    formats: Option<Vec<String>>,
+    channel: Channel,
+) -> Result<FrameSubscriptionInfo, Error> {
+    if state.manager.read().is_none() {
+        return Err(Error::NotInitialized);
+    }
+
+    let subscription_id = state.next_subscription_id.fetch_add(1, Ordering::Relaxed);
+    let codec = FrameCodec::negotiate(&formats.unwrap_or_default());
+    let subscription = Arc::new(FrameSubscription::new(subscription_id, ids, codec));
+    state.subscriptions.insert(subscription_id, subscription.clone());
+
+    stream::spawn_frame_stream(state.manager.clone(), subscription, move |packet| {
+        channel.send(InvokeResponseBody::Raw(packet)).is_ok()
+    });
+
+    log::info!("Frame subscription {} started ({})", subscription_id, codec.format());
+    Ok(FrameSubscriptionInfo {
+        subscription_id,
+        format: codec.format().to_string(),
+    })
+}
+
+/// Stop a frame subscription
+#[tauri::command]
+async fn cef_unsubscribe_frames(
+    state: State<'_, CefPluginState>,
+    subscription_id: u32,
+) -> Result<(), Error> {
+    let (_, subscription) = state
+        .subscriptions
+        .remove(&subscription_id)
+        .ok_or(Error::SubscriptionNotFound(subscription_id))?;
+
+    subscription.stop();
+    Ok(())
+}
 
-    Ok(manager.get_all_frames())
+/// Acknowledge that every packet up to `seq` for a browser was applied
+#[tauri::command]
+async fn cef_ack_frame(
+    state: State<'_, CefPluginState>,
+    subscription_id: u32,
+    id: String,
+    seq: u64,
+) -> Result<(), Error> {
+    let subscription = state
+        .subscriptions
+        .get(&subscription_id)
+        .ok_or(Error::SubscriptionNotFound(subscription_id))?;
+
+    subscription.ack(&id, seq);
+    Ok(())
 }
 
 /// Send mouse event to browser
@@ -207,12 +479,11 @@ async fn cef_send_mouse_event(
     state: State<'_, CefPluginState>,
     id: String,
     event: MouseEvent,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     let manager = state.manager.read();
-    let manager = manager.as_ref().ok_or("CEF not initialized")?;
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
 
     manager.send_mouse_event(&id, event)
-        .map_err(|e| format!("Failed to send mouse event: {}", e))
 }
 
 /// Send keyboard event to browser
@@ -221,12 +492,39 @@ async fn cef_send_key_event(
     state: State<'_, CefPluginState>,
     id: String,
     event: KeyEvent,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     let manager = state.manager.read();
-    let manager = manager.as_ref().ok_or("CEF not initialized")?;
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
 
     manager.send_key_event(&id, event)
-        .map_err(|e| format!("Failed to send key event: {}", e))
+}
+
+/// Send a DOM mouse/wheel event captured on the canvas; coordinates are
+/// mapped into the browser using the canvas `transform`
+#[tauri::command]
+async fn cef_send_dom_mouse_event(
+    state: State<'_, CefPluginState>,
+    id: String,
+    event: DomMouseEvent,
+    transform: Option<CanvasTransform>,
+) -> Result<(), Error> {
+    let manager = state.manager.read();
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    manager.send_dom_mouse_event(&id, &event, &transform.unwrap_or_default())
+}
+
+/// Send a DOM keyboard event (translated to CEF key codes)
+#[tauri::command]
+async fn cef_send_dom_key_event(
+    state: State<'_, CefPluginState>,
+    id: String,
+    event: DomKeyEvent,
+) -> Result<(), Error> {
+    let manager = state.manager.read();
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    manager.send_dom_key_event(&id, &event)
 }
 
 /// Focus a browser (for keyboard input)
@@ -234,21 +532,74 @@ async fn cef_send_key_event(
 async fn cef_focus_browser(
     state: State<'_, CefPluginState>,
     id: String,
-) -> Result<(), String> {
+) -> Result<(), Error> {
     let manager = state.manager.read();
-    let manager = manager.as_ref().ok_or("CEF not initialized")?;
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
 
     manager.focus_browser(&id)
-        .map_err(|e| format!("Failed to focus browser: {}", e))
+}
+
+/// Show or hide a browser (hidden browsers stop painting)
+#[tauri::command]
+async fn cef_set_visibility(
+    state: State<'_, CefPluginState>,
+    id: String,
+    visible: bool,
+) -> Result<(), Error> {
+    let manager = state.manager.read();
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    manager.set_visibility(&id, visible)
+}
+
+/// Report the canvas viewport rect; browsers outside it or behind the
+/// fullscreen browser are throttled to ~1 fps
+#[tauri::command]
+async fn cef_set_viewport(
+    state: State<'_, CefPluginState>,
+    viewport: CefBounds,
+    fullscreen_id: Option<String>,
+) -> Result<(), Error> {
+    let manager = state.manager.read();
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    manager.set_viewport(viewport, fullscreen_id)
+}
+
+/// Set the target frame rate of a browser
+#[tauri::command]
+async fn cef_set_frame_rate(
+    state: State<'_, CefPluginState>,
+    id: String,
+    fps: u32,
+) -> Result<(), Error> {
+    let manager = state.manager.read();
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
+
+    manager.set_frame_rate(&id, fps)
+}
+
+/// Downscaled PNG of a browser's current frame, returned as raw bytes
+#[tauri::command]
+async fn cef_capture_thumbnail(
+    state: State<'_, CefPluginState>,
+    id: String,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+) -> Result<tauri::ipc::Response, Error> {
+    let thumbnail = state
+        .capture_thumbnail(&id, max_width, max_height)?;
+
+    Ok(tauri::ipc::Response::new(thumbnail.png))
 }
 
 /// List all active browsers
 #[tauri::command]
 async fn cef_list_browsers(
     state: State<'_, CefPluginState>,
-) -> Result<Vec<BrowserInfo>, String> {
+) -> Result<Vec<BrowserInfo>, Error> {
     let manager = state.manager.read();
-    let manager = manager.as_ref().ok_or("CEF not initialized")?;
+    let manager = manager.as_ref().ok_or(Error::NotInitialized)?;
 
     Ok(manager.list_browsers())
 }
@@ -270,11 +621,25 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             cef_close_browser,
             cef_update_bounds,
             cef_navigate,
+            cef_go_back,
+            cef_go_forward,
+            cef_reload,
+            cef_stop,
             cef_get_frame,
+            cef_get_frame_delta,
             cef_get_all_frames,
+            cef_subscribe_frames,
+            cef_unsubscribe_frames,
+            cef_ack_frame,
             cef_send_mouse_event,
             cef_send_key_event,
+            cef_send_dom_mouse_event,
+            cef_send_dom_key_event,
             cef_focus_browser,
+            cef_set_visibility,
+            cef_set_viewport,
+            cef_set_frame_rate,
+            cef_capture_thumbnail,
             cef_list_browsers,
         ])
         .build()
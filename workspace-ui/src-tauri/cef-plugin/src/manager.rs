@@ -1,28 +1,51 @@
 //! Browser Manager - Manages multiple CEF browser instances
 
-use crate::{BrowserInfo, CefBounds, FrameData, KeyEvent, MouseEvent};
+use crate::backend::{BrowserBackend, PlaceholderBackend};
+use crate::error::Error;
+use crate::{BrowserEvent, BrowserInfo, CefBounds, FrameData, FrameDelta, KeyEvent, MouseEvent};
+use crate::input::{CanvasTransform, DomKeyEvent, DomMouseEvent};
+use crate::navigation_filter::{NavigationFilterSlot, NavigationKind, NavigationRequest};
 use crate::renderer::OsrBrowserInstance;
+use crate::request_filter::RequestFilterSlot;
+use crate::thumbnail::Thumbnail;
 use dashmap::DashMap;
+use parking_lot::RwLock;
 use std::sync::Arc;
 
+/// Canvas viewport last reported by the frontend
+struct Viewport {
+    rect: CefBounds,
+    /// Browser currently covering the canvas, if any
+    fullscreen_id: Option<String>,
+}
+
 /// Manages multiple CEF browser instances with off-screen rendering
-pub struct BrowserManager {
-    browsers: Arc<DashMap<String, OsrBrowserInstance>>,
-    cef_initialized: bool,
+pub struct BrowserManager<B: BrowserBackend = PlaceholderBackend> {
+    browsers: Arc<DashMap<String, OsrBrowserInstance<B>>>,
+    backend: Arc<B>,
+    viewport: RwLock<Option<Viewport>>,
+    /// Host filter for `create_browser` and `navigate`
+    navigation_filter: NavigationFilterSlot,
 }
 
-impl BrowserManager {
-    /// Create a new BrowserManager and initialize CEF
-    pub fn new() -> Result<Self, String> {
-        // TODO: Initialize CEF here
-        // For now, we'll create a stub that can be filled in
+impl<B: BrowserBackend + Default> BrowserManager<B> {
+    /// Create a new BrowserManager with a default-constructed backend
+    pub fn new() -> Result<Self, Error> {
+        Ok(Self::with_backend(B::default()))
+    }
+}
 
+impl<B: BrowserBackend> BrowserManager<B> {
+    /// Create a new BrowserManager on top of the given backend
+    pub fn with_backend(backend: B) -> Self {
         log::info!("Initializing CEF BrowserManager...");
 
-        Ok(Self {
+        Self {
             browsers: Arc::new(DashMap::new()),
-            cef_initialized: true,
-        })
+            backend: Arc::new(backend),
+            viewport: RwLock::new(None),
+            navigation_filter: NavigationFilterSlot::default(),
+        }
     }
 
     /// Shutdown CEF and cleanup all browsers
@@ -35,7 +58,7 @@ impl BrowserManager {
         }
         self.browsers.clear();
 
-        // TODO: Call cef::shutdown()
+        self.backend.shutdown();
     }
 
     /// Create a new browser instance
@@ -44,12 +67,14 @@ impl BrowserManager {
         id: &str,
         url: &str,
         bounds: CefBounds,
-    ) -> Result<(), String> {
+    ) -> Result<(), Error> {
         if self.browsers.contains_key(id) {
-            return Err(format!("Browser '{}' already exists", id));
+            return Err(Error::BrowserExists(id.to_string()));
         }
+        self.check_navigation(id, url, NavigationKind::Create)?;
 
-        let browser = OsrBrowserInstance::new(id, url, bounds)?;
+        let browser = OsrBrowserInstance::new(self.backend.clone(), id, url, bounds)?;
+        self.apply_viewport(id, &browser)?;
         self.browsers.insert(id.to_string(), browser);
 
         log::info!("Created browser: {} -> {}", id, url);
@@ -57,42 +82,93 @@ impl BrowserManager {
     }
 
     /// Close a browser instance
-    pub fn close_browser(&self, id: &str) -> Result<(), String> {
+    pub fn close_browser(&self, id: &str) -> Result<(), Error> {
         if let Some((_, browser)) = self.browsers.remove(id) {
             browser.close()?;
             log::info!("Closed browser: {}", id);
             Ok(())
         } else {
-            Err(format!("Browser '{}' not found", id))
+            Err(Error::BrowserNotFound(id.to_string()))
         }
     }
 
     /// Update browser bounds (triggers resize)
-    pub fn update_bounds(&self, id: &str, bounds: CefBounds) -> Result<(), String> {
+    pub fn update_bounds(&self, id: &str, bounds: CefBounds) -> Result<(), Error> {
         if let Some(mut browser) = self.browsers.get_mut(id) {
             browser.update_bounds(bounds)?;
-            Ok(())
+            self.apply_viewport(id, &browser)
         } else {
-            Err(format!("Browser '{}' not found", id))
+            Err(Error::BrowserNotFound(id.to_string()))
         }
     }
 
     /// Navigate to a URL
-    pub fn navigate(&self, id: &str, url: &str) -> Result<(), String> {
+    pub fn navigate(&self, id: &str, url: &str) -> Result<(), Error> {
         if let Some(browser) = self.browsers.get(id) {
+            self.check_navigation(id, url, NavigationKind::Navigate)?;
             browser.navigate(url)?;
             log::info!("Navigate browser {} to: {}", id, url);
             Ok(())
         } else {
-            Err(format!("Browser '{}' not found", id))
+            Err(Error::BrowserNotFound(id.to_string()))
         }
     }
 
+    /// Go back one history entry
+    pub fn go_back(&self, id: &str) -> Result<(), Error> {
+        let browser = self
+            .browsers
+            .get(id)
+            .ok_or_else(|| Error::BrowserNotFound(id.to_string()))?;
+        browser.go_back().map_err(Error::Backend)
+    }
+
+    /// Go forward one history entry
+    pub fn go_forward(&self, id: &str) -> Result<(), Error> {
+        let browser = self
+            .browsers
+            .get(id)
+            .ok_or_else(|| Error::BrowserNotFound(id.to_string()))?;
+        browser.go_forward().map_err(Error::Backend)
+    }
+
+    /// Reload a browser; `ignore_cache` bypasses the HTTP cache
+    pub fn reload(&self, id: &str, ignore_cache: bool) -> Result<(), Error> {
+        let browser = self
+            .browsers
+            .get(id)
+            .ok_or_else(|| Error::BrowserNotFound(id.to_string()))?;
+        browser.reload(ignore_cache).map_err(Error::Backend)
+    }
+
+    /// Stop loading
+    pub fn stop(&self, id: &str) -> Result<(), Error> {
+        let browser = self
+            .browsers
+            .get(id)
+            .ok_or_else(|| Error::BrowserNotFound(id.to_string()))?;
+        browser.stop().map_err(Error::Backend)
+    }
+
     /// Get the latest frame for a browser
     pub fn get_frame(&self, id: &str) -> Option<FrameData> {
         self.browsers.get(id).and_then(|b| b.get_frame())
     }
 
+    /// Get the changes since the client's last acknowledged frame
+    pub fn get_frame_delta(&self, id: &str, since_seq: u64) -> Option<FrameDelta> {
+        self.browsers.get(id).and_then(|b| b.get_frame_delta(since_seq))
+    }
+
+    /// Downscaled PNG of a browser's current frame
+    pub fn capture_thumbnail(&self, id: &str, max_width: u32, max_height: u32) -> Result<Thumbnail, Error> {
+        let browser = self
+            .browsers
+            .get(id)
+            .ok_or_else(|| Error::BrowserNotFound(id.to_string()))?;
+        browser.capture_thumbnail(max_width, max_height).map_err(Error::Backend)
+    }
+
     /// Get frames for all browsers (batch)
     pub fn get_all_frames(&self) -> Vec<FrameData> {
         self.browsers
@@ -102,27 +178,50 @@ impl BrowserManager {
     }
 
     /// Send mouse event to browser
-    pub fn send_mouse_event(&self, id: &str, event: MouseEvent) -> Result<(), String> {
+    pub fn send_mouse_event(&self, id: &str, event: MouseEvent) -> Result<(), Error> {
         if let Some(browser) = self.browsers.get(id) {
             browser.send_mouse_event(event)?;
             Ok(())
         } else {
-            Err(format!("Browser '{}' not found", id))
+            Err(Error::BrowserNotFound(id.to_string()))
         }
     }
 
     /// Send keyboard event to browser
-    pub fn send_key_event(&self, id: &str, event: KeyEvent) -> Result<(), String> {
+    pub fn send_key_event(&self, id: &str, event: KeyEvent) -> Result<(), Error> {
         if let Some(browser) = self.browsers.get(id) {
             browser.send_key_event(event)?;
             Ok(())
         } else {
-            Err(format!("Browser '{}' not found", id))
+            Err(Error::BrowserNotFound(id.to_string()))
         }
     }
 
+    /// Send a DOM mouse event captured on the canvas
+    pub fn send_dom_mouse_event(
+        &self,
+        id: &str,
+        event: &DomMouseEvent,
+        transform: &CanvasTransform,
+    ) -> Result<(), Error> {
+        let browser = self
+            .browsers
+            .get(id)
+            .ok_or_else(|| Error::BrowserNotFound(id.to_string()))?;
+        browser.send_dom_mouse_event(event, transform).map_err(Error::Backend)
+    }
+
+    /// Send a DOM keyboard event
+    pub fn send_dom_key_event(&self, id: &str, event: &DomKeyEvent) -> Result<(), Error> {
+        let browser = self
+            .browsers
+            .get(id)
+            .ok_or_else(|| Error::BrowserNotFound(id.to_string()))?;
+        browser.send_dom_key_event(event).map_err(Error::Backend)
+    }
+
     /// Focus a browser for keyboard input
-    pub fn focus_browser(&self, id: &str) -> Result<(), String> {
+    pub fn focus_browser(&self, id: &str) -> Result<(), Error> {
         // Unfocus all others first
         for entry in self.browsers.iter() {
             if entry.key() != id {
@@ -135,7 +234,99 @@ impl BrowserManager {
             browser.set_focus(true)?;
             Ok(())
         } else {
-            Err(format!("Browser '{}' not found", id))
+            Err(Error::BrowserNotFound(id.to_string()))
+        }
+    }
+
+    /// Set the target frame rate of a browser (clamped to 1..=60)
+    pub fn set_frame_rate(&self, id: &str, fps: u32) -> Result<(), Error> {
+        let browser = self
+            .browsers
+            .get(id)
+            .ok_or_else(|| Error::BrowserNotFound(id.to_string()))?;
+        browser.update_governor(|governor| governor.set_target_fps(fps)).map_err(Error::Backend)
+    }
+
+    /// Show or hide a browser; hidden browsers are suspended
+    pub fn set_visibility(&self, id: &str, visible: bool) -> Result<(), Error> {
+        let browser = self
+            .browsers
+            .get(id)
+            .ok_or_else(|| Error::BrowserNotFound(id.to_string()))?;
+        browser.update_governor(|governor| governor.set_visible(visible)).map_err(Error::Backend)
+    }
+
+    /// Update the canvas viewport (same coordinate space as browser bounds).
+    /// Browsers outside it, or behind `fullscreen_id`, are throttled.
+    pub fn set_viewport(&self, rect: CefBounds, fullscreen_id: Option<String>) -> Result<(), Error> {
+        *self.viewport.write() = Some(Viewport { rect, fullscreen_id });
+
+        for entry in self.browsers.iter() {
+            self.apply_viewport(entry.key(), entry.value())?;
+        }
+        Ok(())
+    }
+
+    fn apply_viewport(&self, id: &str, browser: &OsrBrowserInstance<B>) -> Result<(), Error> {
+        let viewport = self.viewport.read();
+        let Some(viewport) = viewport.as_ref() else {
+            return Ok(());
+        };
+
+        let occluded = viewport.fullscreen_id.as_deref().is_some_and(|f| f != id);
+        let bounds = browser.bounds();
+        browser.update_governor(|governor| governor.set_placement(&bounds, &viewport.rect, occluded)).map_err(Error::Backend)
+    }
+
+    /// Pump every browser and collect the lifecycle events since the last call
+    pub fn take_events(&self) -> Vec<BrowserEvent> {
+        self.browsers
+            .iter()
+            .flat_map(|entry| {
+                let browser_id = entry.key().clone();
+                entry
+                    .value()
+                    .take_events()
+                    .into_iter()
+                    .map(move |event| BrowserEvent {
+                        browser_id: browser_id.clone(),
+                        event,
+                    })
+            })
+            .collect()
+    }
+
+    /// Ids of all browsers
+    pub fn browser_ids(&self) -> Vec<String> {
+        self.browsers.iter().map(|entry| entry.key().clone()).collect()
+    }
+
+    /// Info of a single browser
+    pub fn browser_info(&self, id: &str) -> Option<BrowserInfo> {
+        self.browsers.get(id).map(|browser| browser.get_info())
+    }
+
+    /// Route resource requests of all browsers through the host's filter
+    pub fn set_request_filter(&self, filter: RequestFilterSlot) {
+        self.backend.set_request_filter(filter);
+    }
+
+    /// Check host-requested loads here and page-initiated ones in the backend
+    pub fn set_navigation_filter(&mut self, filter: NavigationFilterSlot) {
+        self.backend.set_navigation_filter(filter.clone());
+        self.navigation_filter = filter;
+    }
+
+    fn check_navigation(&self, id: &str, url: &str, kind: NavigationKind) -> Result<(), Error> {
+        let request = NavigationRequest {
+            browser_id: id,
+            url,
+            kind,
+        };
+        if self.navigation_filter.allow(&request) {
+            Ok(())
+        } else {
+            Err(Error::NavigationBlocked(url.to_string()))
         }
     }
 
@@ -147,3 +338,354 @@ impl BrowserManager {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{BackendEvent, TestBackend, TestCall};
+    use crate::governor::RenderState;
+
+    fn bounds(width: i32, height: i32) -> CefBounds {
+        CefBounds { x: 0, y: 0, width, height }
+    }
+
+    #[test]
+    fn test_create_and_get_frame_is_deterministic() {
+        let backend = TestBackend::new();
+        let manager = BrowserManager::with_backend(backend.clone());
+        manager.create_browser("b1", "https://example.com", bounds(120, 100)).unwrap();
+
+        let frame = manager.get_frame("b1").unwrap();
+        assert_eq!((frame.width, frame.height), (120, 100));
+        assert_eq!(frame.data, TestBackend::expected_frame(1, 120, 100));
+
+        // Nothing repainted since the last poll
+        assert!(manager.get_frame("b1").is_none());
+        assert_eq!(
+            backend.calls(),
+            vec![TestCall::Create {
+                id: "b1".into(),
+                url: "https://example.com".into(),
+                width: 120,
+                height: 100,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_resize_repaints_at_new_size() {
+        let manager = BrowserManager::with_backend(TestBackend::new());
+        manager.create_browser("b1", "https://example.com", bounds(100, 100)).unwrap();
+        manager.get_frame("b1").unwrap();
+
+        manager.update_bounds("b1", bounds(200, 150)).unwrap();
+        let frame = manager.get_frame("b1").unwrap();
+        assert_eq!((frame.width, frame.height), (200, 150));
+        assert_eq!(frame.data, TestBackend::expected_frame(2, 200, 150));
+    }
+
+    #[test]
+    fn test_resize_keeps_last_frame_until_repaint() {
+        let manager = BrowserManager::with_backend(TestBackend::new());
+        manager.create_browser("b1", "https://example.com", bounds(100, 100)).unwrap();
+        manager.get_frame("b1").unwrap();
+        let seq = manager.get_frame_delta("b1", 0).unwrap().seq;
+
+        // Hidden browsers don't paint - the resize alone must not publish
+        manager.set_visibility("b1", false).unwrap();
+        manager.update_bounds("b1", bounds(200, 150)).unwrap();
+        assert!(manager.get_frame_delta("b1", seq).is_none());
+        assert!(manager.get_frame("b1").is_none());
+
+        manager.set_visibility("b1", true).unwrap();
+        let delta = manager.get_frame_delta("b1", seq).unwrap();
+        assert!(delta.full);
+        assert_eq!((delta.width, delta.height), (200, 150));
+        assert_eq!(delta.regions[0].data, TestBackend::expected_frame(2, 200, 150));
+    }
+
+    #[test]
+    fn test_load_events_update_browser_info() {
+        let backend = TestBackend::new();
+        let manager = BrowserManager::with_backend(backend.clone());
+        manager.create_browser("b1", "https://example.com", bounds(100, 100)).unwrap();
+
+        let info = manager.list_browsers().pop().unwrap();
+        assert!(!info.is_loading);
+
+        manager.navigate("b1", "https://example.org").unwrap();
+        backend.queue_event("b1", BackendEvent::LoadStart { url: "https://example.org/redirected".into() });
+
+        let info = manager.list_browsers().pop().unwrap();
+        assert_eq!(info.url, "https://example.org/redirected");
+        assert!(info.is_loading);
+    }
+
+    #[test]
+    fn test_hidden_browser_is_suspended() {
+        let backend = TestBackend::new();
+        let manager = BrowserManager::with_backend(backend.clone());
+        manager.create_browser("b1", "https://example.com", bounds(100, 100)).unwrap();
+        manager.get_frame("b1").unwrap();
+
+        manager.set_visibility("b1", false).unwrap();
+        manager.navigate("b1", "https://example.org").unwrap();
+        assert!(manager.get_frame("b1").is_none());
+        assert_eq!(manager.list_browsers()[0].render_state, RenderState::Suspended);
+
+        // Showing it again delivers the pending paint
+        manager.set_visibility("b1", true).unwrap();
+        assert!(manager.get_frame("b1").is_some());
+
+        let governor_calls: Vec<TestCall> = backend
+            .calls()
+            .into_iter()
+            .filter(|c| matches!(c, TestCall::Hidden { .. } | TestCall::FrameRate { .. }))
+            .collect();
+        assert_eq!(
+            governor_calls,
+            vec![
+                TestCall::Hidden { id: "b1".into(), hidden: true },
+                TestCall::Hidden { id: "b1".into(), hidden: false },
+                TestCall::FrameRate { id: "b1".into(), fps: 60 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_viewport_throttles_offscreen_and_occluded_browsers() {
+        let manager = BrowserManager::with_backend(TestBackend::new());
+        manager.create_browser("inside", "https://a.com", bounds(100, 100)).unwrap();
+        manager
+            .create_browser("outside", "https://b.com", CefBounds { x: 2000, y: 0, width: 100, height: 100 })
+            .unwrap();
+
+        let state = |id: &str| {
+            manager
+                .list_browsers()
+                .into_iter()
+                .find(|b| b.id == id)
+                .unwrap()
+                .render_state
+        };
+
+        manager.set_viewport(bounds(1000, 800), None).unwrap();
+        assert_eq!(state("inside"), RenderState::Active);
+        assert_eq!(state("outside"), RenderState::Throttled);
+
+        // Moving into the viewport activates it again
+        manager
+            .update_bounds("outside", CefBounds { x: 500, y: 0, width: 100, height: 100 })
+            .unwrap();
+        assert_eq!(state("outside"), RenderState::Active);
+
+        manager.set_viewport(bounds(1000, 800), Some("inside".into())).unwrap();
+        assert_eq!(state("inside"), RenderState::Active);
+        assert_eq!(state("outside"), RenderState::Throttled);
+    }
+
+    #[test]
+    fn test_dom_double_click_reaches_backend() {
+        let backend = TestBackend::new();
+        let manager = BrowserManager::with_backend(backend.clone());
+        manager
+            .create_browser("b1", "https://example.com", CefBounds { x: 100, y: 100, width: 400, height: 300 })
+            .unwrap();
+
+        let transform = CanvasTransform { offset_x: 0.0, offset_y: 0.0, scale: 2.0 };
+        for (event_type, time_stamp) in [("mousedown", 0.0), ("mouseup", 40.0), ("mousedown", 120.0)] {
+            let dom = DomMouseEvent {
+                event_type: event_type.into(),
+                client_x: 300.0,
+                client_y: 260.0,
+                time_stamp,
+                ..Default::default()
+            };
+            manager.send_dom_mouse_event("b1", &dom, &transform).unwrap();
+        }
+
+        let events: Vec<MouseEvent> = backend
+            .calls()
+            .into_iter()
+            .filter_map(|call| match call {
+                TestCall::MouseEvent { event, .. } => Some(event),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!((events[2].x, events[2].y), (50, 30));
+        assert_eq!(events[2].click_count, 2);
+    }
+
+    #[test]
+    fn test_lifecycle_events_are_drained_in_order() {
+        let backend = TestBackend::new();
+        let manager = BrowserManager::with_backend(backend.clone());
+        manager.create_browser("b1", "https://example.com", bounds(100, 100)).unwrap();
+        manager.take_events();
+
+        manager.navigate("b1", "https://example.org").unwrap();
+        backend.queue_event("b1", BackendEvent::TitleChanged { title: "Example".into() });
+
+        let names: Vec<&str> = manager.take_events().iter().map(|e| e.event.name()).collect();
+        assert_eq!(
+            names,
+            vec!["load-start", "url-changed", "history-changed", "load-end", "title-changed"]
+        );
+        assert!(manager.take_events().is_empty());
+
+        let info = manager.list_browsers().pop().unwrap();
+        assert_eq!(info.title.as_deref(), Some("Example"));
+        assert!(info.can_go_back);
+        assert!(!info.can_go_forward);
+    }
+
+    #[test]
+    fn test_load_error_and_crash_stop_loading() {
+        let backend = TestBackend::new();
+        let manager = BrowserManager::with_backend(backend.clone());
+        manager.create_browser("b1", "https://example.com", bounds(100, 100)).unwrap();
+
+        backend.queue_event("b1", BackendEvent::LoadStart { url: "https://down.example".into() });
+        backend.queue_event(
+            "b1",
+            BackendEvent::LoadError {
+                url: "https://down.example".into(),
+                error_code: -105,
+                error_text: "ERR_NAME_NOT_RESOLVED".into(),
+            },
+        );
+        let info = manager.list_browsers().pop().unwrap();
+        assert!(!info.is_loading);
+        assert_eq!(info.url, "https://down.example");
+
+        backend.queue_event("b1", BackendEvent::LoadStart { url: "https://example.com".into() });
+        backend.queue_event("b1", BackendEvent::Crashed { reason: "killed".into() });
+        assert!(!manager.list_browsers().pop().unwrap().is_loading);
+    }
+
+    #[test]
+    fn test_event_payload_shape() {
+        let event = BrowserEvent {
+            browser_id: "b1".into(),
+            event: BackendEvent::LoadEnd { url: "https://example.com".into(), http_status: 404 },
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            serde_json::json!({
+                "browser_id": "b1",
+                "type": "load-end",
+                "url": "https://example.com",
+                "http_status": 404,
+            })
+        );
+    }
+
+    #[test]
+    fn test_back_forward_updates_history_state() {
+        let manager = BrowserManager::with_backend(TestBackend::new());
+        manager.create_browser("b1", "https://a.com", bounds(100, 100)).unwrap();
+        manager.navigate("b1", "https://b.com").unwrap();
+
+        manager.go_back("b1").unwrap();
+        let info = manager.list_browsers().pop().unwrap();
+        assert_eq!(info.url, "https://a.com");
+        assert!(!info.can_go_back);
+        assert!(info.can_go_forward);
+
+        manager.go_forward("b1").unwrap();
+        manager.reload("b1", true).unwrap();
+        let info = manager.list_browsers().pop().unwrap();
+        assert_eq!(info.url, "https://b.com");
+        assert!(info.can_go_back);
+        assert!(!info.can_go_forward);
+    }
+
+    #[test]
+    fn test_thumbnail_of_suspended_browser_uses_last_frame() {
+        let manager = BrowserManager::with_backend(TestBackend::new());
+        manager.create_browser("b1", "https://example.com", bounds(400, 300)).unwrap();
+        manager.get_frame("b1").unwrap();
+        manager.set_visibility("b1", false).unwrap();
+
+        let thumb = manager.capture_thumbnail("b1", 100, 100).unwrap();
+        assert_eq!((thumb.width, thumb.height), (100, 75));
+        assert_eq!(thumb.url, "https://example.com");
+        assert!(manager.capture_thumbnail("missing", 100, 100).is_err());
+    }
+
+    #[test]
+    fn test_unknown_browser_is_an_error() {
+        let manager = BrowserManager::with_backend(TestBackend::new());
+        assert!(manager.navigate("missing", "https://example.com").is_err());
+        assert!(manager.go_back("missing").is_err());
+        assert!(manager.close_browser("missing").is_err());
+        assert_eq!(manager.go_forward("missing"), Err(Error::BrowserNotFound("missing".into())));
+
+        manager.create_browser("b1", "https://example.com", bounds(400, 300)).unwrap();
+        assert_eq!(
+            manager.create_browser("b1", "https://example.com", bounds(400, 300)),
+            Err(Error::BrowserExists("b1".into()))
+        );
+        assert_eq!(manager.browser_info("b1").unwrap().url, "https://example.com");
+    }
+
+    #[test]
+    fn test_request_filter_reaches_backend() {
+        use crate::request_filter::{RequestFilter, ResourceRequest, ResourceType};
+
+        struct BlockTrackers;
+        impl RequestFilter for BlockTrackers {
+            fn should_block(&self, request: &ResourceRequest<'_>) -> bool {
+                request.browser_id == "b1" && request.url.contains("tracker")
+            }
+        }
+
+        let backend = TestBackend::new();
+        let manager = BrowserManager::with_backend(backend.clone());
+        let slot = RequestFilterSlot::default();
+        manager.set_request_filter(slot.clone());
+        manager.create_browser("b1", "https://example.com", bounds(400, 300)).unwrap();
+
+        let tracker = "https://tracker.example/pixel.gif";
+        assert!(backend.load_resource("b1", tracker, None, ResourceType::Image));
+
+        // Filter registered after the browser was created
+        slot.set(Some(Arc::new(BlockTrackers)));
+        assert!(!backend.load_resource("b1", tracker, Some("https://example.com"), ResourceType::Image));
+        assert!(backend.load_resource("b1", "https://example.com/app.js", None, ResourceType::Script));
+        assert!(backend.load_resource("b2", tracker, None, ResourceType::Image));
+    }
+
+    #[test]
+    fn test_navigation_filter_guards_host_and_page_navigations() {
+        use crate::navigation_filter::NavigationFilter;
+
+        struct DenyBlocked;
+        impl NavigationFilter for DenyBlocked {
+            fn allow(&self, request: &NavigationRequest<'_>) -> bool {
+                !request.url.contains("blocked")
+            }
+        }
+
+        let backend = TestBackend::new();
+        let mut manager = BrowserManager::with_backend(backend.clone());
+        let slot = NavigationFilterSlot::default();
+        manager.set_navigation_filter(slot.clone());
+        slot.set(Some(Arc::new(DenyBlocked)));
+
+        let blocked = "https://blocked.example/";
+        assert_eq!(
+            manager.create_browser("b1", blocked, bounds(400, 300)),
+            Err(Error::NavigationBlocked(blocked.into()))
+        );
+        assert!(manager.browser_info("b1").is_none());
+
+        manager.create_browser("b1", "https://example.com", bounds(400, 300)).unwrap();
+        assert_eq!(manager.navigate("b1", blocked), Err(Error::NavigationBlocked(blocked.into())));
+        assert!(!backend.calls().iter().any(|call| matches!(call, TestCall::Navigate { .. })));
+
+        assert!(!backend.navigate_in_page("b1", blocked, NavigationKind::Automatic));
+        assert!(backend.navigate_in_page("b1", "https://example.com/next", NavigationKind::UserInitiated));
+    }
+}
@@ -0,0 +1,90 @@
+//! Navigation filtering hook - lets the host app veto page loads
+//!
+//! The host registers a `NavigationFilter` through
+//! `CefPluginState::set_navigation_filter`. The manager asks it before
+//! `cef_create_browser` and `cef_navigate` load a URL; backends ask it before
+//! each navigation inside the page (CEF: `OnBeforeBrowse`) and cancel the
+//! navigation when it says no.
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Why a browser is about to load a URL
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NavigationKind {
+    /// Initial URL of `cef_create_browser`
+    Create,
+    /// `cef_navigate` from the host
+    Navigate,
+    /// Main-frame navigation started by a user gesture in the page
+    /// (link click, form submit) that is not a redirect
+    UserInitiated,
+    /// Redirects, script-driven and subframe navigations
+    Automatic,
+}
+
+/// A navigation as seen by the manager or backend
+#[derive(Debug, Clone, Copy)]
+pub struct NavigationRequest<'a> {
+    pub browser_id: &'a str,
+    pub url: &'a str,
+    pub kind: NavigationKind,
+}
+
+/// Decides whether a navigation may proceed
+pub trait NavigationFilter: Send + Sync + 'static {
+    fn allow(&self, request: &NavigationRequest<'_>) -> bool;
+}
+
+/// Shared slot for the host's filter; cheap to clone into backends.
+/// Empty until the host registers a filter - then everything is allowed.
+#[derive(Clone, Default)]
+pub struct NavigationFilterSlot(Arc<RwLock<Option<Arc<dyn NavigationFilter>>>>);
+
+impl NavigationFilterSlot {
+    pub fn set(&self, filter: Option<Arc<dyn NavigationFilter>>) {
+        *self.0.write() = filter;
+    }
+
+    pub fn allow(&self, request: &NavigationRequest<'_>) -> bool {
+        // Clone out of the lock so a slow filter never blocks `set`
+        let filter = self.0.read().clone();
+        filter.is_none_or(|filter| filter.allow(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct OnlyExampleCom;
+
+    impl NavigationFilter for OnlyExampleCom {
+        fn allow(&self, request: &NavigationRequest<'_>) -> bool {
+            request.url.starts_with("https://example.com/")
+        }
+    }
+
+    #[test]
+    fn test_slot_allows_everything_until_set() {
+        let slot = NavigationFilterSlot::default();
+        let request = NavigationRequest {
+            browser_id: "b1",
+            url: "https://tracker.example/",
+            kind: NavigationKind::Automatic,
+        };
+        assert!(slot.allow(&request));
+
+        slot.clone().set(Some(Arc::new(OnlyExampleCom)));
+        assert!(!slot.allow(&request));
+        assert!(slot.allow(&NavigationRequest {
+            url: "https://example.com/docs",
+            ..request
+        }));
+
+        slot.set(None);
+        assert!(slot.allow(&request));
+    }
+}
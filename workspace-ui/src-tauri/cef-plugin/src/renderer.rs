@@ -1,8 +1,22 @@
 //! OSR Browser Instance - Individual browser with off-screen rendering
 
-use crate::{BrowserInfo, CefBounds, FrameData, KeyEvent, MouseEvent};
-use parking_lot::RwLock;
+use crate::backend::{BackendEvent, BrowserBackend};
+use crate::codec::FrameCodec;
+use crate::governor::{FrameGovernor, RenderState};
+use crate::input::{self, CanvasTransform, ClickTracker, DomKeyEvent, DomMouseEvent};
+use crate::thumbnail::{self, Thumbnail};
+use crate::transfer::{coalesce_rects, copy_rect, crop_rect, DirtyRect};
+use crate::{BrowserInfo, CefBounds, FrameData, FrameDelta, FrameRegion, KeyEvent, MouseEvent};
+use parking_lot::{Mutex, RwLock};
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::Instant;
+
+/// How many dirty rects are kept for delta requests
+const DIRTY_HISTORY: usize = 64;
+
+/// Deltas covering more than this share of the frame are sent as full frames
+const FULL_FRAME_THRESHOLD: f64 = 0.5;
 
 /// Frame buffer for storing rendered content
 struct FrameBuffer {
@@ -10,6 +24,23 @@ struct FrameBuffer {
     width: u32,
     height: u32,
     dirty: bool,
+    /// Sequence number of the latest change (0 = nothing painted yet)
+    seq: u64,
+    /// Sequence number of the last resize; deltas across it are full frames
+    resize_seq: u64,
+    /// Dirty rects of recent paints with their sequence number, oldest first
+    history: VecDeque<(u64, DirtyRect)>,
+    /// Changes at or before this sequence number are no longer in `history`
+    history_floor: u64,
+}
+
+/// Changed regions since a given sequence number
+struct BufferDelta {
+    seq: u64,
+    width: u32,
+    height: u32,
+    full: bool,
+    regions: Vec<FrameRegion>,
 }
 
 impl FrameBuffer {
@@ -20,26 +51,53 @@ impl FrameBuffer {
             width,
             height,
             dirty: false,
+            seq: 0,
+            resize_seq: 0,
+            history: VecDeque::new(),
+            history_floor: 0,
         }
     }
 
-    fn resize(&mut self, width: u32, height: u32) {
+    fn update(&mut self, buffer: &[u8], width: u32, height: u32, dirty_rects: &[DirtyRect]) {
         let size = (width * height * 4) as usize;
-        self.data.resize(size, 0);
-        self.width = width;
-        self.height = height;
-        self.dirty = true;
-    }
+        if buffer.len() < size {
+            return;
+        }
 
-    fn update(&mut self, buffer: &[u8], width: u32, height: u32) {
-        if self.width != width || self.height != height {
-            self.resize(width, height);
+        self.seq += 1;
+
+        // Reallocate only together with the first paint at the new size, so
+        // a resize never publishes an empty buffer
+        let resized = self.width != width || self.height != height;
+        if resized {
+            self.data.resize(size, 0);
+            self.width = width;
+            self.height = height;
+            self.resize_seq = self.seq;
+            self.history.clear();
         }
-        let size = (width * height * 4) as usize;
-        if buffer.len() >= size {
-            self.data[..size].copy_from_slice(&buffer[..size]);
-            self.dirty = true;
+
+        let rects: Vec<DirtyRect> = if resized || dirty_rects.is_empty() {
+            vec![DirtyRect::full(width, height)]
+        } else {
+            dirty_rects
+                .iter()
+                .filter_map(|rect| rect.clamp_to(width, height))
+                .collect()
+        };
+
+        for rect in rects {
+            copy_rect(&mut self.data, buffer, width, &rect);
+            self.history.push_back((self.seq, rect));
         }
+
+        while self.history.len() > DIRTY_HISTORY {
+            if let Some((seq, _)) = self.history.pop_front() {
+                self.history_floor = self.history_floor.max(seq);
+            }
+        }
+
+        self.dirty = true;
     }
 
     fn take_if_dirty(&mut self) -> Option<(Vec<u8>, u32, u32)> {
@@ -50,79 +108,179 @@ impl FrameBuffer {
             None
         }
     }
+
+    /// Current contents, `None` before the first paint or resize
+    fn snapshot(&self) -> Option<(Vec<u8>, u32, u32)> {
+        (self.seq > 0).then(|| (self.data.clone(), self.width, self.height))
+    }
+
+    /// Regions changed after `since_seq`, or `None` if the client is up to date
+    fn delta_since(&self, since_seq: u64) -> Option<BufferDelta> {
+        if self.seq == 0 || since_seq == self.seq {
+            return None;
+        }
+
+        let mut full = since_seq == 0
+            || since_seq > self.seq
+            || since_seq < self.resize_seq
+            || since_seq < self.history_floor;
+
+        let mut rects = Vec::new();
+        if !full {
+            let changed: Vec<DirtyRect> = self
+                .history
+                .iter()
+                .filter(|(seq, _)| *seq > since_seq)
+                .map(|(_, rect)| rect.clone())
+                .collect();
+            rects = coalesce_rects(&changed);
+
+            let changed_area: u64 = rects.iter().map(DirtyRect::area).sum();
+            let frame_area = self.width as u64 * self.height as u64;
+            full = changed_area as f64 > frame_area as f64 * FULL_FRAME_THRESHOLD;
+        }
+
+        if full {
+            rects = vec![DirtyRect::full(self.width, self.height)];
+        }
+
+        let regions = rects
+            .into_iter()
+            .map(|rect| FrameRegion {
+                data: if full {
+                    self.data.clone()
+                } else {
+                    crop_rect(&self.data, self.width, &rect)
+                },
+                x: rect.x,
+                y: rect.y,
+                width: rect.width,
+                height: rect.height,
+            })
+            .collect();
+
+        Some(BufferDelta {
+            seq: self.seq,
+            width: self.width,
+            height: self.height,
+            full,
+            regions,
+        })
+    }
 }
 
-/// Single CEF browser instance with off-screen rendering
-pub struct OsrBrowserInstance {
-    id: String,
+/// Lifecycle events kept per browser until they are drained
+const MAX_PENDING_EVENTS: usize = 256;
+
+/// Page state derived from backend events
+#[derive(Debug, Clone, Default)]
+struct PageState {
     url: String,
+    title: Option<String>,
+    is_loading: bool,
+    can_go_back: bool,
+    can_go_forward: bool,
+}
+
+impl PageState {
+    fn apply(&mut self, event: &BackendEvent) {
+        match event {
+            BackendEvent::LoadStart { url } => {
+                self.url = url.clone();
+                self.is_loading = true;
+            }
+            BackendEvent::LoadEnd { .. } | BackendEvent::LoadError { .. } | BackendEvent::Crashed { .. } => {
+                self.is_loading = false;
+            }
+            BackendEvent::TitleChanged { title } => self.title = Some(title.clone()),
+            BackendEvent::UrlChanged { url } => self.url = url.clone(),
+            BackendEvent::HistoryChanged { can_go_back, can_go_forward } => {
+                self.can_go_back = *can_go_back;
+                self.can_go_forward = *can_go_forward;
+            }
+            BackendEvent::FaviconChanged { .. } => {}
+        }
+    }
+}
+
+/// Single browser instance with off-screen rendering, driven by a `BrowserBackend`
+pub struct OsrBrowserInstance<B: BrowserBackend> {
+    id: String,
+    page: RwLock<PageState>,
     bounds: RwLock<CefBounds>,
     frame_buffer: Arc<RwLock<FrameBuffer>>,
-    is_loading: RwLock<bool>,
+    /// Events not yet drained by `take_events`
+    events: Mutex<VecDeque<BackendEvent>>,
     is_focused: RwLock<bool>,
-    // TODO: Add actual CEF browser handle
-    // browser: Option<cef::Browser>,
+    governor: Mutex<FrameGovernor>,
+    click_tracker: Mutex<ClickTracker>,
+    backend: Arc<B>,
+    handle: Mutex<B::Handle>,
 }
 
-impl OsrBrowserInstance {
+impl<B: BrowserBackend> OsrBrowserInstance<B> {
     /// Create a new browser instance
-    pub fn new(id: &str, url: &str, bounds: CefBounds) -> Result<Self, String> {
+    pub fn new(backend: Arc<B>, id: &str, url: &str, bounds: CefBounds) -> Result<Self, String> {
         let width = bounds.width.max(100) as u32;
         let height = bounds.height.max(100) as u32;
 
-        let instance = Self {
+        let handle = backend.create(id, url, width, height)?;
+
+        Ok(Self {
             id: id.to_string(),
-            url: url.to_string(),
+            page: RwLock::new(PageState {
+                url: url.to_string(),
+                is_loading: true,
+                ..Default::default()
+            }),
             bounds: RwLock::new(bounds),
             frame_buffer: Arc::new(RwLock::new(FrameBuffer::new(width, height))),
-            is_loading: RwLock::new(true),
+            events: Mutex::new(VecDeque::new()),
             is_focused: RwLock::new(false),
-        };
-
-        // TODO: Create actual CEF browser with off-screen rendering
-        // This would involve:
-        // 1. Create WindowInfo with windowless_rendering_enabled = true
-        // 2. Create BrowserSettings with windowless_frame_rate = 60
-        // 3. Create RenderHandler that captures OnPaint callbacks
-        // 4. Create browser via browser_host_create_browser_sync
-
-        // For now, we'll generate a placeholder frame
-        instance.generate_placeholder_frame();
-
-        Ok(instance)
+            governor: Mutex::new(FrameGovernor::default()),
+            click_tracker: Mutex::new(ClickTracker::default()),
+            backend,
+            handle: Mutex::new(handle),
+        })
     }
 
-    /// Generate a placeholder frame (for testing before CEF is fully integrated)
-    fn generate_placeholder_frame(&self) {
-        let bounds = self.bounds.read();
-        let width = bounds.width.max(100) as u32;
-        let height = bounds.height.max(100) as u32;
-        drop(bounds);
+    /// Pull pending paints and lifecycle events from the backend
+    fn pump(&self) {
+        let frame_due = self.governor.lock().frame_due(Instant::now());
+        let mut handle = self.handle.lock();
 
-        let mut buffer = self.frame_buffer.write();
+        if let Some(paint) = frame_due.then(|| self.backend.poll_paint(&mut handle)).flatten() {
+            self.frame_buffer
+                .write()
+                .update(&paint.buffer, paint.width, paint.height, &paint.dirty_rects);
+        }
 
-        // Fill with a gradient (placeholder)
-        buffer.resize(width, height);
-        for y in 0..height {
-            for x in 0..width {
-                let idx = ((y * width + x) * 4) as usize;
-                if idx + 3 < buffer.data.len() {
-                    // BGRA format
-                    buffer.data[idx] = ((x * 255 / width) as u8);     // B
-                    buffer.data[idx + 1] = ((y * 255 / height) as u8); // G
-                    buffer.data[idx + 2] = 100;                         // R
-                    buffer.data[idx + 3] = 255;                         // A
-                }
+        let events = self.backend.poll_events(&mut handle);
+        if events.is_empty() {
+            return;
+        }
+
+        let mut page = self.page.write();
+        let mut pending = self.events.lock();
+        for event in events {
+            page.apply(&event);
+            if pending.len() == MAX_PENDING_EVENTS {
+                pending.pop_front();
             }
+            pending.push_back(event);
         }
-        buffer.dirty = true;
+    }
+
+    /// Pump the backend and return the lifecycle events since the last call
+    pub fn take_events(&self) -> Vec<BackendEvent> {
+        self.pump();
+        self.events.lock().drain(..).collect()
     }
 
     /// Close the browser
     pub fn close(&self) -> Result<(), String> {
-        // TODO: Close actual CEF browser
         log::info!("Closing browser: {}", self.id);
-        Ok(())
+        self.backend.close(&mut self.handle.lock())
     }
 
     /// Update bounds (resize)
@@ -132,64 +290,152 @@ impl OsrBrowserInstance {
 
         *self.bounds.write() = bounds;
 
-        // Resize frame buffer
-        self.frame_buffer.write().resize(width, height);
-
-        // TODO: Notify CEF browser of resize via host.was_resized()
-
-        // Regenerate placeholder for now
-        self.generate_placeholder_frame();
-
-        Ok(())
+        // The frame buffer keeps the last frame until the backend paints at
+        // the new size
+        self.backend.resize(&mut self.handle.lock(), width, height)
     }
 
     /// Navigate to URL
     pub fn navigate(&self, url: &str) -> Result<(), String> {
-        // TODO: Call browser.get_main_frame().load_url(url)
         log::info!("Navigate {} to: {}", self.id, url);
-        Ok(())
+        self.backend.navigate(&mut self.handle.lock(), url)
+    }
+
+    pub fn go_back(&self) -> Result<(), String> {
+        self.backend.go_back(&mut self.handle.lock())
+    }
+
+    pub fn go_forward(&self) -> Result<(), String> {
+        self.backend.go_forward(&mut self.handle.lock())
+    }
+
+    /// Reload the current page, optionally bypassing the cache
+    pub fn reload(&self, ignore_cache: bool) -> Result<(), String> {
+        self.backend.reload(&mut self.handle.lock(), ignore_cache)
+    }
+
+    /// Stop loading the current page
+    pub fn stop(&self) -> Result<(), String> {
+        self.backend.stop(&mut self.handle.lock())
     }
 
     /// Get the latest frame if dirty
     pub fn get_frame(&self) -> Option<FrameData> {
+        self.pump();
+
         let mut buffer = self.frame_buffer.write();
         buffer.take_if_dirty().map(|(data, width, height)| FrameData {
             browser_id: self.id.clone(),
             width,
             height,
-            format: "BGRA8".to_string(),
+            format: FrameCodec::Raw.format().to_string(),
             data,
         })
     }
 
+    /// Get the regions that changed since the client's last acknowledged frame
+    pub fn get_frame_delta(&self, since_seq: u64) -> Option<FrameDelta> {
+        self.pump();
+
+        let buffer = self.frame_buffer.read();
+        buffer.delta_since(since_seq).map(|delta| FrameDelta {
+            browser_id: self.id.clone(),
+            seq: delta.seq,
+            width: delta.width,
+            height: delta.height,
+            format: FrameCodec::Raw.format().to_string(),
+            full: delta.full,
+            regions: delta.regions,
+        })
+    }
+
+    /// Downscaled PNG of the current frame
+    pub fn capture_thumbnail(&self, max_width: u32, max_height: u32) -> Result<Thumbnail, String> {
+        self.pump();
+
+        let (data, width, height) = self
+            .frame_buffer
+            .read()
+            .snapshot()
+            .ok_or_else(|| format!("Browser '{}' has not painted yet", self.id))?;
+        let url = self.page.read().url.clone();
+
+        thumbnail::create_thumbnail(&self.id, &url, &data, width, height, max_width, max_height)
+    }
+
     /// Send mouse event
     pub fn send_mouse_event(&self, event: MouseEvent) -> Result<(), String> {
-        // TODO: Forward to CEF via browser.host().send_mouse_*_event()
-        log::trace!("Mouse event on {}: {:?}", self.id, event.event_type);
-        Ok(())
+        self.backend.send_mouse_event(&mut self.handle.lock(), &event)
     }
 
     /// Send keyboard event
     pub fn send_key_event(&self, event: KeyEvent) -> Result<(), String> {
-        // TODO: Forward to CEF via browser.host().send_key_event()
-        log::trace!("Key event on {}: {:?}", self.id, event.event_type);
+        self.backend.send_key_event(&mut self.handle.lock(), &event)
+    }
+
+    /// Translate a DOM mouse event from the canvas and send it
+    pub fn send_dom_mouse_event(&self, dom: &DomMouseEvent, transform: &CanvasTransform) -> Result<(), String> {
+        let mut event = input::translate_mouse_event(dom, transform, &self.bounds.read())?;
+        self.click_tracker.lock().apply(&mut event, dom.time_stamp);
+        self.send_mouse_event(event)
+    }
+
+    /// Translate a DOM keyboard event and send the resulting key events
+    pub fn send_dom_key_event(&self, dom: &DomKeyEvent) -> Result<(), String> {
+        for event in input::translate_key_event(dom)? {
+            self.send_key_event(event)?;
+        }
         Ok(())
     }
 
     /// Set focus state
     pub fn set_focus(&self, focused: bool) -> Result<(), String> {
         *self.is_focused.write() = focused;
-        // TODO: Call browser.host().set_focus(focused)
+        self.backend.set_focus(&mut self.handle.lock(), focused)
+    }
+
+    /// Change the governor and push the resulting frame rate / hidden state
+    /// to the backend if it changed
+    pub fn update_governor(&self, change: impl FnOnce(&mut FrameGovernor)) -> Result<(), String> {
+        let (old_state, old_fps, state, fps) = {
+            let mut governor = self.governor.lock();
+            let (old_state, old_fps) = (governor.state(), governor.effective_fps());
+            change(&mut governor);
+            (old_state, old_fps, governor.state(), governor.effective_fps())
+        };
+
+        let suspended = state == RenderState::Suspended;
+        let mut handle = self.handle.lock();
+        if suspended != (old_state == RenderState::Suspended) {
+            self.backend.set_hidden(&mut handle, suspended)?;
+        }
+        if !suspended && fps != old_fps {
+            self.backend.set_frame_rate(&mut handle, fps)?;
+        }
         Ok(())
     }
 
+    /// Current bounds
+    pub fn bounds(&self) -> CefBounds {
+        self.bounds.read().clone()
+    }
+
     /// Get browser info
     pub fn get_info(&self) -> BrowserInfo {
+        self.pump();
+
+        let governor = self.governor.lock();
+        let page = self.page.read();
         BrowserInfo {
             id: self.id.clone(),
-            url: self.url.clone(),
+            url: page.url.clone(),
+            title: page.title.clone(),
             bounds: self.bounds.read().clone(),
-            is_loading: *self.is_loading.read(),
+            is_loading: page.is_loading,
+            can_go_back: page.can_go_back,
+            can_go_forward: page.can_go_forward,
+            render_state: governor.state(),
+            frame_rate: governor.effective_fps(),
         }
     }
 }
@@ -228,7 +474,89 @@ impl RenderHandler for OsrRenderHandler {
         let buffer_size = (width * height * 4) as usize;
         let buffer_slice = unsafe { std::slice::from_raw_parts(buffer, buffer_size) };
 
-        self.frame_buffer.write().update(buffer_slice, width as u32, height as u32);
+        let dirty: Vec<DirtyRect> = dirty_rects.iter().map(|r| DirtyRect {
+            x: r.x as u32,
+            y: r.y as u32,
+            width: r.width as u32,
+            height: r.height as u32,
+        }).collect();
+
+        self.frame_buffer.write().update(buffer_slice, width as u32, height as u32, &dirty);
     }
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_frame(width: u32, height: u32, value: u8) -> Vec<u8> {
+        vec![value; (width * height * 4) as usize]
+    }
+
+    #[test]
+    fn test_update_copies_only_dirty_rects() {
+        let mut buffer = FrameBuffer::new(4, 4);
+        let rect = DirtyRect { x: 1, y: 1, width: 2, height: 1 };
+        buffer.update(&solid_frame(4, 4, 9), 4, 4, &[rect]);
+
+        let touched: Vec<usize> = buffer
+            .data
+            .chunks_exact(4)
+            .enumerate()
+            .filter(|(_, p)| p[0] == 9)
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(touched, vec![5, 6]);
+    }
+
+    #[test]
+    fn test_delta_since_returns_changed_regions() {
+        let mut buffer = FrameBuffer::new(100, 100);
+        buffer.update(&solid_frame(100, 100, 1), 100, 100, &[]);
+        let first = buffer.delta_since(0).unwrap();
+        assert!(first.full);
+
+        let rect = DirtyRect { x: 10, y: 20, width: 5, height: 5 };
+        buffer.update(&solid_frame(100, 100, 2), 100, 100, &[rect]);
+
+        let delta = buffer.delta_since(first.seq).unwrap();
+        assert!(!delta.full);
+        assert_eq!(delta.regions.len(), 1);
+        let region = &delta.regions[0];
+        assert_eq!((region.x, region.y, region.width, region.height), (10, 20, 5, 5));
+        assert_eq!(region.data, vec![2; 5 * 5 * 4]);
+
+        // Up to date - nothing to send
+        assert!(buffer.delta_since(delta.seq).is_none());
+    }
+
+    #[test]
+    fn test_delta_across_resize_is_full_frame() {
+        let mut buffer = FrameBuffer::new(100, 100);
+        buffer.update(&solid_frame(100, 100, 1), 100, 100, &[]);
+        let seq = buffer.delta_since(0).unwrap().seq;
+
+        let rect = DirtyRect { x: 0, y: 0, width: 1, height: 1 };
+        buffer.update(&solid_frame(200, 100, 3), 200, 100, &[rect]);
+
+        let delta = buffer.delta_since(seq).unwrap();
+        assert!(delta.full);
+        assert_eq!((delta.width, delta.height), (200, 100));
+        assert_eq!(delta.regions[0].data.len(), 200 * 100 * 4);
+    }
+
+    #[test]
+    fn test_delta_older_than_history_is_full_frame() {
+        let mut buffer = FrameBuffer::new(100, 100);
+        buffer.update(&solid_frame(100, 100, 1), 100, 100, &[]);
+        let seq = buffer.delta_since(0).unwrap().seq;
+
+        let rect = DirtyRect { x: 0, y: 0, width: 1, height: 1 };
+        for _ in 0..=DIRTY_HISTORY {
+            buffer.update(&solid_frame(100, 100, 2), 100, 100, std::slice::from_ref(&rect));
+        }
+
+        assert!(buffer.delta_since(seq).unwrap().full);
+    }
+}
@@ -0,0 +1,94 @@
+//! Request filtering hook - lets the host app block resource requests
+//!
+//! The plugin ships no filter rules of its own. The host registers a
+//! `RequestFilter` through `CefPluginState::set_request_filter`; backends ask
+//! it before loading each resource (CEF: `OnBeforeResourceLoad`) and cancel
+//! the request when it says so.
+
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// What a request loads (subset of CEF's `cef_resource_type_t`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceType {
+    MainFrame,
+    SubFrame,
+    Stylesheet,
+    Script,
+    Image,
+    Font,
+    Media,
+    Object,
+    Xhr,
+    Ping,
+    Other,
+}
+
+/// A resource request as seen by the backend
+#[derive(Debug, Clone, Copy)]
+pub struct ResourceRequest<'a> {
+    pub browser_id: &'a str,
+    pub url: &'a str,
+    /// URL of the frame that issued the request (first/third party checks)
+    pub frame_url: Option<&'a str>,
+    pub resource_type: ResourceType,
+}
+
+/// Decides whether a resource request is cancelled
+pub trait RequestFilter: Send + Sync + 'static {
+    fn should_block(&self, request: &ResourceRequest<'_>) -> bool;
+}
+
+/// Shared slot for the host's filter; cheap to clone into backends.
+/// Empty until the host registers a filter - then nothing is blocked.
+#[derive(Clone, Default)]
+pub struct RequestFilterSlot(Arc<RwLock<Option<Arc<dyn RequestFilter>>>>);
+
+impl RequestFilterSlot {
+    pub fn set(&self, filter: Option<Arc<dyn RequestFilter>>) {
+        *self.0.write() = filter;
+    }
+
+    pub fn should_block(&self, request: &ResourceRequest<'_>) -> bool {
+        // Clone out of the lock so a slow filter never blocks `set`
+        let filter = self.0.read().clone();
+        filter.is_some_and(|filter| filter.should_block(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct BlockScripts;
+
+    impl RequestFilter for BlockScripts {
+        fn should_block(&self, request: &ResourceRequest<'_>) -> bool {
+            request.resource_type == ResourceType::Script
+        }
+    }
+
+    #[test]
+    fn test_slot_is_empty_until_set() {
+        let slot = RequestFilterSlot::default();
+        let request = ResourceRequest {
+            browser_id: "b1",
+            url: "https://ads.example/ad.js",
+            frame_url: None,
+            resource_type: ResourceType::Script,
+        };
+        assert!(!slot.should_block(&request));
+
+        slot.clone().set(Some(Arc::new(BlockScripts)));
+        assert!(slot.should_block(&request));
+        assert!(!slot.should_block(&ResourceRequest {
+            resource_type: ResourceType::Image,
+            ..request
+        }));
+
+        slot.set(None);
+        assert!(!slot.should_block(&request));
+    }
+}
@@ -0,0 +1,358 @@
+//! Frame Streaming - Push binary frame packets to subscribers
+//!
+//! Each subscription runs a worker that polls the `BrowserManager` at a fixed
+//! rate and pushes changed regions as binary packets. A browser never has more
+//! than `MAX_IN_FLIGHT` unacknowledged packets; while the consumer lags, ticks
+//! are skipped and the next packet carries the merged changes, so intermediate
+//! frames are dropped instead of queued. Packets that stay unacknowledged
+//! for `ACK_TIMEOUT` count as lost (e.g. the consumer remounted); the browser
+//! then starts over with a full frame.
+//!
+//! Region data is encoded with the subscription's `FrameCodec`. For `XorRle`
+//! the subscription mirrors the frame the client has assembled so far, which
+//! is what each region is XORed against. Full frames are XORed against an
+//! empty frame, so they never depend on earlier packets.
+
+use crate::backend::BrowserBackend;
+use crate::codec::FrameCodec;
+use crate::manager::BrowserManager;
+use crate::transfer::{crop_rect, encode_frame_packet, paste_rect, DirtyRect};
+use crate::FrameDelta;
+use parking_lot::{Mutex, RwLock};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Polling interval of the stream worker (~60 fps)
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Unacknowledged packets allowed per browser before frames are dropped
+const MAX_IN_FLIGHT: usize = 2;
+
+/// Unacknowledged packets older than this are considered lost (~8 intervals)
+const ACK_TIMEOUT: Duration = Duration::from_millis(128);
+
+#[derive(Default)]
+struct BrowserStreamState {
+    /// Sequence number of the last packet sent (deltas are relative to it)
+    last_sent_seq: u64,
+    /// Sent but not yet acknowledged sequence numbers with send time, oldest first
+    in_flight: VecDeque<(u64, Instant)>,
+    /// Client-side frame after applying every sent packet (`XorRle` only)
+    reference: Vec<u8>,
+    reference_size: (u32, u32),
+}
+
+impl BrowserStreamState {
+    /// Encode the regions of `delta` in place and update the reference frame
+    fn encode_regions(&mut self, codec: FrameCodec, delta: &mut FrameDelta) {
+        delta.format = codec.format().to_string();
+        if codec != FrameCodec::XorRle {
+            for region in &mut delta.regions {
+                region.data = codec.encode(&region.data, None);
+            }
+            return;
+        }
+
+        let size = (delta.width, delta.height);
+        if self.reference_size != size || delta.full {
+            self.reference = vec![0; (delta.width * delta.height * 4) as usize];
+            self.reference_size = size;
+        }
+
+        for region in &mut delta.regions {
+            let rect = DirtyRect {
+                x: region.x,
+                y: region.y,
+                width: region.width,
+                height: region.height,
+            };
+            let previous = crop_rect(&self.reference, delta.width, &rect);
+            paste_rect(&mut self.reference, delta.width, &rect, &region.data);
+            region.data = codec.encode(&region.data, Some(&previous));
+        }
+    }
+}
+
+/// A single frame subscription
+pub struct FrameSubscription {
+    /// Written into every packet header
+    id: u32,
+    /// `None` streams every browser
+    browser_ids: Option<HashSet<String>>,
+    codec: FrameCodec,
+    active: AtomicBool,
+    browsers: Mutex<HashMap<String, BrowserStreamState>>,
+    dropped_frames: AtomicU64,
+}
+
+impl FrameSubscription {
+    pub fn new(id: u32, browser_ids: Option<Vec<String>>, codec: FrameCodec) -> Self {
+        Self {
+            id,
+            browser_ids: browser_ids.map(|ids| ids.into_iter().collect()),
+            codec,
+            active: AtomicBool::new(true),
+            browsers: Mutex::new(HashMap::new()),
+            dropped_frames: AtomicU64::new(0),
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn codec(&self) -> FrameCodec {
+        self.codec
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    pub fn stop(&self) {
+        self.active.store(false, Ordering::Relaxed);
+    }
+
+    /// Number of ticks skipped because the consumer lagged
+    pub fn dropped_frames(&self) -> u64 {
+        self.dropped_frames.load(Ordering::Relaxed)
+    }
+
+    fn wants(&self, id: &str) -> bool {
+        self.browser_ids.as_ref().is_none_or(|ids| ids.contains(id))
+    }
+
+    /// Consumer confirms it has applied every packet up to `seq`
+    pub fn ack(&self, browser_id: &str, seq: u64) {
+        if let Some(state) = self.browsers.lock().get_mut(browser_id) {
+            while state.in_flight.front().is_some_and(|(sent, _)| *sent <= seq) {
+                state.in_flight.pop_front();
+            }
+        }
+    }
+
+    /// Encode one round of packets and hand them to `sink`.
+    /// Returns `false` once the sink reports the consumer is gone.
+    pub fn poll<B, F>(&self, manager: &BrowserManager<B>, sink: &mut F) -> bool
+    where
+        B: BrowserBackend,
+        F: FnMut(Vec<u8>) -> bool,
+    {
+        self.poll_at(manager, sink, Instant::now())
+    }
+
+    fn poll_at<B, F>(&self, manager: &BrowserManager<B>, sink: &mut F, now: Instant) -> bool
+    where
+        B: BrowserBackend,
+        F: FnMut(Vec<u8>) -> bool,
+    {
+        let mut browsers = self.browsers.lock();
+        let ids = manager.browser_ids();
+
+        // Forget browsers that were closed
+        browsers.retain(|id, _| ids.contains(id));
+
+        for id in ids.iter().filter(|id| self.wants(id)) {
+            let state = browsers.entry(id.clone()).or_default();
+
+            // Lost acks would stall this browser forever - resync with a full frame
+            let timed_out = state
+                .in_flight
+                .front()
+                .is_some_and(|(_, sent_at)| now.saturating_duration_since(*sent_at) >= ACK_TIMEOUT);
+            if timed_out {
+                log::debug!("Frame acks for {} timed out, resending full frame", id);
+                state.in_flight.clear();
+                state.last_sent_seq = 0;
+            }
+
+            if state.in_flight.len() >= MAX_IN_FLIGHT {
+                self.dropped_frames.fetch_add(1, Ordering::Relaxed);
+                continue;
+            }
+
+            if let Some(mut delta) = manager.get_frame_delta(id, state.last_sent_seq) {
+                state.encode_regions(self.codec, &mut delta);
+                let packet = match encode_frame_packet(self.id, &delta) {
+                    Ok(packet) => packet,
+                    Err(e) => {
+                        log::warn!("Dropping frame {} of {}: {}", delta.seq, id, e);
+                        self.dropped_frames.fetch_add(1, Ordering::Relaxed);
+                        continue;
+                    }
+                };
+                if !sink(packet) {
+                    return false;
+                }
+                state.last_sent_seq = delta.seq;
+                state.in_flight.push_back((delta.seq, now));
+            }
+        }
+
+        true
+    }
+}
+
+/// Run a subscription on a background thread until it is stopped, the
+/// manager shuts down or the sink fails
+pub fn spawn_frame_stream<B, F>(
+    manager: Arc<RwLock<Option<BrowserManager<B>>>>,
+    subscription: Arc<FrameSubscription>,
+    mut sink: F,
+) where
+    B: BrowserBackend,
+    F: FnMut(Vec<u8>) -> bool + Send + 'static,
+{
+    std::thread::spawn(move || {
+        while subscription.is_active() {
+            {
+                let guard = manager.read();
+                let Some(manager) = guard.as_ref() else {
+                    break;
+                };
+                if !subscription.poll(manager, &mut sink) {
+                    break;
+                }
+            }
+            std::thread::sleep(FRAME_INTERVAL);
+        }
+
+        subscription.stop();
+        log::info!("Frame stream stopped");
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::TestBackend;
+    use crate::transfer::decode_frame_header;
+    use crate::CefBounds;
+
+    fn manager_with_browser() -> BrowserManager<TestBackend> {
+        let manager = BrowserManager::with_backend(TestBackend::new());
+        let bounds = CefBounds { x: 0, y: 0, width: 100, height: 100 };
+        manager.create_browser("b1", "https://example.com", bounds).unwrap();
+        manager
+    }
+
+    fn resize(manager: &BrowserManager<TestBackend>, width: i32) {
+        let bounds = CefBounds { x: 0, y: 0, width, height: 100 };
+        manager.update_bounds("b1", bounds).unwrap();
+    }
+
+    #[test]
+    fn test_poll_sends_packet_per_change() {
+        let manager = manager_with_browser();
+        let subscription = FrameSubscription::new(1, None, FrameCodec::Raw);
+        let mut packets = Vec::new();
+
+        assert!(subscription.poll(&manager, &mut |p| { packets.push(p); true }));
+        assert_eq!(packets.len(), 1);
+        let (header, _) = decode_frame_header(&packets[0]).unwrap();
+        assert_eq!(header.subscription_id, 1);
+        assert_eq!(header.browser_id, "b1");
+        assert!(header.full);
+
+        // No change - nothing sent
+        subscription.poll(&manager, &mut |p| { packets.push(p); true });
+        assert_eq!(packets.len(), 1);
+    }
+
+    #[test]
+    fn test_lagging_consumer_drops_intermediate_frames() {
+        let manager = manager_with_browser();
+        let subscription = FrameSubscription::new(1, None, FrameCodec::Raw);
+        let mut packets = Vec::new();
+
+        for width in [100, 110, 120, 130] {
+            resize(&manager, width);
+            subscription.poll(&manager, &mut |p| { packets.push(p); true });
+        }
+
+        // Only MAX_IN_FLIGHT packets went out without acks
+        assert_eq!(packets.len(), MAX_IN_FLIGHT);
+        assert_eq!(subscription.dropped_frames(), 2);
+
+        // After the ack the latest frame is sent, skipping the ones in between
+        let (last, _) = decode_frame_header(packets.last().unwrap()).unwrap();
+        subscription.ack("b1", last.seq);
+        subscription.poll(&manager, &mut |p| { packets.push(p); true });
+
+        let (latest, _) = decode_frame_header(packets.last().unwrap()).unwrap();
+        assert_eq!(latest.width, 130);
+    }
+
+    #[test]
+    fn test_lost_acks_time_out_with_full_frame() {
+        let manager = manager_with_browser();
+        let subscription = FrameSubscription::new(1, None, FrameCodec::Raw);
+        let mut packets = Vec::new();
+        let start = Instant::now();
+
+        for width in [100, 110, 120] {
+            resize(&manager, width);
+            subscription.poll_at(&manager, &mut |p| { packets.push(p); true }, start);
+        }
+        assert_eq!(packets.len(), MAX_IN_FLIGHT);
+
+        // No ack arrives - still waiting just before the timeout
+        let waiting = start + ACK_TIMEOUT - Duration::from_millis(1);
+        subscription.poll_at(&manager, &mut |p| { packets.push(p); true }, waiting);
+        assert_eq!(packets.len(), MAX_IN_FLIGHT);
+
+        subscription.poll_at(&manager, &mut |p| { packets.push(p); true }, start + ACK_TIMEOUT);
+        assert_eq!(packets.len(), MAX_IN_FLIGHT + 1);
+        let (header, _) = decode_frame_header(packets.last().unwrap()).unwrap();
+        assert!(header.full);
+        assert_eq!(header.width, 120);
+
+        // A late ack for the lost packets does not release the new one early
+        let (lost, _) = decode_frame_header(&packets[1]).unwrap();
+        subscription.ack("b1", lost.seq);
+        assert_eq!(subscription.browsers.lock()["b1"].in_flight.len(), 1);
+    }
+
+    #[test]
+    fn test_filtered_subscription_ignores_other_browsers() {
+        let manager = manager_with_browser();
+        let subscription = FrameSubscription::new(1, Some(vec!["other".to_string()]), FrameCodec::Raw);
+        let mut packets: Vec<Vec<u8>> = Vec::new();
+
+        subscription.poll(&manager, &mut |p| { packets.push(p); true });
+        assert!(packets.is_empty());
+    }
+
+    #[test]
+    fn test_xor_rle_packets_rebuild_frames() {
+        let manager = manager_with_browser();
+        let subscription = FrameSubscription::new(1, None, FrameCodec::XorRle);
+        let mut client = vec![0u8; 100 * 100 * 4];
+
+        // Every navigation makes the TestBackend paint the next frame number
+        for frame_number in 1..=3 {
+            let mut packets = Vec::new();
+            subscription.poll(&manager, &mut |p| { packets.push(p); true });
+            let packet = packets.pop().unwrap();
+            let (header, mut offset) = decode_frame_header(&packet).unwrap();
+            assert_eq!(header.format, FrameCodec::XorRle.code());
+            if header.full {
+                client.fill(0);
+            }
+
+            for (rect, len) in header.rects.iter().zip(&header.data_lengths) {
+                let data = &packet[offset..offset + *len as usize];
+                offset += *len as usize;
+                let previous = crop_rect(&client, header.width, rect);
+                let pixels = FrameCodec::XorRle.decode(data, Some(&previous)).unwrap();
+                paste_rect(&mut client, header.width, rect, &pixels);
+            }
+
+            assert_eq!(client, TestBackend::expected_frame(frame_number, 100, 100));
+            subscription.ack("b1", header.seq);
+            manager.navigate("b1", "https://example.com/next").unwrap();
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Thumbnails - Downscaled PNG snapshots of a browser's current frame
+//!
+//! Used for zoomed-out canvases, the minimap and board previews. The frame is
+//! taken from the `FrameBuffer`, so capturing never triggers a repaint and
+//! also works for throttled browsers (suspended ones return their last frame).
+
+use crate::transfer::{encode_image, resize_bgra, ImageFormat, ResizeFilter};
+use serde::{Deserialize, Serialize};
+
+/// Shared with the host so native and OSR thumbnails get the same size
+pub use image_scale::fit_size;
+
+/// Default bounding box when the caller doesn't pass one
+pub const DEFAULT_THUMBNAIL_WIDTH: u32 = 480;
+pub const DEFAULT_THUMBNAIL_HEIGHT: u32 = 360;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Thumbnail {
+    pub browser_id: String,
+    /// Page the frame belongs to (cache key for the host)
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+    pub png: Vec<u8>,
+}
+
+/// Downscale a BGRA frame into the bounding box and encode it
+pub fn create_thumbnail(
+    browser_id: &str,
+    url: &str,
+    bgra: &[u8],
+    width: u32,
+    height: u32,
+    max_width: u32,
+    max_height: u32,
+) -> Result<Thumbnail, String> {
+    let (thumb_width, thumb_height) = fit_size(width, height, max_width, max_height);
+    // Box keeps small text legible at thumbnail sizes and is the cheapest filter
+    let pixels = resize_bgra(bgra, width, height, thumb_width, thumb_height, ResizeFilter::Box);
+
+    Ok(Thumbnail {
+        browser_id: browser_id.to_string(),
+        url: url.to_string(),
+        width: thumb_width,
+        height: thumb_height,
+        png: encode_image(&pixels, thumb_width, thumb_height, ImageFormat::Png)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thumbnail_is_valid_png() {
+        let frame = vec![200u8; 640 * 480 * 4];
+        let thumb = create_thumbnail("b1", "https://example.com", &frame, 640, 480, 160, 160).unwrap();
+
+        assert_eq!((thumb.width, thumb.height), (160, 120));
+        assert_eq!(&thumb.png[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
@@ -3,13 +3,30 @@
 //! This module handles the transfer of rendered frames from CEF to the frontend.
 //! We use raw byte arrays that can be efficiently uploaded to WebGL textures.
 
-use crate::FrameData;
+use crate::codec::FrameCodec;
+use crate::{FrameData, FrameDelta};
+use serde::{Deserialize, Serialize};
 
-/// Compress frame data for transfer (optional, can be disabled for speed)
-pub fn compress_frame(_frame: &FrameData) -> Vec<u8> {
-    // For now, we just return raw BGRA data
-    // In the future, we could use LZ4 or similar for compression
-    _frame.data.clone()
+/// Resampling lives in `image-scale` so the host scales native thumbnails the same way
+pub use image_scale::{resize_bgra, ResizeFilter};
+
+/// Encode frame data for transfer; the codec is reported in `format`.
+/// `previous` is the frame the client already has (used by `XorRle`).
+pub fn compress_frame(frame: &FrameData, codec: FrameCodec, previous: Option<&[u8]>) -> FrameData {
+    FrameData {
+        browser_id: frame.browser_id.clone(),
+        width: frame.width,
+        height: frame.height,
+        format: codec.format().to_string(),
+        data: codec.encode(&frame.data, previous),
+    }
+}
+
+/// Recover raw BGRA pixels from a frame produced by `compress_frame`
+pub fn decompress_frame(frame: &FrameData, previous: Option<&[u8]>) -> Result<Vec<u8>, String> {
+    let codec = FrameCodec::from_format(&frame.format)
+        .ok_or_else(|| format!("Unknown frame format {}", frame.format))?;
+    codec.decode(&frame.data, previous)
 }
 
 /// Convert BGRA to RGBA for WebGL compatibility
@@ -43,8 +60,151 @@ impl FrameBatch {
     }
 }
 
+// ============================================================================
+// BINARY FRAME PACKETS
+// ============================================================================
+
+/// Version byte at the start of every frame packet
+pub const PACKET_VERSION: u8 = 3;
+
+/// Header flag: the packet contains one region covering the whole frame
+pub const FLAG_FULL_FRAME: u8 = 0b0000_0001;
+
+/// Fixed header size in bytes (without browser id and rects)
+pub const PACKET_HEADER_LEN: usize = 28;
+
+/// Decoded packet header (used by tests and native consumers)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FramePacketHeader {
+    /// Subscription the packet was sent on - acks go back to it
+    pub subscription_id: u32,
+    pub browser_id: String,
+    pub seq: u64,
+    pub width: u32,
+    pub height: u32,
+    pub format: u8,
+    pub full: bool,
+    pub rects: Vec<DirtyRect>,
+    /// Encoded byte length of each region's data, in rect order
+    pub data_lengths: Vec<u32>,
+}
+
+/// Bytes per rect entry: x, y, width, height, data length
+const RECT_ENTRY_LEN: usize = 20;
+
+fn format_code(format: &str) -> u8 {
+    FrameCodec::from_format(format).map_or(u8::MAX, |codec| codec.code())
+}
+
+/// Encode a frame delta as a compact binary packet.
+///
+/// Layout (little endian):
+/// `version u8 | format u8 | flags u8 | id_len u8 | subscription u32 |
+/// seq u64 | width u32 | height u32 | rect_count u16 | reserved u16 |
+/// id bytes | rect_count * (x u32, y u32, width u32, height u32, data_len u32) |
+/// encoded data of all regions, in rect order`
+///
+/// The subscription id lets the client ack packets that arrive before
+/// `cef_subscribe_frames` has returned. Fails if the browser id is longer than
+/// 255 bytes or the delta has more than 65535 regions.
+pub fn encode_frame_packet(subscription_id: u32, delta: &FrameDelta) -> Result<Vec<u8>, String> {
+    let id = delta.browser_id.as_bytes();
+    let id_len = u8::try_from(id.len())
+        .map_err(|_| format!("Browser id is {} bytes, packets allow at most {}", id.len(), u8::MAX))?;
+    let rect_count = u16::try_from(delta.regions.len())
+        .map_err(|_| format!("Frame has {} regions, packets allow at most {}", delta.regions.len(), u16::MAX))?;
+    let payload_len: usize = delta.regions.iter().map(|r| r.data.len()).sum();
+
+    let mut packet =
+        Vec::with_capacity(PACKET_HEADER_LEN + id.len() + delta.regions.len() * RECT_ENTRY_LEN + payload_len);
+
+    packet.push(PACKET_VERSION);
+    packet.push(format_code(&delta.format));
+    packet.push(if delta.full { FLAG_FULL_FRAME } else { 0 });
+    packet.push(id_len);
+    packet.extend_from_slice(&subscription_id.to_le_bytes());
+    packet.extend_from_slice(&delta.seq.to_le_bytes());
+    packet.extend_from_slice(&delta.width.to_le_bytes());
+    packet.extend_from_slice(&delta.height.to_le_bytes());
+    packet.extend_from_slice(&rect_count.to_le_bytes());
+    packet.extend_from_slice(&0u16.to_le_bytes());
+    packet.extend_from_slice(id);
+
+    for region in &delta.regions {
+        packet.extend_from_slice(&region.x.to_le_bytes());
+        packet.extend_from_slice(&region.y.to_le_bytes());
+        packet.extend_from_slice(&region.width.to_le_bytes());
+        packet.extend_from_slice(&region.height.to_le_bytes());
+        packet.extend_from_slice(&(region.data.len() as u32).to_le_bytes());
+    }
+
+    for region in &delta.regions {
+        packet.extend_from_slice(&region.data);
+    }
+
+    Ok(packet)
+}
+
+/// Decode the header of a frame packet, returning it with the payload offset
+pub fn decode_frame_header(packet: &[u8]) -> Result<(FramePacketHeader, usize), String> {
+    if packet.len() < PACKET_HEADER_LEN {
+        return Err("Frame packet too short".to_string());
+    }
+    if packet[0] != PACKET_VERSION {
+        return Err(format!("Unsupported frame packet version {}", packet[0]));
+    }
+
+    let u32_at = |offset: usize| u32::from_le_bytes(packet[offset..offset + 4].try_into().unwrap());
+
+    let format = packet[1];
+    let full = packet[2] & FLAG_FULL_FRAME != 0;
+    let id_len = packet[3] as usize;
+    let subscription_id = u32_at(4);
+    let seq = u64::from_le_bytes(packet[8..16].try_into().unwrap());
+    let width = u32_at(16);
+    let height = u32_at(20);
+    let rect_count = u16::from_le_bytes([packet[24], packet[25]]) as usize;
+
+    let rects_start = PACKET_HEADER_LEN + id_len;
+    let payload_start = rects_start + rect_count * RECT_ENTRY_LEN;
+    if packet.len() < payload_start {
+        return Err("Frame packet header truncated".to_string());
+    }
+
+    let browser_id = String::from_utf8_lossy(&packet[PACKET_HEADER_LEN..rects_start]).into_owned();
+    let rects = (0..rect_count)
+        .map(|i| {
+            let offset = rects_start + i * RECT_ENTRY_LEN;
+            DirtyRect {
+                x: u32_at(offset),
+                y: u32_at(offset + 4),
+                width: u32_at(offset + 8),
+                height: u32_at(offset + 12),
+            }
+        })
+        .collect();
+    let data_lengths = (0..rect_count)
+        .map(|i| u32_at(rects_start + i * RECT_ENTRY_LEN + 16))
+        .collect();
+
+    Ok((
+        FramePacketHeader {
+            subscription_id,
+            browser_id,
+            seq,
+            width,
+            height,
+            format,
+            full,
+            rects,
+            data_lengths,
+        },
+        payload_start,
+    ))
+}
+
 /// Dirty rect tracking for partial updates
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DirtyRect {
     pub x: u32,
     pub y: u32,
@@ -76,6 +236,184 @@ impl DirtyRect {
             height: bottom - y,
         }
     }
+
+    pub fn area(&self) -> u64 {
+        self.width as u64 * self.height as u64
+    }
+
+    /// True if the two rects share at least one pixel
+    pub fn intersects(&self, other: &DirtyRect) -> bool {
+        self.x < other.x + other.width
+            && other.x < self.x + self.width
+            && self.y < other.y + other.height
+            && other.y < self.y + self.height
+    }
+
+    /// Clip the rect to a `width` x `height` frame, `None` if nothing is left
+    pub fn clamp_to(&self, width: u32, height: u32) -> Option<DirtyRect> {
+        let right = (self.x.saturating_add(self.width)).min(width);
+        let bottom = (self.y.saturating_add(self.height)).min(height);
+        if self.x >= right || self.y >= bottom {
+            return None;
+        }
+
+        Some(DirtyRect {
+            x: self.x,
+            y: self.y,
+            width: right - self.x,
+            height: bottom - self.y,
+        })
+    }
+}
+
+/// Merge overlapping rects so every pixel is sent at most once
+pub fn coalesce_rects(rects: &[DirtyRect]) -> Vec<DirtyRect> {
+    let mut result: Vec<DirtyRect> = Vec::new();
+
+    for rect in rects {
+        let mut current = rect.clone();
+        // A merged rect can grow into rects it didn't touch before
+        while let Some(i) = result.iter().position(|r| r.intersects(&current)) {
+            let other = result.swap_remove(i);
+            current = current.merge(&other);
+        }
+        result.push(current);
+    }
+
+    result
+}
+
+/// Copy the pixels of `rect` out of a BGRA frame that is `frame_width` wide
+pub fn crop_rect(data: &[u8], frame_width: u32, rect: &DirtyRect) -> Vec<u8> {
+    let stride = frame_width as usize * 4;
+    let row_bytes = rect.width as usize * 4;
+    let mut out = Vec::with_capacity(row_bytes * rect.height as usize);
+
+    for row in rect.y..rect.y + rect.height {
+        let start = row as usize * stride + rect.x as usize * 4;
+        out.extend_from_slice(&data[start..start + row_bytes]);
+    }
+
+    out
+}
+
+/// Copy the pixels of `rect` from `src` into `dst` (both BGRA, same size)
+pub fn copy_rect(dst: &mut [u8], src: &[u8], frame_width: u32, rect: &DirtyRect) {
+    let stride = frame_width as usize * 4;
+    let row_bytes = rect.width as usize * 4;
+
+    for row in rect.y..rect.y + rect.height {
+        let start = row as usize * stride + rect.x as usize * 4;
+        dst[start..start + row_bytes].copy_from_slice(&src[start..start + row_bytes]);
+    }
+}
+
+/// Write the pixels of a cropped region back into a BGRA frame
+pub fn paste_rect(dst: &mut [u8], frame_width: u32, rect: &DirtyRect, region: &[u8]) {
+    let stride = frame_width as usize * 4;
+    let row_bytes = rect.width as usize * 4;
+
+    for (i, row) in (rect.y..rect.y + rect.height).enumerate() {
+        let start = row as usize * stride + rect.x as usize * 4;
+        dst[start..start + row_bytes].copy_from_slice(&region[i * row_bytes..(i + 1) * row_bytes]);
+    }
+}
+
+// ============================================================================
+// IMAGE TOOLING (thumbnails, screenshots, exports)
+// ============================================================================
+
+/// Still-image encoding of BGRA pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ImageFormat {
+    Png,
+    /// `quality` is 1-100
+    Jpeg { quality: u8 },
+}
+
+impl ImageFormat {
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            ImageFormat::Png => "image/png",
+            ImageFormat::Jpeg { .. } => "image/jpeg",
+        }
+    }
+}
+
+/// Encode BGRA pixels as PNG (RGBA, alpha kept) or JPEG (alpha dropped)
+pub fn encode_image(bgra: &[u8], width: u32, height: u32, format: ImageFormat) -> Result<Vec<u8>, String> {
+    if bgra.len() != width as usize * height as usize * 4 {
+        return Err(format!("Pixel buffer does not match {}x{}", width, height));
+    }
+
+    match format {
+        ImageFormat::Png => encode_png(bgra, width, height),
+        ImageFormat::Jpeg { quality } => encode_jpeg(bgra, width, height, quality),
+    }
+}
+
+fn encode_png(bgra: &[u8], width: u32, height: u32) -> Result<Vec<u8>, String> {
+    let mut rgba = bgra.to_vec();
+    bgra_to_rgba(&mut rgba);
+
+    let mut png_data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_data, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder
+        .write_header()
+        .map_err(|e| format!("PNG header failed: {}", e))?;
+    writer
+        .write_image_data(&rgba)
+        .map_err(|e| format!("PNG encoding failed: {}", e))?;
+    writer.finish().map_err(|e| format!("PNG encoding failed: {}", e))?;
+
+    Ok(png_data)
+}
+
+fn encode_jpeg(bgra: &[u8], width: u32, height: u32, quality: u8) -> Result<Vec<u8>, String> {
+    let (Ok(w), Ok(h)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(format!("{}x{} is too large for JPEG", width, height));
+    };
+
+    // The encoder reads BGRA directly (AVX2 path with the `simd` feature)
+    let mut jpeg_data = Vec::new();
+    jpeg_encoder::Encoder::new(&mut jpeg_data, quality.clamp(1, 100))
+        .encode(bgra, w, h, jpeg_encoder::ColorType::Bgra)
+        .map_err(|e| format!("JPEG encoding failed: {}", e))?;
+
+    Ok(jpeg_data)
+}
+
+/// Encode a frame as a still image. Compressed frames are decoded first;
+/// `XorRle` frames need the previous frame and are rejected.
+pub fn encode_frame(frame: &FrameData, format: ImageFormat) -> Result<Vec<u8>, String> {
+    if frame.format == FrameCodec::Raw.format() {
+        encode_image(&frame.data, frame.width, frame.height, format)
+    } else {
+        let pixels = decompress_frame(frame, None)?;
+        encode_image(&pixels, frame.width, frame.height, format)
+    }
+}
+
+/// Crop a raw frame to `rect` (clipped to the frame)
+pub fn crop_frame(frame: &FrameData, rect: &DirtyRect) -> Result<FrameData, String> {
+    if frame.format != FrameCodec::Raw.format() {
+        return Err(format!("Cannot crop {} frames", frame.format));
+    }
+    let rect = rect
+        .clamp_to(frame.width, frame.height)
+        .ok_or("Crop rect is outside the frame")?;
+
+    Ok(FrameData {
+        browser_id: frame.browser_id.clone(),
+        width: rect.width,
+        height: rect.height,
+        format: frame.format.clone(),
+        data: crop_rect(&frame.data, frame.width, &rect),
+    })
 }
 
 #[cfg(test)]
@@ -102,4 +440,192 @@ mod tests {
         assert_eq!(merged.width, 150);
         assert_eq!(merged.height, 150);
     }
+
+    #[test]
+    fn test_frame_packet_roundtrip() {
+        use crate::FrameRegion;
+
+        let delta = FrameDelta {
+            browser_id: "webtab-1".to_string(),
+            seq: 42,
+            width: 800,
+            height: 600,
+            format: "BGRA8".to_string(),
+            full: false,
+            regions: vec![
+                FrameRegion { x: 1, y: 2, width: 1, height: 1, data: vec![1, 2, 3, 4] },
+                FrameRegion { x: 5, y: 6, width: 2, height: 1, data: vec![5; 8] },
+            ],
+        };
+
+        let packet = encode_frame_packet(7, &delta).unwrap();
+        let (header, payload_start) = decode_frame_header(&packet).unwrap();
+
+        assert_eq!(header.subscription_id, 7);
+        assert_eq!(header.browser_id, "webtab-1");
+        assert_eq!(header.seq, 42);
+        assert_eq!((header.width, header.height), (800, 600));
+        assert_eq!(header.format, FrameCodec::Raw.code());
+        assert!(!header.full);
+        assert_eq!(
+            header.rects,
+            vec![
+                DirtyRect { x: 1, y: 2, width: 1, height: 1 },
+                DirtyRect { x: 5, y: 6, width: 2, height: 1 },
+            ]
+        );
+        assert_eq!(header.data_lengths, vec![4, 8]);
+        assert_eq!(&packet[payload_start..], &[1, 2, 3, 4, 5, 5, 5, 5, 5, 5, 5, 5]);
+    }
+
+    #[test]
+    fn test_frame_packet_rejects_oversized_fields() {
+        use crate::FrameRegion;
+
+        let mut delta = FrameDelta {
+            browser_id: "b".repeat(256),
+            seq: 1,
+            width: 1,
+            height: 1,
+            format: "BGRA8".to_string(),
+            full: false,
+            regions: Vec::new(),
+        };
+        assert!(encode_frame_packet(1, &delta).is_err());
+
+        delta.browser_id = "b".repeat(255);
+        assert!(encode_frame_packet(1, &delta).is_ok());
+
+        let region = FrameRegion { x: 0, y: 0, width: 1, height: 1, data: Vec::new() };
+        delta.regions = vec![region; u16::MAX as usize + 1];
+        assert!(encode_frame_packet(1, &delta).is_err());
+    }
+
+    #[test]
+    fn test_dirty_rect_clamp() {
+        let rect = DirtyRect { x: 90, y: 10, width: 50, height: 20 };
+        assert_eq!(
+            rect.clamp_to(100, 100),
+            Some(DirtyRect { x: 90, y: 10, width: 10, height: 20 })
+        );
+        assert_eq!(rect.clamp_to(50, 100), None);
+    }
+
+    #[test]
+    fn test_coalesce_rects() {
+        let rects = vec![
+            DirtyRect { x: 0, y: 0, width: 10, height: 10 },
+            DirtyRect { x: 50, y: 50, width: 10, height: 10 },
+            DirtyRect { x: 5, y: 5, width: 10, height: 10 },
+            // Touching but not overlapping stays separate
+            DirtyRect { x: 60, y: 50, width: 10, height: 10 },
+        ];
+        let mut coalesced = coalesce_rects(&rects);
+        coalesced.sort_by_key(|r| (r.x, r.y));
+
+        assert_eq!(
+            coalesced,
+            vec![
+                DirtyRect { x: 0, y: 0, width: 15, height: 15 },
+                DirtyRect { x: 50, y: 50, width: 10, height: 10 },
+                DirtyRect { x: 60, y: 50, width: 10, height: 10 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crop_and_copy_rect() {
+        // 4x2 frame where every pixel's B channel is its index
+        let frame: Vec<u8> = (0..8u8).flat_map(|i| [i, 0, 0, 255]).collect();
+        let rect = DirtyRect { x: 1, y: 0, width: 2, height: 2 };
+
+        let cropped = crop_rect(&frame, 4, &rect);
+        let blues: Vec<u8> = cropped.chunks_exact(4).map(|p| p[0]).collect();
+        assert_eq!(blues, vec![1, 2, 5, 6]);
+
+        let mut target = vec![0u8; frame.len()];
+        copy_rect(&mut target, &frame, 4, &rect);
+        let blues: Vec<u8> = target.chunks_exact(4).map(|p| p[0]).collect();
+        assert_eq!(blues, vec![0, 1, 2, 0, 0, 5, 6, 0]);
+
+        let mut pasted = vec![0u8; frame.len()];
+        paste_rect(&mut pasted, 4, &rect, &cropped);
+        assert_eq!(pasted, target);
+    }
+
+    #[test]
+    fn test_compress_frame_reports_format() {
+        let frame = FrameData {
+            browser_id: "b1".to_string(),
+            width: 2,
+            height: 1,
+            format: "BGRA8".to_string(),
+            data: vec![1, 2, 3, 255, 1, 2, 3, 255],
+        };
+
+        for codec in FrameCodec::ALL {
+            let compressed = compress_frame(&frame, codec, None);
+            assert_eq!(compressed.format, codec.format());
+            assert_eq!(decompress_frame(&compressed, None).unwrap(), frame.data);
+        }
+    }
+
+    fn raw_frame(width: u32, height: u32, data: Vec<u8>) -> FrameData {
+        FrameData {
+            browser_id: "b1".to_string(),
+            width,
+            height,
+            format: FrameCodec::Raw.format().to_string(),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_encode_png_roundtrip() {
+        let data: Vec<u8> = (0..6u8).flat_map(|i| [i, i * 10, 200, 255 - i]).collect();
+        let png_data = encode_frame(&raw_frame(3, 2, data.clone()), ImageFormat::Png).unwrap();
+
+        let decoder = png::Decoder::new(std::io::Cursor::new(png_data));
+        let mut reader = decoder.read_info().unwrap();
+        let mut decoded = vec![0; reader.output_buffer_size().unwrap()];
+        let info = reader.next_frame(&mut decoded).unwrap();
+
+        assert_eq!((info.width, info.height), (3, 2));
+        let mut expected = data;
+        bgra_to_rgba(&mut expected);
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn test_encode_jpeg() {
+        let frame = raw_frame(64, 32, [0u8, 0, 255, 255].repeat(64 * 32));
+        let jpeg = encode_frame(&frame, ImageFormat::Jpeg { quality: 80 }).unwrap();
+
+        assert_eq!(&jpeg[..2], &[0xFF, 0xD8]);
+        assert_eq!(&jpeg[jpeg.len() - 2..], &[0xFF, 0xD9]);
+        assert!(encode_image(&[0; 4], 70000, 1, ImageFormat::Jpeg { quality: 80 }).is_err());
+        assert!(encode_image(&[0; 3], 1, 1, ImageFormat::Png).is_err());
+    }
+
+    #[test]
+    fn test_encode_compressed_frame() {
+        let frame = raw_frame(2, 1, vec![1, 2, 3, 255, 1, 2, 3, 255]);
+        let lz4 = compress_frame(&frame, FrameCodec::Lz4, None);
+        assert_eq!(
+            encode_frame(&lz4, ImageFormat::Png).unwrap(),
+            encode_frame(&frame, ImageFormat::Png).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_crop_frame() {
+        let frame = raw_frame(4, 2, (0..8u8).flat_map(|i| [i, 0, 0, 255]).collect());
+
+        let cropped = crop_frame(&frame, &DirtyRect { x: 2, y: 1, width: 10, height: 10 }).unwrap();
+        assert_eq!((cropped.width, cropped.height), (2, 1));
+        assert_eq!(cropped.data, vec![6, 0, 0, 255, 7, 0, 0, 255]);
+
+        assert!(crop_frame(&frame, &DirtyRect { x: 4, y: 0, width: 1, height: 1 }).is_err());
+        assert!(crop_frame(&compress_frame(&frame, FrameCodec::Lz4, None), &DirtyRect::full(1, 1)).is_err());
+    }
 }
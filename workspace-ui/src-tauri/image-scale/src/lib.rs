@@ -0,0 +1,234 @@
+//! Image Scale - Thumbnail sizing and resampling shared by the host and the CEF plugin
+//!
+//! Native tabs (host) and OSR browsers (cef-plugin) produce thumbnails for the
+//! same canvas, so both must agree on the size they fit a frame into and
+//! scale it the same way.
+
+use serde::{Deserialize, Serialize};
+
+/// Largest size that fits into `max_width` x `max_height` with the same
+/// aspect ratio. Never upscales, never returns a zero dimension.
+pub fn fit_size(width: u32, height: u32, max_width: u32, max_height: u32) -> (u32, u32) {
+    if width == 0 || height == 0 {
+        return (1, 1);
+    }
+
+    let scale = (max_width as f64 / width as f64)
+        .min(max_height as f64 / height as f64)
+        .min(1.0);
+    (
+        ((width as f64 * scale).round() as u32).max(1),
+        ((height as f64 * scale).round() as u32).max(1),
+    )
+}
+
+/// Resampling filter for `resize_bgra`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResizeFilter {
+    /// Area average - fast, good for large downscales of text
+    #[default]
+    Box,
+    /// Windowed sinc (a = 3) - sharper, slightly slower
+    Lanczos3,
+}
+
+impl ResizeFilter {
+    /// Filter radius in source pixels at scale 1
+    fn support(&self) -> f64 {
+        match self {
+            ResizeFilter::Box => 0.5,
+            ResizeFilter::Lanczos3 => 3.0,
+        }
+    }
+
+    fn weight(&self, x: f64) -> f64 {
+        match self {
+            ResizeFilter::Box => {
+                if x > -0.5 && x <= 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            ResizeFilter::Lanczos3 => {
+                if x.abs() < 3.0 {
+                    sinc(x) * sinc(x / 3.0)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let x = x * std::f64::consts::PI;
+        x.sin() / x
+    }
+}
+
+/// Fixed-point precision of the resize weights. 255 * 1.3 (largest Lanczos
+/// weight sum) * 2^22 still fits into an i32 accumulator.
+const WEIGHT_BITS: u32 = 22;
+
+/// Source window and fixed-point weights for every target coordinate
+struct ResampleWeights {
+    /// (first source index, offset into `weights`, tap count)
+    windows: Vec<(usize, usize, usize)>,
+    weights: Vec<i32>,
+}
+
+impl ResampleWeights {
+    fn new(src: u32, dst: u32, filter: ResizeFilter) -> Self {
+        let scale = src as f64 / dst as f64;
+        // Downscaling widens the kernel so every source pixel contributes
+        let filter_scale = scale.max(1.0);
+        let support = filter.support() * filter_scale;
+
+        let mut windows = Vec::with_capacity(dst as usize);
+        let mut weights = Vec::new();
+        let mut taps = Vec::new();
+
+        for i in 0..dst {
+            let center = (i as f64 + 0.5) * scale;
+            let left = ((center - support).floor().max(0.0)) as usize;
+            let right = ((center + support).ceil() as usize).min(src as usize);
+
+            taps.clear();
+            taps.extend((left..right).map(|j| filter.weight((j as f64 + 0.5 - center) / filter_scale)));
+            let total: f64 = taps.iter().sum();
+
+            let offset = weights.len();
+            if total == 0.0 {
+                // Degenerate window (upscaling with Box): nearest neighbour
+                let nearest = (center as usize).min(src as usize - 1);
+                windows.push((nearest, offset, 1));
+                weights.push(1 << WEIGHT_BITS);
+            } else {
+                windows.push((left, offset, taps.len()));
+                weights.extend(taps.iter().map(|w| (w / total * (1 << WEIGHT_BITS) as f64).round() as i32));
+            }
+        }
+
+        Self { windows, weights }
+    }
+}
+
+#[inline]
+fn fixed_to_u8(acc: i32) -> u8 {
+    ((acc + (1 << (WEIGHT_BITS - 1))) >> WEIGHT_BITS).clamp(0, 255) as u8
+}
+
+/// Resize BGRA pixels with a separable filter (horizontal, then vertical).
+/// Integer math over whole rows, so the inner loops auto-vectorize. Channels
+/// are filtered independently, so RGBA input works just as well.
+pub fn resize_bgra(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    target_width: u32,
+    target_height: u32,
+    filter: ResizeFilter,
+) -> Vec<u8> {
+    if (width, height) == (target_width, target_height) {
+        return data.to_vec();
+    }
+    if width == 0 || height == 0 || target_width == 0 || target_height == 0 {
+        return Vec::new();
+    }
+
+    let (w, tw) = (width as usize, target_width as usize);
+
+    // Horizontal pass: width x height -> target_width x height
+    let horizontal = ResampleWeights::new(width, target_width, filter);
+    let mut tmp = vec![0u8; tw * height as usize * 4];
+    for (src_row, dst_row) in data.chunks_exact(w * 4).zip(tmp.chunks_exact_mut(tw * 4)) {
+        for (&(left, offset, count), out) in horizontal.windows.iter().zip(dst_row.chunks_exact_mut(4)) {
+            let mut acc = [0i32; 4];
+            let pixels = src_row[left * 4..(left + count) * 4].chunks_exact(4);
+            for (px, &weight) in pixels.zip(&horizontal.weights[offset..offset + count]) {
+                for c in 0..4 {
+                    acc[c] += px[c] as i32 * weight;
+                }
+            }
+            for c in 0..4 {
+                out[c] = fixed_to_u8(acc[c]);
+            }
+        }
+    }
+
+    // Vertical pass: accumulate whole source rows into one target row
+    let vertical = ResampleWeights::new(height, target_height, filter);
+    let row_bytes = tw * 4;
+    let mut out = vec![0u8; row_bytes * target_height as usize];
+    let mut acc = vec![0i32; row_bytes];
+    for (&(top, offset, count), dst_row) in vertical.windows.iter().zip(out.chunks_exact_mut(row_bytes)) {
+        acc.fill(0);
+        for (y, &weight) in (top..top + count).zip(&vertical.weights[offset..offset + count]) {
+            let src_row = &tmp[y * row_bytes..(y + 1) * row_bytes];
+            for (a, &p) in acc.iter_mut().zip(src_row) {
+                *a += p as i32 * weight;
+            }
+        }
+        for (o, &a) in dst_row.iter_mut().zip(&acc) {
+            *o = fixed_to_u8(a);
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_size_keeps_aspect_and_never_upscales() {
+        assert_eq!(fit_size(1920, 1080, 480, 360), (480, 270));
+        assert_eq!(fit_size(600, 1200, 480, 360), (180, 360));
+        assert_eq!(fit_size(200, 100, 480, 360), (200, 100));
+        assert_eq!(fit_size(10000, 1, 100, 100), (100, 1));
+    }
+
+    #[test]
+    fn test_box_resize_averages_blocks() {
+        // 4x2 -> 2x1: left half black, right half white
+        let mut data = Vec::new();
+        for _ in 0..2 {
+            data.extend_from_slice(&[0, 0, 0, 255, 0, 0, 0, 255]);
+            data.extend_from_slice(&[255, 255, 255, 255, 255, 255, 255, 255]);
+        }
+        let out = resize_bgra(&data, 4, 2, 2, 1, ResizeFilter::Box);
+        assert_eq!(out, vec![0, 0, 0, 255, 255, 255, 255, 255]);
+
+        // Checkerboard averages to grey
+        let checker = [0u8, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255, 0, 0, 0, 255];
+        assert_eq!(resize_bgra(&checker, 2, 2, 1, 1, ResizeFilter::Box), vec![128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn test_resize_keeps_flat_colors_and_sizes() {
+        let flat: Vec<u8> = [10u8, 20, 30, 255].repeat(97 * 61);
+        for filter in [ResizeFilter::Box, ResizeFilter::Lanczos3] {
+            // Odd ratios, downscale and upscale
+            for (tw, th) in [(40, 25), (13, 7), (150, 90)] {
+                let out = resize_bgra(&flat, 97, 61, tw, th, filter);
+                assert_eq!(out.len(), (tw * th * 4) as usize);
+                assert!(out.chunks_exact(4).all(|px| px == [10, 20, 30, 255]), "{:?} {}x{}", filter, tw, th);
+            }
+        }
+    }
+
+    #[test]
+    fn test_lanczos_keeps_flat_sides_of_an_edge() {
+        // Ringing near a hard black/white edge is clamped, flat areas stay exact
+        let row: Vec<u8> = (0..16u32).flat_map(|x| if x < 8 { [0u8; 4] } else { [255u8; 4] }).collect();
+        let out = resize_bgra(&row, 16, 1, 40, 1, ResizeFilter::Lanczos3);
+        assert_eq!(&out[..4], &[0, 0, 0, 0]);
+        assert_eq!(&out[out.len() - 4..], &[255, 255, 255, 255]);
+    }
+}
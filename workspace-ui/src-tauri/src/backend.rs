@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+use crate::error::{LockExt, WorkspaceError};
+
+// ============================================================================
+// WEB TAB BACKENDS - Native Fenster oder CEF Off-Screen-Rendering
+// ============================================================================
+
+/// Wie ein Web-Tab dargestellt wird
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WebTabBackend {
+    /// Eigenes frameless `webtab-*` Fenster über dem Canvas
+    Native,
+    /// OSR-Browser aus dem cef-plugin, gerendert in den Canvas
+    Osr,
+}
+
+impl WebTabBackend {
+    /// OSR gibt es nur mit aktiviertem `cef` Feature
+    pub fn is_available(self) -> bool {
+        match self {
+            WebTabBackend::Native => true,
+            WebTabBackend::Osr => cfg!(feature = "cef"),
+        }
+    }
+}
+
+/// Standard-Backend für neue Tabs (pro Tab im Frontend überschreibbar)
+pub struct WebTabBackendState {
+    default: Mutex<WebTabBackend>,
+}
+
+impl Default for WebTabBackendState {
+    fn default() -> Self {
+        Self {
+            default: Mutex::new(WebTabBackend::Native),
+        }
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Listet die in diesem Build verfügbaren Backends
+#[tauri::command]
+pub fn list_web_tab_backends() -> Vec<WebTabBackend> {
+    [WebTabBackend::Native, WebTabBackend::Osr]
+        .into_iter()
+        .filter(|backend| backend.is_available())
+        .collect()
+}
+
+/// Liefert das Standard-Backend für neue Tabs
+#[tauri::command]
+pub fn get_web_tab_backend(state: tauri::State<'_, WebTabBackendState>) -> WebTabBackend {
+    *state.default.lock_or_recover()
+}
+
+/// Setzt das Standard-Backend für neue Tabs
+#[tauri::command]
+pub fn set_web_tab_backend(
+    state: tauri::State<'_, WebTabBackendState>,
+    backend: WebTabBackend,
+) -> Result<(), WorkspaceError> {
+    if !backend.is_available() {
+        return Err(WorkspaceError::Unsupported(format!(
+            "Web tab backend {:?} is not available in this build",
+            backend
+        )));
+    }
+
+    *state.default.lock_or_recover() = backend;
+    Ok(())
+}
@@ -0,0 +1,661 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, WindowEvent};
+
+use crate::error::{LockExt, WorkspaceError};
+use crate::fullscreen::{self, FullscreenMode};
+use crate::{TabBounds, WebTabState};
+
+// ============================================================================
+// CANVAS LAYOUT - Web-Tabs aus Canvas-Koordinaten positionieren
+// ============================================================================
+
+/// Kleinere Tabs (logische Pixel) werden versteckt - native Fenster lassen
+/// sich nicht sinnvoll so klein darstellen
+const MIN_VISIBLE_SIZE: f64 = 48.0;
+
+/// Inhaltsbereich einer Tab-Karte in Canvas-Koordinaten (ohne Header)
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct CanvasRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Canvas-Transformation: screen = canvas * scale + (x, y) + origin
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct CanvasViewport {
+    pub x: f64,
+    pub y: f64,
+    pub scale: f64,
+    /// Position der Canvas-Fläche im Hauptfenster (CSS-Pixel, z.B. unter der Toolbar)
+    #[serde(default)]
+    pub origin_x: f64,
+    #[serde(default)]
+    pub origin_y: f64,
+    /// Sichtbare Canvas-Größe (CSS-Pixel) - 0 = unbekannt, dann wird nichts ausgeblendet
+    #[serde(default)]
+    pub width: f64,
+    #[serde(default)]
+    pub height: f64,
+}
+
+/// Rechteck im Inhaltsbereich des Hauptfensters (CSS-Pixel)
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+pub struct ScreenRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl ScreenRect {
+    fn right(&self) -> f64 {
+        self.x + self.width
+    }
+
+    fn bottom(&self) -> f64 {
+        self.y + self.height
+    }
+
+    fn area(&self) -> f64 {
+        self.width.max(0.0) * self.height.max(0.0)
+    }
+
+    fn intersect(&self, other: &ScreenRect) -> Option<ScreenRect> {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        (right > x && bottom > y).then_some(ScreenRect {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        })
+    }
+
+    /// Größter Rest-Rect nach Abzug von `hole` (links, rechts, oben oder unten davon)
+    fn subtract(&self, hole: &ScreenRect) -> Option<ScreenRect> {
+        let Some(hole) = self.intersect(hole) else {
+            return Some(*self);
+        };
+
+        [
+            ScreenRect { width: hole.x - self.x, ..*self },
+            ScreenRect { x: hole.right(), width: self.right() - hole.right(), ..*self },
+            ScreenRect { height: hole.y - self.y, ..*self },
+            ScreenRect { y: hole.bottom(), height: self.bottom() - hole.bottom(), ..*self },
+        ]
+        .into_iter()
+        .filter(|rest| rest.area() > 0.0)
+        .max_by(|a, b| a.area().total_cmp(&b.area()))
+    }
+}
+
+/// Layout-Zustand neben dem `WebTabState`
+#[derive(Default)]
+pub struct CanvasLayoutState {
+    viewport: Option<CanvasViewport>,
+    /// App-Oberfläche über dem Canvas (Panels, Modals) - Tabs malen nie darüber
+    exclusions: HashMap<String, ScreenRect>,
+    /// Vom Frontend versteckte Tabs (Focus Zone, Restore) - das Layout zeigt sie nie
+    hidden: HashSet<String>,
+    /// Vom Layout versteckte Tabs (außerhalb des Canvas oder zu klein)
+    offscreen: HashSet<String>,
+    /// Hauptfenster minimiert - alle Tabs sind versteckt
+    minimized: bool,
+}
+
+impl CanvasLayoutState {
+    /// Darf das Fenster dieses Tabs gerade sichtbar sein?
+    pub fn should_show(&self, tab_id: &str) -> bool {
+        !self.minimized && !self.hidden.contains(tab_id) && !self.offscreen.contains(tab_id)
+    }
+
+    /// Frontend-Sichtbarkeit merken; `true` wenn das Fenster gezeigt werden darf
+    pub fn set_hidden(&mut self, tab_id: &str, hidden: bool) -> bool {
+        if hidden {
+            self.hidden.insert(tab_id.to_string());
+        } else {
+            self.hidden.remove(tab_id);
+        }
+        self.should_show(tab_id)
+    }
+
+    pub fn forget(&mut self, tab_id: &str) {
+        self.hidden.remove(tab_id);
+        self.offscreen.remove(tab_id);
+    }
+
+    /// Sichtbare Canvas-Fläche in physischen Pixeln (`None` solange unbekannt)
+    pub fn canvas_area(&self, scale_factor: f64) -> Option<TabBounds> {
+        let viewport = self.viewport.filter(|v| v.width > 0.0 && v.height > 0.0)?;
+        let (x0, x1) = (
+            (viewport.origin_x * scale_factor).round(),
+            ((viewport.origin_x + viewport.width) * scale_factor).round(),
+        );
+        let (y0, y1) = (
+            (viewport.origin_y * scale_factor).round(),
+            ((viewport.origin_y + viewport.height) * scale_factor).round(),
+        );
+        Some(TabBounds {
+            x: x0 as i32,
+            y: y0 as i32,
+            width: (x1 - x0).max(1.0) as u32,
+            height: (y1 - y0).max(1.0) as u32,
+        })
+    }
+
+    /// Zielfläche für `FillCanvas`-Tabs: die sichtbare Canvas-Fläche ohne
+    /// Exclusion-Rects, damit Panels und Modals frei bleiben. `None` solange
+    /// die Fläche unbekannt ist oder zu wenig davon frei bleibt.
+    pub fn fill_area(&self, scale_factor: f64) -> Option<TabBounds> {
+        let viewport = self.viewport.filter(|v| v.width > 0.0 && v.height > 0.0)?;
+        let canvas = ScreenRect {
+            x: viewport.origin_x,
+            y: viewport.origin_y,
+            width: viewport.width,
+            height: viewport.height,
+        };
+        free_bounds(canvas, &self.sorted_exclusions(), scale_factor)
+    }
+
+    /// Sortiert, damit das Ergebnis nicht von der HashMap-Reihenfolge abhängt
+    fn sorted_exclusions(&self) -> Vec<ScreenRect> {
+        let mut exclusions: Vec<(&String, &ScreenRect)> = self.exclusions.iter().collect();
+        exclusions.sort_by(|a, b| a.0.cmp(b.0));
+        exclusions.into_iter().map(|(_, rect)| *rect).collect()
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WebTabVisibilityEvent {
+    pub tab_id: String,
+    /// `false` solange das Layout den Tab versteckt
+    pub on_screen: bool,
+}
+
+/// Projiziert einen Canvas-Rect auf Fenster-Bounds (physische Pixel relativ
+/// zum Inhaltsbereich des Hauptfensters). Der Tab wird auf die sichtbare
+/// Canvas-Fläche beschnitten und weicht Exclusion-Rects aus, indem er auf den
+/// größten freien Teil schrumpft. `None` = Tab verstecken.
+pub fn project(
+    rect: &CanvasRect,
+    viewport: &CanvasViewport,
+    exclusions: &[ScreenRect],
+    scale_factor: f64,
+) -> Option<TabBounds> {
+    let mut visible = ScreenRect {
+        x: viewport.origin_x + viewport.x + rect.x * viewport.scale,
+        y: viewport.origin_y + viewport.y + rect.y * viewport.scale,
+        width: rect.width * viewport.scale,
+        height: rect.height * viewport.scale,
+    };
+
+    if viewport.width > 0.0 && viewport.height > 0.0 {
+        let canvas = ScreenRect {
+            x: viewport.origin_x,
+            y: viewport.origin_y,
+            width: viewport.width,
+            height: viewport.height,
+        };
+        visible = visible.intersect(&canvas)?;
+    }
+
+    free_bounds(visible, exclusions, scale_factor)
+}
+
+/// Weicht den Exclusion-Rects aus und rechnet in physische Pixel um
+fn free_bounds(mut visible: ScreenRect, exclusions: &[ScreenRect], scale_factor: f64) -> Option<TabBounds> {
+    for exclusion in exclusions {
+        visible = visible.subtract(exclusion)?;
+    }
+
+    // Auch beschnittene Reste unter der Mindestgröße verstecken
+    if visible.width < MIN_VISIBLE_SIZE || visible.height < MIN_VISIBLE_SIZE {
+        return None;
+    }
+
+    // Kanten runden statt Größe, damit benachbarte Tabs nicht um 1px springen
+    let (x0, x1) = ((visible.x * scale_factor).round(), (visible.right() * scale_factor).round());
+    let (y0, y1) = ((visible.y * scale_factor).round(), (visible.bottom() * scale_factor).round());
+    Some(TabBounds {
+        x: x0 as i32,
+        y: y0 as i32,
+        width: (x1 - x0).max(1.0) as u32,
+        height: (y1 - y0).max(1.0) as u32,
+    })
+}
+
+/// Eine geplante Fenster-Änderung
+struct Placement {
+    tab_id: String,
+    bounds: Option<TabBounds>,
+    show: bool,
+    hide: bool,
+}
+
+/// Positioniert die Tabs mit Canvas-Rect neu (`only`: nur diesen einen).
+/// Alle Fenster-Änderungen laufen gesammelt in einem Main-Thread-Durchlauf.
+pub fn layout_tabs(app: &AppHandle, only: Option<&str>) -> Result<(), WorkspaceError> {
+    let layout_state = app.state::<Mutex<CanvasLayoutState>>();
+    let tab_state = app.state::<Mutex<WebTabState>>();
+
+    let main_window = app
+        .get_webview_window("main")
+        .ok_or_else(WorkspaceError::main_window_not_found)?;
+    let scale_factor = main_window
+        .scale_factor()
+        .map_err(WorkspaceError::window_op("scale_factor"))?;
+    let main_pos = main_window
+        .inner_position()
+        .map_err(WorkspaceError::window_op("inner_position"))?;
+
+    let mut placements = Vec::new();
+    let mut events = Vec::new();
+    {
+        let mut layout = layout_state.lock_or_recover();
+        let Some(viewport) = layout.viewport else {
+            return Ok(());
+        };
+        let exclusions = layout.sorted_exclusions();
+        let canvas_known = viewport.width > 0.0 && viewport.height > 0.0;
+        let fill = layout.fill_area(scale_factor);
+        let mut tabs = tab_state.lock_or_recover();
+
+        for tab in tabs.tabs.values_mut() {
+            if only.is_some_and(|id| id != tab.id) {
+                continue;
+            }
+
+            let projected = match (tab.is_fullscreen, tab.fullscreen_mode.unwrap_or_default()) {
+                // Füllt die Canvas-Fläche, weicht aber denselben Exclusions aus wie Karten
+                (true, FullscreenMode::FillCanvas) if canvas_known => fill.clone(),
+                (true, _) => continue,
+                (false, _) => match tab.canvas_rect {
+                    Some(rect) => project(&rect, &viewport, &exclusions, scale_factor),
+                    None => continue,
+                },
+            };
+            let was_offscreen = layout.offscreen.contains(&tab.id);
+            let on_screen = projected.is_some();
+            let changed = on_screen == was_offscreen;
+
+            // Unveränderte Bounds nicht erneut setzen
+            let bounds = match projected {
+                Some(bounds) if was_offscreen || bounds != tab.bounds => {
+                    tab.bounds = bounds.clone();
+                    Some(bounds)
+                }
+                _ => None,
+            };
+
+            let placement = Placement {
+                tab_id: tab.id.clone(),
+                bounds,
+                show: changed && on_screen && !layout.hidden.contains(&tab.id) && !layout.minimized,
+                hide: changed && !on_screen,
+            };
+
+            if changed {
+                if on_screen {
+                    layout.offscreen.remove(&tab.id);
+                } else {
+                    layout.offscreen.insert(tab.id.clone());
+                }
+                events.push(WebTabVisibilityEvent {
+                    tab_id: tab.id.clone(),
+                    on_screen,
+                });
+            }
+
+            if placement.bounds.is_some() || placement.show || placement.hide {
+                placements.push(placement);
+            }
+        }
+    }
+
+    if !placements.is_empty() {
+        apply_placements(app, main_pos, placements)?;
+        crate::snapshot::schedule_snapshot(app);
+    }
+
+    for event in events {
+        app.emit("web-tab-visibility", event).ok();
+    }
+
+    Ok(())
+}
+
+/// Wendet alle Änderungen in einem Main-Thread-Durchlauf an
+fn apply_placements(
+    app: &AppHandle,
+    main_pos: PhysicalPosition<i32>,
+    placements: Vec<Placement>,
+) -> Result<(), WorkspaceError> {
+    let handle = app.clone();
+    app.run_on_main_thread(move || {
+        for placement in placements {
+            let Some(webview) = handle.get_webview_window(&placement.tab_id) else {
+                continue;
+            };
+            if placement.hide {
+                webview.hide().ok();
+            }
+            if let Some(bounds) = placement.bounds {
+                webview
+                    .set_position(PhysicalPosition::new(main_pos.x + bounds.x, main_pos.y + bounds.y))
+                    .ok();
+                webview.set_size(PhysicalSize::new(bounds.width, bounds.height)).ok();
+            }
+            if placement.show {
+                webview.show().ok();
+            }
+        }
+    })
+    .map_err(WorkspaceError::window_op("run_on_main_thread"))
+}
+
+// ============================================================================
+// MAIN WINDOW - Tabs folgen dem Hauptfenster
+// ============================================================================
+
+/// Window-Events des Hauptfensters: verschieben, skalieren, minimieren
+pub fn handle_main_window_event(app: &AppHandle, event: &WindowEvent) {
+    let result = match event {
+        WindowEvent::Moved(_) => anchor_tabs(app),
+        WindowEvent::Resized(size) => {
+            // Minimieren kommt als Resize (Windows meldet 0x0)
+            let minimized = size.width == 0
+                || size.height == 0
+                || app
+                    .get_webview_window("main")
+                    .and_then(|main| main.is_minimized().ok())
+                    .unwrap_or(false);
+            set_minimized(app, minimized)
+        }
+        // Canvas-Rects hängen von der DPI ab, feste Bounds nicht
+        WindowEvent::ScaleFactorChanged { .. } => {
+            anchor_tabs(app).and_then(|_| layout_tabs(app, None))
+        }
+        _ => Ok(()),
+    };
+
+    if let Err(e) = result {
+        log::warn!("Failed to follow main window: {}", e);
+    }
+}
+
+/// Alle Tabs an die aktuelle Position des Hauptfensters hängen.
+/// Fullscreen-Tabs (Canvas füllen) decken weiter die Canvas-Fläche ab,
+/// OS-Vollbild-Tabs bleiben unangetastet.
+fn anchor_tabs(app: &AppHandle) -> Result<(), WorkspaceError> {
+    let main_window = app
+        .get_webview_window("main")
+        .ok_or_else(WorkspaceError::main_window_not_found)?;
+    let main_pos = main_window
+        .inner_position()
+        .map_err(WorkspaceError::window_op("inner_position"))?;
+    let fill = fullscreen::fill_bounds_for_app(app, &main_window)?;
+
+    let placements: Vec<Placement> = {
+        let tab_state = app.state::<Mutex<WebTabState>>();
+        let tabs = tab_state.lock_or_recover();
+        tabs.tabs
+            .values()
+            .filter_map(|tab| {
+                let bounds = match (tab.is_fullscreen, tab.fullscreen_mode.unwrap_or_default()) {
+                    (false, _) => tab.bounds.clone(),
+                    (true, FullscreenMode::FillCanvas) => fill.clone(),
+                    (true, FullscreenMode::Os) => return None,
+                };
+                Some(Placement {
+                    tab_id: tab.id.clone(),
+                    bounds: Some(bounds),
+                    show: false,
+                    hide: false,
+                })
+            })
+            .collect()
+    };
+
+    if placements.is_empty() {
+        return Ok(());
+    }
+    apply_placements(app, main_pos, placements)
+}
+
+/// Minimieren versteckt alle Tabs, Wiederherstellen zeigt die vorher sichtbaren
+fn set_minimized(app: &AppHandle, minimized: bool) -> Result<(), WorkspaceError> {
+    let placements: Vec<Placement> = {
+        let layout_state = app.state::<Mutex<CanvasLayoutState>>();
+        let mut layout = layout_state.lock_or_recover();
+        if layout.minimized == minimized {
+            drop(layout);
+            // Normales Resize: Position des Inhaltsbereichs kann sich verschoben haben
+            return if minimized { Ok(()) } else { anchor_tabs(app) };
+        }
+        layout.minimized = minimized;
+
+        let tab_state = app.state::<Mutex<WebTabState>>();
+        let tabs = tab_state.lock_or_recover();
+        tabs.tabs
+            .keys()
+            .map(|tab_id| Placement {
+                tab_id: tab_id.clone(),
+                bounds: None,
+                show: layout.should_show(tab_id),
+                hide: minimized,
+            })
+            .collect()
+    };
+
+    if minimized {
+        return apply_placements(app, PhysicalPosition::new(0, 0), placements);
+    }
+    // Erst an die neue Position, dann einblenden
+    anchor_tabs(app)?;
+    apply_placements(app, PhysicalPosition::new(0, 0), placements)
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Neuer Viewport nach Pan/Zoom - ersetzt die `update_web_tab_bounds`-Aufrufe pro Tab
+#[tauri::command]
+pub fn set_canvas_viewport(
+    app: AppHandle,
+    layout: tauri::State<'_, Mutex<CanvasLayoutState>>,
+    viewport: CanvasViewport,
+) -> Result<(), WorkspaceError> {
+    if !(viewport.scale.is_finite() && viewport.scale > 0.0) {
+        return Err(WorkspaceError::InvalidInput(format!(
+            "Invalid canvas scale: {}",
+            viewport.scale
+        )));
+    }
+
+    layout.lock_or_recover().viewport = Some(viewport);
+    layout_tabs(&app, None)
+}
+
+/// Registriert (oder ersetzt) einen Bereich, über den Tabs nie malen dürfen
+#[tauri::command]
+pub fn set_canvas_exclusion(
+    app: AppHandle,
+    layout: tauri::State<'_, Mutex<CanvasLayoutState>>,
+    id: String,
+    rect: ScreenRect,
+) -> Result<(), WorkspaceError> {
+    let changed = layout.lock_or_recover().exclusions.insert(id, rect) != Some(rect);
+    if changed {
+        layout_tabs(&app, None)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn remove_canvas_exclusion(
+    app: AppHandle,
+    layout: tauri::State<'_, Mutex<CanvasLayoutState>>,
+    id: String,
+) -> Result<(), WorkspaceError> {
+    let removed = layout.lock_or_recover().exclusions.remove(&id).is_some();
+    if removed {
+        layout_tabs(&app, None)?;
+    }
+    Ok(())
+}
+
+/// Canvas-Rect eines Tabs setzen (Karte verschoben oder skaliert)
+#[tauri::command]
+pub fn set_web_tab_canvas_rect(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<WebTabState>>,
+    tab_id: String,
+    rect: CanvasRect,
+) -> Result<(), WorkspaceError> {
+    {
+        let mut tab_state = state.lock_or_recover();
+        let tab = tab_state
+            .tabs
+            .get_mut(&tab_id)
+            .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))?;
+        tab.canvas_rect = Some(rect);
+    }
+    layout_tabs(&app, Some(&tab_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn viewport(x: f64, y: f64, scale: f64) -> CanvasViewport {
+        CanvasViewport {
+            x,
+            y,
+            scale,
+            origin_x: 0.0,
+            origin_y: 48.0,
+            width: 1000.0,
+            height: 700.0,
+        }
+    }
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> CanvasRect {
+        CanvasRect { x, y, width, height }
+    }
+
+    #[test]
+    fn test_project_applies_viewport_and_dpi() {
+        let bounds = project(&rect(100.0, 50.0, 400.0, 300.0), &viewport(-20.0, 10.0, 0.5), &[], 2.0).unwrap();
+        // left = -20 + 50 = 30, top = 48 + 10 + 25 = 83 (logisch), dann * 2
+        assert_eq!(bounds, TabBounds { x: 60, y: 166, width: 400, height: 300 });
+    }
+
+    #[test]
+    fn test_canvas_area_in_physical_pixels() {
+        let mut layout = CanvasLayoutState::default();
+        assert_eq!(layout.canvas_area(2.0), None);
+
+        layout.viewport = Some(viewport(0.0, 0.0, 1.0));
+        assert_eq!(layout.canvas_area(1.5), Some(TabBounds { x: 0, y: 72, width: 1500, height: 1050 }));
+    }
+
+    #[test]
+    fn test_fill_area_leaves_exclusions_free() {
+        let mut layout = CanvasLayoutState {
+            viewport: Some(viewport(0.0, 0.0, 1.0)),
+            ..Default::default()
+        };
+        assert_eq!(layout.fill_area(1.0), Some(TabBounds { x: 0, y: 48, width: 1000, height: 700 }));
+
+        // Side Panel rechts: der Tab endet davor
+        let panel = ScreenRect { x: 700.0, y: 48.0, width: 300.0, height: 700.0 };
+        layout.exclusions.insert("side-panel".into(), panel);
+        assert_eq!(layout.fill_area(2.0), Some(TabBounds { x: 0, y: 96, width: 1400, height: 1400 }));
+
+        // Modal über der ganzen Fläche: nichts bleibt frei
+        let modal = ScreenRect { x: 0.0, y: 0.0, width: 1000.0, height: 748.0 };
+        layout.exclusions.insert("modal".into(), modal);
+        assert_eq!(layout.fill_area(1.0), None);
+    }
+
+    #[test]
+    fn test_project_hides_offscreen_and_tiny_tabs() {
+        let view = viewport(0.0, 0.0, 1.0);
+        assert!(project(&rect(2000.0, 0.0, 400.0, 300.0), &view, &[], 1.0).is_none());
+        assert!(project(&rect(0.0, -400.0, 400.0, 300.0), &view, &[], 1.0).is_none());
+        // Teilweise sichtbar bleibt sichtbar
+        assert!(project(&rect(-300.0, 0.0, 400.0, 300.0), &view, &[], 1.0).is_some());
+        // Weit herausgezoomt
+        assert!(project(&rect(0.0, 0.0, 400.0, 300.0), &viewport(0.0, 0.0, 0.1), &[], 1.0).is_none());
+
+        // Ohne bekannte Canvas-Größe wird nichts ausgeblendet
+        let unknown = CanvasViewport { width: 0.0, height: 0.0, ..view };
+        assert!(project(&rect(2000.0, 0.0, 400.0, 300.0), &unknown, &[], 1.0).is_some());
+    }
+
+    #[test]
+    fn test_project_neighbours_share_edges() {
+        // Zwei angrenzende Karten bei krummer Skalierung: keine Lücke, keine Überlappung
+        let view = viewport(0.3, 0.0, 0.37);
+        let a = project(&rect(0.0, 0.0, 333.0, 333.0), &view, &[], 1.5).unwrap();
+        let b = project(&rect(333.0, 0.0, 333.0, 333.0), &view, &[], 1.5).unwrap();
+        assert_eq!(a.x + a.width as i32, b.x);
+    }
+
+    #[test]
+    fn test_project_clips_to_canvas() {
+        // Karte ragt links und oben aus dem Canvas (unter die Toolbar)
+        let bounds = project(&rect(-100.0, -100.0, 400.0, 300.0), &viewport(0.0, 0.0, 1.0), &[], 1.0).unwrap();
+        assert_eq!(bounds, TabBounds { x: 0, y: 48, width: 300, height: 200 });
+    }
+
+    #[test]
+    fn test_project_avoids_exclusions() {
+        let view = viewport(0.0, 0.0, 1.0);
+        let card = rect(0.0, 0.0, 600.0, 400.0);
+
+        // Side Panel rechts: Tab schrumpft auf den linken Teil
+        let panel = ScreenRect { x: 400.0, y: 0.0, width: 600.0, height: 748.0 };
+        assert_eq!(
+            project(&card, &view, &[panel], 1.0).unwrap(),
+            TabBounds { x: 0, y: 48, width: 400, height: 400 }
+        );
+
+        // Modal über allem: Tab wird versteckt
+        let modal = ScreenRect { x: 0.0, y: 0.0, width: 1000.0, height: 748.0 };
+        assert!(project(&card, &view, &[modal], 1.0).is_none());
+
+        // Flaches Popup an der Oberkante: der größere Streifen darunter bleibt
+        let popup = ScreenRect { x: 500.0, y: 48.0, width: 200.0, height: 50.0 };
+        assert_eq!(
+            project(&card, &view, &[popup], 1.0).unwrap(),
+            TabBounds { x: 0, y: 98, width: 600, height: 350 }
+        );
+    }
+
+    #[test]
+    fn test_hidden_tabs_stay_hidden() {
+        let mut layout = CanvasLayoutState::default();
+        assert!(!layout.set_hidden("webtab-a", true));
+        layout.offscreen.insert("webtab-a".to_string());
+        assert!(!layout.set_hidden("webtab-a", false));
+
+        layout.forget("webtab-a");
+        assert!(layout.set_hidden("webtab-a", false));
+
+        // Minimiertes Hauptfenster versteckt alles, der Rest bleibt gemerkt
+        layout.set_hidden("webtab-b", true);
+        layout.minimized = true;
+        assert!(!layout.should_show("webtab-a"));
+        layout.minimized = false;
+        assert!(layout.should_show("webtab-a"));
+        assert!(!layout.should_show("webtab-b"));
+    }
+}
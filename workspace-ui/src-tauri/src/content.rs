@@ -0,0 +1,267 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::oneshot;
+
+use crate::error::{LockExt, WorkspaceError};
+use crate::WebTabState;
+
+// ============================================================================
+// AI CONTENT EXTRACTION - Rückkanal vom Webview über IPC
+// ============================================================================
+
+/// Wie lange auf die Antwort des injizierten Scripts gewartet wird
+const EXTRACTION_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Maximale Länge des extrahierten Seitentexts (Zeichen)
+const MAX_TEXT_LENGTH: usize = 10_000;
+
+/// Maximale Anzahl an Überschriften pro Seite
+const MAX_HEADINGS: usize = 50;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WebviewContent {
+    pub tab_id: String,
+    pub url: String,
+    pub title: Option<String>,
+    pub content: Option<String>,
+    pub description: Option<String>,
+    pub headings: Vec<String>,
+    pub selected_text: Option<String>,
+}
+
+/// Payload, den das injizierte Script an `report_webview_content` schickt
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ExtractedContent {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub headings: Vec<String>,
+    pub selected_text: Option<String>,
+    pub text: Option<String>,
+}
+
+/// Offene Extraktions-Anfrage: gehört genau zu einem Tab
+struct PendingExtraction {
+    tab_id: String,
+    sender: oneshot::Sender<ExtractedContent>,
+}
+
+/// State für laufende Extraktionen (Request-ID -> wartender Aufrufer)
+#[derive(Default)]
+pub struct ContentExtractionState {
+    pending: Mutex<HashMap<String, PendingExtraction>>,
+}
+
+/// Baut das Script, das den Seiteninhalt sammelt und per IPC zurückschickt
+fn build_extraction_script(request_id: &str) -> String {
+    // Request-ID als JSON-String einbetten, damit kein Escaping schiefgeht
+    let request_id = serde_json::to_string(request_id).unwrap_or_else(|_| "\"\"".into());
+
+    format!(
+        r#"
+        (function() {{
+            const requestId = {request_id};
+            const send = (payload) => {{
+                try {{
+                    // Abgelehnter Aufruf (z.B. ACL) darf nicht als unhandled rejection enden
+                    Promise.resolve(window.__TAURI_INTERNALS__.invoke('report_webview_content', {{ requestId, payload }}))
+                        .catch((e) => console.warn('[workspace] content report rejected:', e));
+                }} catch (e) {{}}
+            }};
+            try {{
+                const meta = document.querySelector('meta[name="description"], meta[property="og:description"]');
+                const headings = Array.from(document.querySelectorAll('h1, h2, h3'))
+                    .map((h) => (h.innerText || '').trim())
+                    .filter((h) => h.length > 0)
+                    .slice(0, {max_headings});
+                const selection = window.getSelection ? String(window.getSelection()).trim() : '';
+                send({{
+                    title: document.title || null,
+                    description: meta ? meta.getAttribute('content') : null,
+                    headings,
+                    selected_text: selection || null,
+                    text: document.body ? document.body.innerText.substring(0, {max_text}) : null,
+                }});
+            }} catch (e) {{
+                send({{ title: document.title || null, description: null, headings: [], selected_text: null, text: null }});
+            }}
+        }})();
+        "#,
+        request_id = request_id,
+        max_headings = MAX_HEADINGS,
+        max_text = MAX_TEXT_LENGTH,
+    )
+}
+
+/// Führt eine Extraktion für einen Tab aus und wartet auf die Antwort
+async fn extract_tab_content(app: &AppHandle, tab_id: &str) -> Result<WebviewContent, WorkspaceError> {
+    let webview = app
+        .get_webview_window(tab_id)
+        .ok_or_else(|| WorkspaceError::tab_not_found(tab_id))?;
+
+    let url = webview
+        .url()
+        .map_err(WorkspaceError::window_op("url"))?
+        .to_string();
+
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let (sender, receiver) = oneshot::channel();
+
+    let state = app.state::<ContentExtractionState>();
+    state.pending.lock_or_recover().insert(
+        request_id.clone(),
+        PendingExtraction {
+            tab_id: tab_id.to_string(),
+            sender,
+        },
+    );
+
+    if let Err(e) = webview.eval(&build_extraction_script(&request_id)) {
+        state.pending.lock_or_recover().remove(&request_id);
+        return Err(WorkspaceError::window_op("eval")(e));
+    }
+
+    let extracted = match tokio::time::timeout(EXTRACTION_TIMEOUT, receiver).await {
+        Ok(Ok(extracted)) => extracted,
+        Ok(Err(_)) => return Err(WorkspaceError::Internal("Content extraction was cancelled".into())),
+        Err(_) => {
+            state.pending.lock_or_recover().remove(&request_id);
+            return Err(WorkspaceError::Timeout {
+                op: "content_extraction",
+                id: tab_id.to_string(),
+            });
+        }
+    };
+
+    Ok(WebviewContent {
+        tab_id: tab_id.to_string(),
+        url,
+        title: extracted.title.or_else(|| webview.title().ok()),
+        content: extracted.text,
+        description: extracted.description,
+        headings: extracted.headings,
+        selected_text: extracted.selected_text,
+    })
+}
+
+/// Fallback wenn die Seite nicht antwortet: nur URL und Window-Title
+fn basic_tab_content(app: &AppHandle, tab_id: &str) -> Option<WebviewContent> {
+    let webview = app.get_webview_window(tab_id)?;
+
+    Some(WebviewContent {
+        tab_id: tab_id.to_string(),
+        url: webview.url().map(|u| u.to_string()).unwrap_or_default(),
+        title: webview.title().ok(),
+        content: None,
+        description: None,
+        headings: Vec::new(),
+        selected_text: None,
+    })
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Extrahiert Content aus einem Webview für AI-Analyse
+#[tauri::command]
+pub async fn extract_webview_content(
+    app: AppHandle,
+    tab_id: String,
+) -> Result<WebviewContent, WorkspaceError> {
+    extract_tab_content(&app, &tab_id).await
+}
+
+/// Extrahiert Content von allen offenen Webviews (parallel)
+#[tauri::command]
+pub async fn extract_all_webview_contents(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<WebTabState>>,
+) -> Result<Vec<WebviewContent>, WorkspaceError> {
+    let tab_ids: Vec<String> = {
+        let tab_state = state.lock_or_recover();
+        tab_state.tabs.keys().cloned().collect()
+    };
+
+    let handles: Vec<_> = tab_ids
+        .into_iter()
+        .map(|tab_id| {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                match extract_tab_content(&app, &tab_id).await {
+                    Ok(content) => Some(content),
+                    Err(e) => {
+                        log::warn!("Content extraction failed for {}: {}", tab_id, e);
+                        basic_tab_content(&app, &tab_id)
+                    }
+                }
+            })
+        })
+        .collect();
+
+    let mut contents = Vec::new();
+    for handle in handles {
+        if let Ok(Some(content)) = handle.await {
+            contents.push(content);
+        }
+    }
+
+    Ok(contents)
+}
+
+/// Rückkanal: wird vom injizierten Script im Web-Tab aufgerufen
+#[tauri::command]
+pub fn report_webview_content(
+    webview: tauri::Webview,
+    state: tauri::State<'_, ContentExtractionState>,
+    request_id: String,
+    payload: ExtractedContent,
+) -> Result<(), WorkspaceError> {
+    let mut pending = state.pending.lock_or_recover();
+
+    // Nur der Tab, an den die Anfrage ging, darf sie beantworten
+    match pending.get(&request_id) {
+        Some(entry) if entry.tab_id == webview.label() => {}
+        Some(_) => return Err(WorkspaceError::InvalidInput("Content report from wrong tab".into())),
+        None => {
+            return Err(WorkspaceError::NotFound {
+                kind: "extraction_request",
+                id: request_id,
+            })
+        }
+    }
+
+    if let Some(entry) = pending.remove(&request_id) {
+        entry.sender.send(payload).ok();
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_web_tab_capability_allows_content_report() {
+        let remote: serde_json::Value =
+            serde_json::from_str(include_str!("../capabilities/webtab-remote.json")).unwrap();
+        let permissions = remote["permissions"].as_array().unwrap();
+        assert!(permissions.contains(&serde_json::json!("allow-report-webview-content")));
+        assert!(remote["windows"].as_array().unwrap().contains(&serde_json::json!("webtab-*")));
+
+        // Die Permission existiert nur, wenn das App-Manifest den Command deklariert
+        assert!(include_str!("../build.rs").contains("\"report_webview_content\","));
+    }
+
+    #[test]
+    fn test_extraction_script_reports_and_handles_rejection() {
+        let script = build_extraction_script("req-\"1");
+        assert!(script.contains("invoke('report_webview_content'"));
+        assert!(script.contains(r#"const requestId = "req-\"1";"#));
+        assert!(script.contains(".catch("));
+    }
+}
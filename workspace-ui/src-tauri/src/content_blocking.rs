@@ -0,0 +1,1252 @@
+use publicsuffix::Psl;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Manager, Url};
+
+use crate::error::{LockExt, WorkspaceError};
+use crate::policy::host_matches;
+
+// ============================================================================
+// CONTENT BLOCKING - Tracker und Werbung per Filterliste blockieren
+// ============================================================================
+//
+// Filterlisten im EasyList-/uBlock-Format liegen als `*.txt` in
+// `<app_data>/filters/`. Unterstützt werden Netzwerkregeln (`||host^`, `|`,
+// `*`, `^`, `@@`) mit den Optionen Ressourcentyp, third-/first-party,
+// `domain=`, `match-case` und `important`, dazu Hosts-Dateien. Kosmetische
+// Regeln (`##`) werden übersprungen, Regex-Regeln und unbekannte Optionen
+// verworfen - lieber eine Regel zu wenig als eine falsche.
+//
+// Durchgesetzt wird für CEF-Browser über den Request-Filter des cef-plugins
+// und für native Tabs über WebView2 (`WebResourceRequested`). WebKitGTK und
+// WKWebView bieten in den Bindings keinen Hook pro Request.
+
+/// Was ein Request lädt (Filteroptionen `$script`, `$image`, ...)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResourceType {
+    /// Seite im Tab selbst - nur mit `$document` blockiert
+    Document,
+    Subdocument,
+    Script,
+    Stylesheet,
+    Image,
+    Font,
+    Media,
+    Object,
+    Xhr,
+    WebSocket,
+    Ping,
+    Other,
+}
+
+impl ResourceType {
+    fn bit(self) -> u16 {
+        1 << self as u16
+    }
+
+    fn from_option(name: &str) -> Option<Self> {
+        Some(match name {
+            "document" | "doc" => ResourceType::Document,
+            "subdocument" | "frame" => ResourceType::Subdocument,
+            "script" => ResourceType::Script,
+            "stylesheet" | "css" => ResourceType::Stylesheet,
+            "image" => ResourceType::Image,
+            "font" => ResourceType::Font,
+            "media" => ResourceType::Media,
+            "object" => ResourceType::Object,
+            "xmlhttprequest" | "xhr" => ResourceType::Xhr,
+            "websocket" => ResourceType::WebSocket,
+            "ping" | "beacon" => ResourceType::Ping,
+            "other" => ResourceType::Other,
+            _ => return None,
+        })
+    }
+}
+
+/// Regeln ohne Typ-Option gelten für alles außer der Seite selbst
+const DEFAULT_TYPES: u16 = !(1 << ResourceType::Document as u16);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Anchor {
+    None,
+    /// `|https://...` - Anfang der URL
+    Start,
+    /// `||example.com` - Anfang des Hosts oder einer Subdomain
+    Host,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Party {
+    Any,
+    First,
+    Third,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct NetworkRule {
+    /// Ohne Anker, klein geschrieben (außer `match-case`); `*` und `^` als
+    /// Platzhalter. Fertig für `wildcard_match` (siehe `compile_pattern`).
+    pattern: String,
+    anchor: Anchor,
+    /// `|` am Ende - Muster muss bis zum Ende der URL passen
+    end_anchor: bool,
+    types: u16,
+    party: Party,
+    include_domains: Vec<String>,
+    exclude_domains: Vec<String>,
+    match_case: bool,
+    important: bool,
+}
+
+#[derive(Debug, PartialEq)]
+enum ParsedLine {
+    Block(NetworkRule),
+    Allow(NetworkRule),
+    /// Kommentar, Header, leere oder kosmetische Zeile
+    Ignored,
+    /// Regex, unbekannte Option oder Muster, das alles treffen würde
+    Unsupported,
+}
+
+/// Adressen, die in Hosts-Dateien auf "blockiert" zeigen
+const HOSTS_SINKS: &[&str] = &["0.0.0.0", "127.0.0.1", "::", "::1"];
+
+fn parse_line(line: &str) -> ParsedLine {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('!') || line.starts_with('[') || line.starts_with('#') {
+        return ParsedLine::Ignored;
+    }
+    if ["##", "#@#", "#?#", "#$#", "#%#"]
+        .iter()
+        .any(|marker| line.contains(marker))
+    {
+        return ParsedLine::Ignored;
+    }
+
+    // Hosts-Datei: `0.0.0.0 tracker.example`
+    let mut words = line.split_whitespace();
+    if let (Some(sink), Some(host)) = (words.next(), words.next()) {
+        if !HOSTS_SINKS.contains(&sink) {
+            return ParsedLine::Unsupported;
+        }
+        if HOSTS_SINKS.contains(&host) || host == "localhost" || !host.contains('.') {
+            return ParsedLine::Ignored;
+        }
+        return ParsedLine::Block(host_rule(&host.to_ascii_lowercase()));
+    }
+
+    let (exception, line) = match line.strip_prefix("@@") {
+        Some(rest) => (true, rest),
+        None => (false, line),
+    };
+
+    let (pattern, options) = split_options(line);
+    if pattern.len() > 1 && pattern.starts_with('/') && pattern.ends_with('/') {
+        return ParsedLine::Unsupported;
+    }
+
+    let mut rule = NetworkRule {
+        pattern: String::new(),
+        anchor: Anchor::None,
+        end_anchor: false,
+        types: DEFAULT_TYPES,
+        party: Party::Any,
+        include_domains: Vec::new(),
+        exclude_domains: Vec::new(),
+        match_case: false,
+        important: false,
+    };
+    if let Some(options) = options {
+        if apply_options(&mut rule, options).is_none() {
+            return ParsedLine::Unsupported;
+        }
+    }
+
+    let mut pattern = pattern;
+    if let Some(rest) = pattern.strip_prefix("||") {
+        rule.anchor = Anchor::Host;
+        pattern = rest;
+    } else if let Some(rest) = pattern.strip_prefix('|') {
+        rule.anchor = Anchor::Start;
+        pattern = rest;
+    }
+    if let Some(rest) = pattern.strip_suffix('|') {
+        rule.end_anchor = true;
+        pattern = rest;
+    }
+    if rule.anchor == Anchor::None && !rule.end_anchor {
+        pattern = pattern.trim_matches('*');
+    }
+
+    // Leeres Muster trifft jede URL - nur mit Domain-Einschränkung sinnvoll
+    if pattern.trim_matches('*').is_empty() && rule.include_domains.is_empty() {
+        return ParsedLine::Unsupported;
+    }
+
+    let pattern = if rule.match_case {
+        pattern.to_string()
+    } else {
+        pattern.to_ascii_lowercase()
+    };
+    rule.pattern = compile_pattern(&pattern, rule.anchor, rule.end_anchor);
+
+    if exception {
+        ParsedLine::Allow(rule)
+    } else {
+        ParsedLine::Block(rule)
+    }
+}
+
+fn host_rule(host: &str) -> NetworkRule {
+    NetworkRule {
+        pattern: compile_pattern(&format!("{}^", host), Anchor::Host, false),
+        anchor: Anchor::Host,
+        end_anchor: false,
+        types: DEFAULT_TYPES,
+        party: Party::Any,
+        include_domains: Vec::new(),
+        exclude_domains: Vec::new(),
+        match_case: false,
+        important: false,
+    }
+}
+
+/// Ohne Anker darf vor dem Muster, ohne Endanker danach beliebiges stehen -
+/// einmal beim Parsen statt bei jedem Request
+fn compile_pattern(pattern: &str, anchor: Anchor, end_anchor: bool) -> String {
+    let mut compiled = String::with_capacity(pattern.len() + 2);
+    if anchor == Anchor::None {
+        compiled.push('*');
+    }
+    compiled.push_str(pattern);
+    if !end_anchor {
+        compiled.push('*');
+    }
+    compiled
+}
+
+/// Trennt `muster$optionen` - nur wenn hinter dem `$` wirklich Optionen stehen
+fn split_options(line: &str) -> (&str, Option<&str>) {
+    match line.rfind('$') {
+        Some(index) if index + 1 < line.len() => {
+            let options = &line[index + 1..];
+            let looks_like_options = options.chars().all(|c| {
+                c.is_ascii_alphanumeric()
+                    || matches!(c, '~' | ',' | '=' | '|' | '.' | '-' | '_' | '*')
+            });
+            if looks_like_options {
+                (&line[..index], Some(options))
+            } else {
+                (line, None)
+            }
+        }
+        _ => (line, None),
+    }
+}
+
+/// `None` wenn eine Option nicht unterstützt wird
+fn apply_options(rule: &mut NetworkRule, options: &str) -> Option<()> {
+    let mut included = 0u16;
+    let mut excluded = 0u16;
+
+    for option in options.split(',') {
+        let (negated, option) = match option.strip_prefix('~') {
+            Some(rest) => (true, rest),
+            None => (false, option),
+        };
+        let (name, value) = match option.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (option, None),
+        };
+
+        match (name, value) {
+            ("third-party" | "3p", None) => {
+                rule.party = if negated { Party::First } else { Party::Third };
+            }
+            ("first-party" | "1p", None) => {
+                rule.party = if negated { Party::Third } else { Party::First };
+            }
+            ("domain" | "from", Some(domains)) if !negated => {
+                for domain in domains.split('|').filter(|d| !d.is_empty()) {
+                    match domain.strip_prefix('~') {
+                        Some(domain) => rule.exclude_domains.push(domain.to_ascii_lowercase()),
+                        None => rule.include_domains.push(domain.to_ascii_lowercase()),
+                    }
+                }
+            }
+            ("match-case", None) if !negated => rule.match_case = true,
+            ("important", None) if !negated => rule.important = true,
+            ("all", None) if !negated => included = u16::MAX,
+            (name, None) => {
+                let bit = ResourceType::from_option(name)?.bit();
+                if negated {
+                    excluded |= bit;
+                } else {
+                    included |= bit;
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    if included != 0 || excluded != 0 {
+        let base = if included != 0 {
+            included
+        } else {
+            DEFAULT_TYPES
+        };
+        rule.types = base & !excluded;
+    }
+    Some(())
+}
+
+// ============================================================================
+// MATCHING
+// ============================================================================
+
+/// Zeichen, auf die `^` passt (alles außer Buchstaben, Ziffern und `_-.%`)
+fn is_separator(byte: u8) -> bool {
+    !(byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'-' | b'.' | b'%'))
+}
+
+/// Muster mit `*` und `^` gegen den ganzen Text (Backtracking nur am letzten `*`)
+fn wildcard_match(pattern: &[u8], text: &[u8]) -> bool {
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    loop {
+        if p < pattern.len() {
+            match pattern[p] {
+                b'*' => {
+                    star = Some((p, t));
+                    p += 1;
+                    continue;
+                }
+                // `^` passt auch auf das Ende der URL
+                b'^' if t == text.len() => {
+                    p += 1;
+                    continue;
+                }
+                b'^' if is_separator(text[t]) => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                c if t < text.len() && c == text[t] => {
+                    p += 1;
+                    t += 1;
+                    continue;
+                }
+                _ => {}
+            }
+        } else if t == text.len() {
+            return true;
+        }
+
+        match star {
+            Some((star_p, star_t)) if star_t < text.len() => {
+                star = Some((star_p, star_t + 1));
+                p = star_p + 1;
+                t = star_t + 1;
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// Public Suffix List von Mozilla (publicsuffix.org), beim ersten Zugriff geparst
+fn public_suffixes() -> &'static publicsuffix::List {
+    static LIST: OnceLock<publicsuffix::List> = OnceLock::new();
+    LIST.get_or_init(|| {
+        include_str!("../resources/public_suffix_list.dat")
+            .parse()
+            .expect("embedded public suffix list is valid")
+    })
+}
+
+/// Registrierbare Domain laut Public Suffix List (`example.co.uk`,
+/// `user.github.io`); IPs und Suffixe selbst bleiben unverändert
+fn base_domain(host: &str) -> &str {
+    if host.parse::<std::net::IpAddr>().is_ok() {
+        return host;
+    }
+    match public_suffixes().domain(host.as_bytes()) {
+        Some(domain) => &host[host.len() - domain.as_bytes().len()..],
+        None => host,
+    }
+}
+
+/// Ein zu prüfender Request, einmal vorbereitet für alle Regeln
+pub struct Request {
+    url: String,
+    url_lower: String,
+    host: String,
+    /// Byte-Offset des Hosts in der URL
+    host_start: usize,
+    document_host: Option<String>,
+    resource_type: ResourceType,
+    tokens: Vec<String>,
+}
+
+impl Request {
+    /// `None` für URLs, die nicht gefiltert werden (kein http/ws, kein Host)
+    pub fn new(url: &str, document_url: Option<&str>, resource_type: ResourceType) -> Option<Self> {
+        let parsed: Url = url.parse().ok()?;
+        if !matches!(parsed.scheme(), "http" | "https" | "ws" | "wss") {
+            return None;
+        }
+        let host = parsed.host_str()?.to_ascii_lowercase();
+        let url = parsed.to_string();
+        let url_lower = url.to_ascii_lowercase();
+        let scheme_end = url_lower.find("://")? + 3;
+        let host_start = scheme_end + url_lower[scheme_end..].find(host.as_str())?;
+
+        let document_host = document_url
+            .and_then(|doc| doc.parse::<Url>().ok())
+            .and_then(|doc| doc.host_str().map(str::to_ascii_lowercase));
+
+        let mut tokens: Vec<String> = url_lower
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|token| token.len() >= 2)
+            .map(str::to_string)
+            .collect();
+        tokens.sort_unstable();
+        tokens.dedup();
+
+        Some(Self {
+            url,
+            url_lower,
+            host,
+            host_start,
+            document_host,
+            resource_type,
+            tokens,
+        })
+    }
+
+    fn is_third_party(&self) -> Option<bool> {
+        let document_host = self.document_host.as_deref()?;
+        Some(base_domain(&self.host) != base_domain(document_host))
+    }
+}
+
+impl NetworkRule {
+    fn matches(&self, request: &Request) -> bool {
+        if self.types & request.resource_type.bit() == 0 {
+            return false;
+        }
+
+        match (self.party, request.is_third_party()) {
+            (Party::Any, _) => {}
+            (Party::Third, Some(true)) | (Party::First, Some(false) | None) => {}
+            _ => return false,
+        }
+
+        let context_host = request.document_host.as_deref().unwrap_or(&request.host);
+        if !self.include_domains.is_empty()
+            && !self
+                .include_domains
+                .iter()
+                .any(|d| host_matches(d, context_host))
+        {
+            return false;
+        }
+        if self
+            .exclude_domains
+            .iter()
+            .any(|d| host_matches(d, context_host))
+        {
+            return false;
+        }
+
+        let text = if self.match_case {
+            &request.url
+        } else {
+            &request.url_lower
+        };
+        self.matches_pattern(text.as_bytes(), request)
+    }
+
+    fn matches_pattern(&self, text: &[u8], request: &Request) -> bool {
+        let pattern = self.pattern.as_bytes();
+        match self.anchor {
+            Anchor::Host => {
+                // Am Anfang des Hosts oder nach jedem Punkt im Host
+                let host = request.host.as_bytes();
+                std::iter::once(0)
+                    .chain(
+                        host.iter()
+                            .enumerate()
+                            .filter(|(_, &b)| b == b'.')
+                            .map(|(i, _)| i + 1),
+                    )
+                    .any(|offset| wildcard_match(pattern, &text[request.host_start + offset..]))
+            }
+            _ => wildcard_match(pattern, text),
+        }
+    }
+
+    /// Wort aus dem Muster, das vollständig in jeder passenden URL vorkommt.
+    /// Die `*` aus `compile_pattern` begrenzen Wörter am Rand wie fehlende Anker.
+    fn index_token(&self) -> Option<String> {
+        let pattern = self.pattern.to_ascii_lowercase();
+        let bytes = pattern.as_bytes();
+        let mut best: Option<&str> = None;
+        let mut start = 0;
+
+        while start < bytes.len() {
+            if !bytes[start].is_ascii_alphanumeric() {
+                start += 1;
+                continue;
+            }
+            let mut end = start;
+            while end < bytes.len() && bytes[end].is_ascii_alphanumeric() {
+                end += 1;
+            }
+
+            let left_bounded = if start == 0 {
+                self.anchor != Anchor::None
+            } else {
+                bytes[start - 1] != b'*'
+            };
+            let right_bounded = if end == bytes.len() {
+                self.end_anchor
+            } else {
+                bytes[end] != b'*'
+            };
+            let token = &pattern[start..end];
+            let common = matches!(token, "http" | "https" | "www" | "com");
+            if left_bounded
+                && right_bounded
+                && token.len() >= 2
+                && !common
+                && best.is_none_or(|b| token.len() > b.len())
+            {
+                best = Some(token);
+            }
+            start = end;
+        }
+
+        best.map(str::to_string)
+    }
+}
+
+/// Regeln mit Token-Index - pro Request werden nur Kandidaten geprüft
+#[derive(Default)]
+struct RuleSet {
+    rules: Vec<NetworkRule>,
+    by_token: HashMap<String, Vec<usize>>,
+    /// Regeln ohne verwertbares Token (z.B. nur Platzhalter)
+    generic: Vec<usize>,
+}
+
+impl RuleSet {
+    fn add(&mut self, rule: NetworkRule) {
+        let index = self.rules.len();
+        match rule.index_token() {
+            Some(token) => self.by_token.entry(token).or_default().push(index),
+            None => self.generic.push(index),
+        }
+        self.rules.push(rule);
+    }
+
+    fn matches(&self, request: &Request) -> bool {
+        let candidates = request
+            .tokens
+            .iter()
+            .filter_map(|token| self.by_token.get(token))
+            .flatten()
+            .chain(&self.generic);
+        candidates
+            .into_iter()
+            .any(|&index| self.rules[index].matches(request))
+    }
+}
+
+/// Anzahl Regeln pro Filterliste
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+pub struct FilterListInfo {
+    pub name: String,
+    pub rules: usize,
+    /// Nicht unterstützte Zeilen (Regex, unbekannte Optionen)
+    pub skipped: usize,
+}
+
+/// Kompilierte Filterlisten
+#[derive(Default)]
+pub struct FilterEngine {
+    blocking: RuleSet,
+    exceptions: RuleSet,
+    /// `$important` - gewinnt auch gegen Ausnahmen
+    important: RuleSet,
+    lists: Vec<FilterListInfo>,
+}
+
+impl FilterEngine {
+    pub fn add_list(&mut self, name: &str, text: &str) -> FilterListInfo {
+        let mut info = FilterListInfo {
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        for line in text.lines() {
+            match parse_line(line) {
+                ParsedLine::Block(rule) if rule.important => self.important.add(rule),
+                ParsedLine::Block(rule) => self.blocking.add(rule),
+                ParsedLine::Allow(rule) => self.exceptions.add(rule),
+                ParsedLine::Ignored => continue,
+                ParsedLine::Unsupported => {
+                    info.skipped += 1;
+                    continue;
+                }
+            }
+            info.rules += 1;
+        }
+
+        self.lists.push(info.clone());
+        info
+    }
+
+    pub fn lists(&self) -> &[FilterListInfo] {
+        &self.lists
+    }
+
+    pub fn rule_count(&self) -> usize {
+        self.lists.iter().map(|list| list.rules).sum()
+    }
+
+    pub fn should_block(&self, request: &Request) -> bool {
+        if self.important.matches(request) {
+            return true;
+        }
+        self.blocking.matches(request) && !self.exceptions.matches(request)
+    }
+}
+
+// ============================================================================
+// STATE
+// ============================================================================
+
+/// Aktive Filter, pro Tab abschaltbar, mit Zähler blockierter Requests
+#[derive(Default)]
+pub struct ContentBlockingState {
+    engine: Mutex<Arc<FilterEngine>>,
+    disabled_tabs: Mutex<HashSet<String>>,
+    /// Native Tabs ohne Request-Hook - dort greift kein Filter
+    unfiltered_tabs: Mutex<HashSet<String>>,
+    blocked: Mutex<HashMap<String, u64>>,
+}
+
+impl ContentBlockingState {
+    /// Soll der Request blockiert werden? Zählt blockierte Requests pro Tab.
+    pub fn check(
+        &self,
+        tab_id: &str,
+        url: &str,
+        document_url: Option<&str>,
+        resource_type: ResourceType,
+    ) -> bool {
+        if self.disabled_tabs.lock_or_recover().contains(tab_id) {
+            return false;
+        }
+
+        // Engine aus dem Lock holen - Matching läuft ohne Lock
+        let engine = self.engine.lock_or_recover().clone();
+        let blocked = Request::new(url, document_url, resource_type)
+            .is_some_and(|request| engine.should_block(&request));
+        if blocked {
+            *self
+                .blocked
+                .lock_or_recover()
+                .entry(tab_id.to_string())
+                .or_default() += 1;
+        }
+        blocked
+    }
+
+    pub fn replace_engine(&self, engine: FilterEngine) {
+        *self.engine.lock_or_recover() = Arc::new(engine);
+    }
+
+    pub fn set_enabled(&self, tab_id: &str, enabled: bool) {
+        let mut disabled = self.disabled_tabs.lock_or_recover();
+        if enabled {
+            disabled.remove(tab_id);
+        } else {
+            disabled.insert(tab_id.to_string());
+        }
+    }
+
+    /// Tab läuft auf einer Plattform ohne Request-Hook
+    pub fn mark_unfiltered(&self, tab_id: &str) {
+        self.unfiltered_tabs.lock_or_recover().insert(tab_id.to_string());
+    }
+
+    pub fn stats(&self, tab_id: &str) -> ContentBlockingStats {
+        let supported = !self.unfiltered_tabs.lock_or_recover().contains(tab_id);
+        ContentBlockingStats {
+            tab_id: tab_id.to_string(),
+            supported,
+            enabled: supported && !self.disabled_tabs.lock_or_recover().contains(tab_id),
+            blocked_requests: self
+                .blocked
+                .lock_or_recover()
+                .get(tab_id)
+                .copied()
+                .unwrap_or(0),
+            rules: self.engine.lock_or_recover().rule_count(),
+        }
+    }
+
+    /// Tab geschlossen - Schalter und Zähler vergessen
+    pub fn forget(&self, tab_id: &str) {
+        self.disabled_tabs.lock_or_recover().remove(tab_id);
+        self.unfiltered_tabs.lock_or_recover().remove(tab_id);
+        self.blocked.lock_or_recover().remove(tab_id);
+    }
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq)]
+pub struct ContentBlockingStats {
+    pub tab_id: String,
+    /// `false`: der Tab kann nicht gefiltert werden (WebKitGTK, WKWebView)
+    pub supported: bool,
+    pub enabled: bool,
+    pub blocked_requests: u64,
+    /// Regeln aller geladenen Listen
+    pub rules: usize,
+}
+
+fn filters_dir(app: &AppHandle) -> Result<PathBuf, WorkspaceError> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(WorkspaceError::io("resolve_app_data_dir"))?
+        .join("filters"))
+}
+
+/// Kompiliert alle `*.txt` im Verzeichnis (alphabetisch, fehlendes Verzeichnis = leer)
+pub fn load_filter_lists(dir: &Path) -> Result<FilterEngine, WorkspaceError> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(FilterEngine::default()),
+        Err(e) => return Err(WorkspaceError::io("read_filter_dir")(e)),
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .collect();
+    paths.sort();
+
+    let mut engine = FilterEngine::default();
+    for path in paths {
+        let text = std::fs::read(&path).map_err(WorkspaceError::io("read_filter_list"))?;
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        engine.add_list(name, &String::from_utf8_lossy(&text));
+    }
+    Ok(engine)
+}
+
+/// Lädt die Filterlisten beim Start, ohne den Main-Thread aufzuhalten
+pub fn load_in_background(app: &AppHandle) {
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let engine = filters_dir(&app).and_then(|dir| load_filter_lists(&dir));
+        match engine {
+            Ok(engine) => app.state::<ContentBlockingState>().replace_engine(engine),
+            Err(e) => log::error!("Failed to load content filters: {}", e),
+        }
+    });
+}
+
+// ============================================================================
+// ENFORCEMENT - CEF und native Tabs
+// ============================================================================
+
+/// Request-Filter für die OSR-Browser des cef-plugins (Browser-ID = Tab-ID)
+#[cfg(feature = "cef")]
+pub struct CefRequestFilter {
+    app: AppHandle,
+}
+
+#[cfg(feature = "cef")]
+impl CefRequestFilter {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+#[cfg(feature = "cef")]
+impl cef_plugin::RequestFilter for CefRequestFilter {
+    fn should_block(&self, request: &cef_plugin::ResourceRequest<'_>) -> bool {
+        use cef_plugin::ResourceType as Cef;
+
+        let resource_type = match request.resource_type {
+            Cef::MainFrame => ResourceType::Document,
+            Cef::SubFrame => ResourceType::Subdocument,
+            Cef::Stylesheet => ResourceType::Stylesheet,
+            Cef::Script => ResourceType::Script,
+            Cef::Image => ResourceType::Image,
+            Cef::Font => ResourceType::Font,
+            Cef::Media => ResourceType::Media,
+            Cef::Object => ResourceType::Object,
+            Cef::Xhr => ResourceType::Xhr,
+            Cef::Ping => ResourceType::Ping,
+            Cef::Other => ResourceType::Other,
+        };
+        self.app.state::<ContentBlockingState>().check(
+            request.browser_id,
+            request.url,
+            request.frame_url,
+            resource_type,
+        )
+    }
+}
+
+/// Hängt den Filter an einen nativen Tab (WebView2 `WebResourceRequested`)
+#[cfg(windows)]
+pub fn install(app: &AppHandle, webview: &tauri::WebviewWindow) -> Result<(), WorkspaceError> {
+    use webview2_com::Microsoft::Web::WebView2::Win32::*;
+    use webview2_com::{
+        take_pwstr, NavigationStartingEventHandler, WebResourceRequestedEventHandler,
+    };
+    use windows::core::{HSTRING, PWSTR};
+
+    /// Request- und Navigations-URIs unterscheiden sich höchstens im Fragment
+    fn without_fragment(uri: &str) -> &str {
+        uri.split('#').next().unwrap_or(uri)
+    }
+
+    let app = app.clone();
+    let tab_id = webview.label().to_string();
+    // WebView2 unterscheidet Seite und iframe beim Request nicht: die Seite ist
+    // das Dokument, dessen Navigation zuletzt startete (auch nach Redirects).
+    // Die erste Navigation läuft evtl. schon, bevor der Handler hängt.
+    let main_frame = Arc::new(Mutex::new(webview.url().ok().map(|url| url.to_string())));
+    webview
+        .with_webview(move |platform| unsafe {
+            let environment = platform.environment();
+            let Ok(core) = platform.controller().CoreWebView2() else {
+                return;
+            };
+
+            let navigation_target = main_frame.clone();
+            let navigation_handler =
+                NavigationStartingEventHandler::create(Box::new(move |_, args| {
+                    if let Some(args) = args {
+                        let mut uri = PWSTR::null();
+                        args.Uri(&mut uri)?;
+                        *navigation_target.lock_or_recover() = Some(take_pwstr(uri));
+                    }
+                    Ok(())
+                }));
+
+            let handler =
+                WebResourceRequestedEventHandler::create(Box::new(move |sender, args| {
+                    let (Some(sender), Some(args)) = (sender, args) else {
+                        return Ok(());
+                    };
+                    let uri = {
+                        let mut uri = PWSTR::null();
+                        args.Request()?.Uri(&mut uri)?;
+                        take_pwstr(uri)
+                    };
+                    let source = {
+                        let mut source = PWSTR::null();
+                        sender.Source(&mut source)?;
+                        take_pwstr(source)
+                    };
+                    let mut context = COREWEBVIEW2_WEB_RESOURCE_CONTEXT::default();
+                    args.ResourceContext(&mut context)?;
+
+                    let is_main_frame = || {
+                        main_frame
+                            .lock_or_recover()
+                            .as_deref()
+                            .is_some_and(|target| without_fragment(target) == without_fragment(&uri))
+                    };
+                    let resource_type = match context {
+                        COREWEBVIEW2_WEB_RESOURCE_CONTEXT_DOCUMENT if is_main_frame() => {
+                            ResourceType::Document
+                        }
+                        COREWEBVIEW2_WEB_RESOURCE_CONTEXT_DOCUMENT => ResourceType::Subdocument,
+                        COREWEBVIEW2_WEB_RESOURCE_CONTEXT_STYLESHEET => ResourceType::Stylesheet,
+                        COREWEBVIEW2_WEB_RESOURCE_CONTEXT_IMAGE => ResourceType::Image,
+                        COREWEBVIEW2_WEB_RESOURCE_CONTEXT_MEDIA => ResourceType::Media,
+                        COREWEBVIEW2_WEB_RESOURCE_CONTEXT_FONT => ResourceType::Font,
+                        COREWEBVIEW2_WEB_RESOURCE_CONTEXT_SCRIPT => ResourceType::Script,
+                        COREWEBVIEW2_WEB_RESOURCE_CONTEXT_XML_HTTP_REQUEST
+                        | COREWEBVIEW2_WEB_RESOURCE_CONTEXT_FETCH => ResourceType::Xhr,
+                        COREWEBVIEW2_WEB_RESOURCE_CONTEXT_WEBSOCKET => ResourceType::WebSocket,
+                        COREWEBVIEW2_WEB_RESOURCE_CONTEXT_PING => ResourceType::Ping,
+                        _ => ResourceType::Other,
+                    };
+
+                    let state = app.state::<ContentBlockingState>();
+                    if state.check(&tab_id, &uri, Some(&source), resource_type) {
+                        let response = environment.CreateWebResourceResponse(
+                            None,
+                            403,
+                            &HSTRING::from("Blocked"),
+                            &HSTRING::new(),
+                        )?;
+                        args.SetResponse(&response)?;
+                    }
+                    Ok(())
+                }));
+
+            let result = core
+                .AddWebResourceRequestedFilter(
+                    &HSTRING::from("*"),
+                    COREWEBVIEW2_WEB_RESOURCE_CONTEXT_ALL,
+                )
+                .and_then(|_| {
+                    let mut token = 0i64;
+                    core.add_WebResourceRequested(&handler, &mut token)
+                })
+                .and_then(|_| {
+                    let mut token = 0i64;
+                    core.add_NavigationStarting(&navigation_handler, &mut token)
+                });
+            if let Err(e) = result {
+                log::error!("Failed to install content blocking: {}", e);
+            }
+        })
+        .map_err(WorkspaceError::window_op("with_webview"))
+}
+
+/// Ohne Request-Hook (WebKitGTK, WKWebView) bleibt der Tab ungefiltert -
+/// die Stats melden das, damit die Karte es anzeigen kann
+#[cfg(not(windows))]
+pub fn install(app: &AppHandle, webview: &tauri::WebviewWindow) -> Result<(), WorkspaceError> {
+    app.state::<ContentBlockingState>().mark_unfiltered(webview.label());
+    Ok(())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Liest die Filterlisten neu ein und liefert die geladenen Listen
+#[tauri::command]
+pub async fn reload_content_filters(
+    app: AppHandle,
+    state: tauri::State<'_, ContentBlockingState>,
+) -> Result<Vec<FilterListInfo>, WorkspaceError> {
+    let engine = load_filter_lists(&filters_dir(&app)?)?;
+    let lists = engine.lists().to_vec();
+    state.replace_engine(engine);
+    Ok(lists)
+}
+
+/// Schaltet das Blockieren für einen Tab an oder aus
+#[tauri::command]
+pub fn set_web_tab_content_blocking(
+    state: tauri::State<'_, ContentBlockingState>,
+    tab_id: String,
+    enabled: bool,
+) {
+    state.set_enabled(&tab_id, enabled);
+}
+
+/// Schalter und Anzahl blockierter Requests eines Tabs
+#[tauri::command]
+pub fn get_web_tab_content_blocking(
+    state: tauri::State<'_, ContentBlockingState>,
+    tab_id: String,
+) -> ContentBlockingStats {
+    state.stats(&tab_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "https://www.notion.so/workspace";
+
+    fn engine(list: &str) -> FilterEngine {
+        let mut engine = FilterEngine::default();
+        engine.add_list("test", list);
+        engine
+    }
+
+    fn blocks(engine: &FilterEngine, url: &str, resource_type: ResourceType) -> bool {
+        let request = Request::new(url, Some(PAGE), resource_type).unwrap();
+        engine.should_block(&request)
+    }
+
+    #[test]
+    fn test_parse_skips_comments_cosmetics_and_unsupported() {
+        let engine = engine(
+            "[Adblock Plus 2.0]\n\
+             ! Title: Test\n\
+             \n\
+             example.com##.ad-banner\n\
+             ||ads.example^\n\
+             /banner\\d+/\n\
+             ||tracker.example^$redirect=noop.js\n\
+             *$script\n\
+             0.0.0.0 metrics.example\n\
+             127.0.0.1 localhost\n",
+        );
+        let list = &engine.lists()[0];
+        assert_eq!(list.rules, 2);
+        assert_eq!(list.skipped, 3);
+    }
+
+    #[test]
+    fn test_host_anchor_and_separator() {
+        let engine = engine("||doubleclick.net^\n0.0.0.0 metrics.example\n");
+        assert!(blocks(
+            &engine,
+            "https://doubleclick.net/ad",
+            ResourceType::Script
+        ));
+        assert!(blocks(
+            &engine,
+            "https://stats.g.doubleclick.net/x?y=1",
+            ResourceType::Image
+        ));
+        assert!(blocks(
+            &engine,
+            "https://metrics.example/collect",
+            ResourceType::Xhr
+        ));
+        assert!(!blocks(
+            &engine,
+            "https://notdoubleclick.net/",
+            ResourceType::Script
+        ));
+        assert!(!blocks(
+            &engine,
+            "https://doubleclick.net.example/",
+            ResourceType::Script
+        ));
+        // Die Seite selbst nur mit $document
+        assert!(!blocks(
+            &engine,
+            "https://doubleclick.net/",
+            ResourceType::Document
+        ));
+    }
+
+    #[test]
+    fn test_wildcards_and_anchors() {
+        let engine = engine("/ads/*/banner.\n|https://cdn.example/track.js|\n");
+        assert!(blocks(
+            &engine,
+            "https://site.example/ads/2024/banner.png",
+            ResourceType::Image
+        ));
+        assert!(!blocks(
+            &engine,
+            "https://site.example/ads/banner.png",
+            ResourceType::Image
+        ));
+        assert!(blocks(
+            &engine,
+            "https://cdn.example/track.js",
+            ResourceType::Script
+        ));
+        assert!(!blocks(
+            &engine,
+            "https://cdn.example/track.js?v=2",
+            ResourceType::Script
+        ));
+
+        assert!(wildcard_match(
+            b"*/pixel^*",
+            b"https://a.example/pixel?id=1"
+        ));
+        assert!(wildcard_match(b"*/pixel^*", b"https://a.example/pixel"));
+        assert!(!wildcard_match(b"*/pixel^*", b"https://a.example/pixels"));
+    }
+
+    #[test]
+    fn test_options_types_party_and_domains() {
+        let engine = engine(
+            "||cdn.example^$script,third-party\n\
+             ||widgets.example^$~image\n\
+             ||chat.example^$domain=notion.so|~docs.notion.so\n",
+        );
+        assert!(blocks(
+            &engine,
+            "https://cdn.example/a.js",
+            ResourceType::Script
+        ));
+        assert!(!blocks(
+            &engine,
+            "https://cdn.example/a.png",
+            ResourceType::Image
+        ));
+        let first_party = Request::new(
+            "https://cdn.example/a.js",
+            Some("https://cdn.example/"),
+            ResourceType::Script,
+        );
+        assert!(!engine.should_block(&first_party.unwrap()));
+
+        assert!(blocks(
+            &engine,
+            "https://widgets.example/w.js",
+            ResourceType::Script
+        ));
+        assert!(!blocks(
+            &engine,
+            "https://widgets.example/w.png",
+            ResourceType::Image
+        ));
+
+        assert!(blocks(
+            &engine,
+            "https://chat.example/embed",
+            ResourceType::Subdocument
+        ));
+        let excluded = Request::new(
+            "https://chat.example/embed",
+            Some("https://docs.notion.so/"),
+            ResourceType::Subdocument,
+        );
+        assert!(!engine.should_block(&excluded.unwrap()));
+    }
+
+    #[test]
+    fn test_exceptions_and_important() {
+        let engine = engine(
+            "||tracker.example^\n\
+             @@||tracker.example/consent.js\n\
+             ||ads.example^$important\n\
+             @@||ads.example^\n\
+             /Banner.$match-case\n",
+        );
+        assert!(blocks(
+            &engine,
+            "https://tracker.example/t.js",
+            ResourceType::Script
+        ));
+        assert!(!blocks(
+            &engine,
+            "https://tracker.example/consent.js",
+            ResourceType::Script
+        ));
+        assert!(blocks(
+            &engine,
+            "https://ads.example/a.js",
+            ResourceType::Script
+        ));
+        assert!(blocks(
+            &engine,
+            "https://site.example/Banner.png",
+            ResourceType::Image
+        ));
+        assert!(!blocks(
+            &engine,
+            "https://site.example/banner.png",
+            ResourceType::Image
+        ));
+    }
+
+    #[test]
+    fn test_token_index_only_uses_complete_words() {
+        let rule = |line: &str| match parse_line(line) {
+            ParsedLine::Block(rule) => rule,
+            other => panic!("{:?}", other),
+        };
+        assert_eq!(
+            rule("||doubleclick.net^").index_token().as_deref(),
+            Some("doubleclick")
+        );
+        assert_eq!(rule("ad*banner").index_token(), None);
+        assert_eq!(rule("/adframe/*").index_token().as_deref(), Some("adframe"));
+    }
+
+    #[test]
+    fn test_base_domain_uses_public_suffixes() {
+        assert_eq!(base_domain("a.b.example.com"), "example.com");
+        assert_eq!(base_domain("www.bbc.co.uk"), "bbc.co.uk");
+        assert_eq!(base_domain("alice.github.io"), "alice.github.io");
+        assert_eq!(base_domain("co.uk"), "co.uk");
+        assert_eq!(base_domain("10.0.0.1"), "10.0.0.1");
+
+        // Zwei Sites unter demselben Suffix sind füreinander third-party
+        let engine = engine("||cdn.example.co.uk^$third-party\n");
+        let request = |page: &str| {
+            Request::new("https://cdn.example.co.uk/lib.js", Some(page), ResourceType::Script).unwrap()
+        };
+        assert!(engine.should_block(&request("https://shop.co.uk/")));
+        assert!(!engine.should_block(&request("https://www.example.co.uk/")));
+    }
+
+    #[test]
+    fn test_state_counts_and_per_tab_switch() {
+        let state = ContentBlockingState::default();
+        state.replace_engine(engine("||tracker.example^\n"));
+        let tracker = "https://tracker.example/p.gif";
+
+        assert!(state.check("webtab-a", tracker, Some(PAGE), ResourceType::Image));
+        assert!(state.check("webtab-a", tracker, Some(PAGE), ResourceType::Image));
+        assert!(!state.check(
+            "webtab-a",
+            "https://cdn.example/app.js",
+            Some(PAGE),
+            ResourceType::Script
+        ));
+
+        state.set_enabled("webtab-b", false);
+        assert!(!state.check("webtab-b", tracker, Some(PAGE), ResourceType::Image));
+
+        let stats = state.stats("webtab-a");
+        assert_eq!(
+            (stats.enabled, stats.blocked_requests, stats.rules),
+            (true, 2, 1)
+        );
+        assert_eq!(state.stats("webtab-b").blocked_requests, 0);
+        assert!(!state.stats("webtab-b").enabled);
+
+        state.forget("webtab-a");
+        assert_eq!(state.stats("webtab-a").blocked_requests, 0);
+    }
+
+    #[test]
+    fn test_unfiltered_tabs_report_unsupported() {
+        let state = ContentBlockingState::default();
+        assert!(state.stats("webtab-a").supported);
+
+        state.mark_unfiltered("webtab-a");
+        let stats = state.stats("webtab-a");
+        assert_eq!((stats.supported, stats.enabled), (false, false));
+
+        // Ersetztes Fenster startet ohne Markierung
+        state.forget("webtab-a");
+        assert!(state.stats("webtab-a").enabled);
+    }
+
+    #[test]
+    fn test_load_filter_lists_from_directory() {
+        let dir = std::env::temp_dir().join(format!("filters-{}", uuid::Uuid::new_v4()));
+        assert_eq!(load_filter_lists(&dir).unwrap().rule_count(), 0);
+
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("easylist.txt"),
+            "||ads.example^\n||tracker.example^\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("custom.txt"), "||metrics.example^\n").unwrap();
+        std::fs::write(dir.join("notes.md"), "||ignored.example^\n").unwrap();
+
+        let engine = load_filter_lists(&dir).unwrap();
+        let names: Vec<&str> = engine.lists().iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(names, ["custom", "easylist"]);
+        assert_eq!(engine.rule_count(), 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
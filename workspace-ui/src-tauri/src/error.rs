@@ -0,0 +1,258 @@
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::fmt;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+// ============================================================================
+// ERRORS - Einheitliche Fehler für Web-Tab- und CEF-Commands
+// ============================================================================
+//
+// Das Frontend bekommt `{ code, message, context }`. `code` ist stabil und
+// zum Verzweigen gedacht, `message` nur zum Anzeigen/Loggen.
+
+/// Stabiler Fehlercode für das Frontend
+#[derive(Clone, Copy, Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    NotFound,
+    InvalidUrl,
+    InvalidInput,
+    WindowOp,
+    NotInitialized,
+    AlreadyExists,
+    Conflict,
+    Unsupported,
+    Timeout,
+    Io,
+    PolicyViolation,
+    Internal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WorkspaceError {
+    /// Tab, Fenster, Profil, Browser ... existiert nicht (`kind` z.B. "web_tab")
+    NotFound { kind: &'static str, id: String },
+    InvalidUrl { url: String, reason: String },
+    /// Ungültige Argumente (Namen, IDs, Skalierung ...)
+    InvalidInput(String),
+    /// Fenster-Operation fehlgeschlagen (`op` z.B. "set_position")
+    WindowOp { op: &'static str, reason: String },
+    /// Subsystem noch nicht bereit (z.B. "cef")
+    NotInitialized(&'static str),
+    AlreadyExists { kind: &'static str, id: String },
+    /// Aktion passt nicht zum aktuellen Zustand (z.B. Profil noch in Benutzung)
+    Conflict(String),
+    /// In diesem Build oder auf dieser Plattform nicht verfügbar
+    Unsupported(String),
+    Timeout { op: &'static str, id: String },
+    /// Lesen/Schreiben auf der Platte (`op` z.B. "read_snapshot")
+    Io { op: &'static str, reason: String },
+    /// Von der URL-Policy blockiert (`rule` z.B. "deny", "scheme")
+    PolicyViolation { url: String, rule: &'static str },
+    Internal(String),
+}
+
+impl WorkspaceError {
+    pub fn tab_not_found(tab_id: &str) -> Self {
+        WorkspaceError::NotFound {
+            kind: "web_tab",
+            id: tab_id.to_string(),
+        }
+    }
+
+    pub fn main_window_not_found() -> Self {
+        WorkspaceError::NotFound {
+            kind: "window",
+            id: "main".to_string(),
+        }
+    }
+
+    /// Für `map_err`: `.map_err(WorkspaceError::window_op("set_focus"))?`
+    pub fn window_op<E: fmt::Display>(op: &'static str) -> impl FnOnce(E) -> Self {
+        move |e| WorkspaceError::WindowOp {
+            op,
+            reason: e.to_string(),
+        }
+    }
+
+    /// Für `map_err` beim Parsen einer URL
+    pub fn invalid_url<E: fmt::Display>(url: &str) -> impl FnOnce(E) -> Self + '_ {
+        move |e| WorkspaceError::InvalidUrl {
+            url: url.to_string(),
+            reason: e.to_string(),
+        }
+    }
+
+    /// Für `map_err` bei Dateizugriffen
+    pub fn io<E: fmt::Display>(op: &'static str) -> impl FnOnce(E) -> Self {
+        move |e| WorkspaceError::Io {
+            op,
+            reason: e.to_string(),
+        }
+    }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            WorkspaceError::NotFound { .. } => ErrorCode::NotFound,
+            WorkspaceError::InvalidUrl { .. } => ErrorCode::InvalidUrl,
+            WorkspaceError::InvalidInput(_) => ErrorCode::InvalidInput,
+            WorkspaceError::WindowOp { .. } => ErrorCode::WindowOp,
+            WorkspaceError::NotInitialized(_) => ErrorCode::NotInitialized,
+            WorkspaceError::AlreadyExists { .. } => ErrorCode::AlreadyExists,
+            WorkspaceError::Conflict(_) => ErrorCode::Conflict,
+            WorkspaceError::Unsupported(_) => ErrorCode::Unsupported,
+            WorkspaceError::Timeout { .. } => ErrorCode::Timeout,
+            WorkspaceError::Io { .. } => ErrorCode::Io,
+            WorkspaceError::PolicyViolation { .. } => ErrorCode::PolicyViolation,
+            WorkspaceError::Internal(_) => ErrorCode::Internal,
+        }
+    }
+
+    /// Maschinenlesbare Details (welcher Tab, welche URL, welche Operation)
+    pub fn context(&self) -> serde_json::Value {
+        match self {
+            WorkspaceError::NotFound { kind, id } | WorkspaceError::AlreadyExists { kind, id } => {
+                serde_json::json!({ "kind": kind, "id": id })
+            }
+            WorkspaceError::InvalidUrl { url, .. } => serde_json::json!({ "url": url }),
+            WorkspaceError::WindowOp { op, .. } | WorkspaceError::Io { op, .. } => {
+                serde_json::json!({ "op": op })
+            }
+            WorkspaceError::NotInitialized(subsystem) => serde_json::json!({ "subsystem": subsystem }),
+            WorkspaceError::Timeout { op, id } => serde_json::json!({ "op": op, "id": id }),
+            WorkspaceError::PolicyViolation { url, rule } => serde_json::json!({ "url": url, "rule": rule }),
+            _ => serde_json::Value::Null,
+        }
+    }
+}
+
+impl fmt::Display for WorkspaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorkspaceError::NotFound { kind, id } => write!(f, "{} not found: {}", kind.replace('_', " "), id),
+            WorkspaceError::InvalidUrl { url, reason } => write!(f, "Invalid URL {}: {}", url, reason),
+            WorkspaceError::WindowOp { op, reason } => write!(f, "Window operation {} failed: {}", op, reason),
+            WorkspaceError::NotInitialized(subsystem) => write!(f, "{} not initialized", subsystem),
+            WorkspaceError::AlreadyExists { kind, id } => {
+                write!(f, "{} already exists: {}", kind.replace('_', " "), id)
+            }
+            WorkspaceError::Timeout { op, id } => write!(f, "{} timed out for {}", op, id),
+            WorkspaceError::Io { op, reason } => write!(f, "{} failed: {}", op, reason),
+            WorkspaceError::PolicyViolation { url, rule } => {
+                write!(f, "URL {} blocked by policy ({})", url, rule)
+            }
+            WorkspaceError::InvalidInput(message)
+            | WorkspaceError::Conflict(message)
+            | WorkspaceError::Unsupported(message)
+            | WorkspaceError::Internal(message) => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for WorkspaceError {}
+
+#[cfg(feature = "cef")]
+impl From<cef_plugin::Error> for WorkspaceError {
+    fn from(error: cef_plugin::Error) -> Self {
+        match error {
+            cef_plugin::Error::NotInitialized => WorkspaceError::NotInitialized("cef"),
+            cef_plugin::Error::BrowserNotFound(id) => WorkspaceError::NotFound { kind: "browser", id },
+            cef_plugin::Error::BrowserExists(id) => WorkspaceError::AlreadyExists { kind: "browser", id },
+            cef_plugin::Error::SubscriptionNotFound(id) => WorkspaceError::NotFound {
+                kind: "subscription",
+                id: id.to_string(),
+            },
+            cef_plugin::Error::InvalidInput(message) => WorkspaceError::InvalidInput(message),
+            cef_plugin::Error::NavigationBlocked(url) => WorkspaceError::PolicyViolation {
+                url,
+                rule: "navigation_filter",
+            },
+            cef_plugin::Error::Backend(message) => WorkspaceError::Internal(message),
+        }
+    }
+}
+
+impl Serialize for WorkspaceError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("WorkspaceError", 3)?;
+        error.serialize_field("code", &self.code())?;
+        error.serialize_field("message", &self.to_string())?;
+        error.serialize_field("context", &self.context())?;
+        error.end()
+    }
+}
+
+// ============================================================================
+// LOCKING - Vergiftete Mutexe nicht weiter eskalieren
+// ============================================================================
+
+/// Ein Panic in einem Command darf nicht alle folgenden Commands mitreißen:
+/// der State bleibt benutzbar, auch wenn der Mutex vergiftet ist.
+pub trait LockExt<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T>;
+}
+
+impl<T> LockExt<T> for Mutex<T> {
+    fn lock_or_recover(&self) -> MutexGuard<'_, T> {
+        self.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_serializes_code_message_and_context() {
+        let value = serde_json::to_value(WorkspaceError::tab_not_found("webtab-1")).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "code": "not_found",
+                "message": "web tab not found: webtab-1",
+                "context": { "kind": "web_tab", "id": "webtab-1" },
+            })
+        );
+
+        let error = "not a url".parse::<tauri::Url>().map_err(WorkspaceError::invalid_url("not a url"));
+        let value = serde_json::to_value(error.unwrap_err()).unwrap();
+        assert_eq!(value["code"], "invalid_url");
+        assert_eq!(value["context"], serde_json::json!({ "url": "not a url" }));
+    }
+
+    #[test]
+    fn test_codes_are_stable() {
+        let cases = [
+            (WorkspaceError::main_window_not_found(), "not_found"),
+            (WorkspaceError::window_op("set_size")("gone"), "window_op"),
+            (WorkspaceError::NotInitialized("cef"), "not_initialized"),
+            (WorkspaceError::AlreadyExists { kind: "profile", id: "work".into() }, "already_exists"),
+            (WorkspaceError::io("read_snapshot")("denied"), "io"),
+            (
+                WorkspaceError::PolicyViolation { url: "file:///".into(), rule: "scheme" },
+                "policy_violation",
+            ),
+            (WorkspaceError::Internal("boom".to_string()), "internal"),
+        ];
+        for (error, code) in cases {
+            assert_eq!(serde_json::to_value(error.code()).unwrap(), code);
+        }
+    }
+
+    #[test]
+    fn test_lock_or_recover_survives_poisoning() {
+        let state = Arc::new(Mutex::new(1));
+        let poisoner = state.clone();
+        std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poison the mutex");
+        })
+        .join()
+        .unwrap_err();
+
+        assert!(state.is_poisoned());
+        *state.lock_or_recover() += 1;
+        assert_eq!(*state.lock_or_recover(), 2);
+    }
+}
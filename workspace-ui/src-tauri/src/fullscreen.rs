@@ -0,0 +1,391 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, PhysicalPosition, PhysicalSize, Runtime};
+
+use crate::canvas::CanvasLayoutState;
+use crate::error::{LockExt, WorkspaceError};
+use crate::{snapshot, TabBounds, WebTabState};
+
+// ============================================================================
+// FULLSCREEN - Ein Koordinatenmodell für Tabs im und außerhalb des Vollbilds
+// ============================================================================
+//
+// Alle `TabBounds` sind physische Pixel relativ zum Inhaltsbereich
+// (`inner_position`) des Hauptfensters - beim Erstellen, beim Layout und beim
+// Speichern vor dem Vollbild. Tab-Fenster haben keine Dekorationen, dort sind
+// innere und äußere Position gleich.
+
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum FullscreenMode {
+    /// Tab deckt die Canvas-Fläche im Hauptfenster ab
+    #[default]
+    FillCanvas,
+    /// Echtes Vollbild des Betriebssystems
+    Os,
+}
+
+/// Fenster-Geometrie - abstrahiert für Tests ohne echte Fenster
+pub trait WindowGeometry {
+    fn inner_position(&self) -> Result<PhysicalPosition<i32>, WorkspaceError>;
+    fn inner_size(&self) -> Result<PhysicalSize<u32>, WorkspaceError>;
+    fn set_position(&self, position: PhysicalPosition<i32>) -> Result<(), WorkspaceError>;
+    fn set_size(&self, size: PhysicalSize<u32>) -> Result<(), WorkspaceError>;
+    fn set_fullscreen(&self, fullscreen: bool) -> Result<(), WorkspaceError>;
+}
+
+impl<R: Runtime> WindowGeometry for tauri::WebviewWindow<R> {
+    fn inner_position(&self) -> Result<PhysicalPosition<i32>, WorkspaceError> {
+        tauri::WebviewWindow::inner_position(self).map_err(WorkspaceError::window_op("inner_position"))
+    }
+
+    fn inner_size(&self) -> Result<PhysicalSize<u32>, WorkspaceError> {
+        tauri::WebviewWindow::inner_size(self).map_err(WorkspaceError::window_op("inner_size"))
+    }
+
+    fn set_position(&self, position: PhysicalPosition<i32>) -> Result<(), WorkspaceError> {
+        tauri::WebviewWindow::set_position(self, position).map_err(WorkspaceError::window_op("set_position"))
+    }
+
+    fn set_size(&self, size: PhysicalSize<u32>) -> Result<(), WorkspaceError> {
+        tauri::WebviewWindow::set_size(self, size).map_err(WorkspaceError::window_op("set_size"))
+    }
+
+    fn set_fullscreen(&self, fullscreen: bool) -> Result<(), WorkspaceError> {
+        tauri::WebviewWindow::set_fullscreen(self, fullscreen)
+            .map_err(WorkspaceError::window_op("set_fullscreen"))
+    }
+}
+
+/// Bounds eines Fensters relativ zum Inhaltsbereich des Hauptfensters
+pub fn relative_bounds(main: &impl WindowGeometry, window: &impl WindowGeometry) -> Result<TabBounds, WorkspaceError> {
+    let main_pos = main.inner_position()?;
+    let pos = window.inner_position()?;
+    let size = window.inner_size()?;
+    Ok(TabBounds {
+        x: pos.x - main_pos.x,
+        y: pos.y - main_pos.y,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Setzt ein Fenster auf Bounds relativ zum Inhaltsbereich des Hauptfensters
+pub fn place(main: &impl WindowGeometry, window: &impl WindowGeometry, bounds: &TabBounds) -> Result<(), WorkspaceError> {
+    let main_pos = main.inner_position()?;
+    window.set_position(PhysicalPosition::new(main_pos.x + bounds.x, main_pos.y + bounds.y))?;
+    window.set_size(PhysicalSize::new(bounds.width, bounds.height))
+}
+
+/// Zielfläche für `FillCanvas`: die gemeldete Canvas-Fläche, sonst der ganze Inhaltsbereich
+pub fn fill_bounds(main: &impl WindowGeometry, canvas: Option<TabBounds>) -> Result<TabBounds, WorkspaceError> {
+    if let Some(canvas) = canvas {
+        return Ok(canvas);
+    }
+    let size = main.inner_size()?;
+    Ok(TabBounds {
+        x: 0,
+        y: 0,
+        width: size.width,
+        height: size.height,
+    })
+}
+
+/// Vollbild an: gibt die Bounds zurück, die beim Verlassen wiederhergestellt werden
+pub fn enter(
+    main: &impl WindowGeometry,
+    tab: &impl WindowGeometry,
+    mode: FullscreenMode,
+    fill: &TabBounds,
+) -> Result<TabBounds, WorkspaceError> {
+    let saved = relative_bounds(main, tab)?;
+    match mode {
+        FullscreenMode::FillCanvas => place(main, tab, fill)?,
+        FullscreenMode::Os => tab.set_fullscreen(true)?,
+    }
+    Ok(saved)
+}
+
+/// Vollbild aus: zurück auf die gespeicherten Bounds
+pub fn exit(
+    main: &impl WindowGeometry,
+    tab: &impl WindowGeometry,
+    mode: FullscreenMode,
+    saved: &TabBounds,
+) -> Result<(), WorkspaceError> {
+    if mode == FullscreenMode::Os {
+        tab.set_fullscreen(false)?;
+    }
+    place(main, tab, saved)
+}
+
+/// `fill_bounds` für das echte Hauptfenster inkl. gemeldeter Canvas-Fläche
+/// ohne Exclusion-Rects. Bleibt davon nichts frei, die ganze Canvas-Fläche -
+/// `layout_tabs` versteckt den Tab dann, bis die Fläche wieder frei ist.
+pub fn fill_bounds_for_app(app: &AppHandle, main: &tauri::WebviewWindow) -> Result<TabBounds, WorkspaceError> {
+    let scale_factor = main
+        .scale_factor()
+        .map_err(WorkspaceError::window_op("scale_factor"))?;
+    let canvas = {
+        let layout_state = app.state::<Mutex<CanvasLayoutState>>();
+        let layout = layout_state.lock_or_recover();
+        let free = layout.fill_area(scale_factor);
+        free.or_else(|| layout.canvas_area(scale_factor))
+    };
+    fill_bounds(main, canvas)
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Setzt Web-Tab in Fullscreen-Modus (Standard: Canvas-Fläche ausfüllen)
+#[tauri::command]
+pub async fn set_web_tab_fullscreen(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<WebTabState>>,
+    tab_id: String,
+    fullscreen: bool,
+    mode: Option<FullscreenMode>,
+) -> Result<(), WorkspaceError> {
+    let webview = app
+        .get_webview_window(&tab_id)
+        .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))?;
+
+    let main_window = app
+        .get_webview_window("main")
+        .ok_or_else(WorkspaceError::main_window_not_found)?;
+
+    let current_mode = {
+        let tab_state = state.lock_or_recover();
+        let tab = tab_state
+            .tabs
+            .get(&tab_id)
+            .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))?;
+        tab.is_fullscreen.then(|| tab.fullscreen_mode.unwrap_or_default())
+    };
+
+    let mode = if fullscreen {
+        let mode = mode.unwrap_or_default();
+        // Moduswechsel im Vollbild: erst sauber verlassen
+        if let Some(current) = current_mode.filter(|current| *current != mode) {
+            leave(&state, &main_window, &webview, &tab_id, current)?;
+        }
+        if current_mode != Some(mode) {
+            let fill = fill_bounds_for_app(&app, &main_window)?;
+            let saved = enter(&main_window, &webview, mode, &fill)?;
+
+            {
+                let mut tab_state = state.lock_or_recover();
+                if let Some(tab) = tab_state.tabs.get_mut(&tab_id) {
+                    tab.saved_bounds = Some(saved);
+                    tab.is_fullscreen = true;
+                    tab.fullscreen_mode = Some(mode);
+                }
+            }
+            // Sichtbarkeit wie im Layout: versteckt solange Exclusions alles belegen
+            crate::canvas::layout_tabs(&app, Some(&tab_id))?;
+        }
+        webview.set_focus().ok();
+        Some(mode)
+    } else {
+        if let Some(current) = current_mode {
+            leave(&state, &main_window, &webview, &tab_id, current)?;
+            // Viewport kann sich im Vollbild geändert haben
+            crate::canvas::layout_tabs(&app, Some(&tab_id))?;
+        }
+        None
+    };
+
+    snapshot::schedule_snapshot(&app);
+
+    // Event senden
+    app.emit("web-tab-fullscreen", serde_json::json!({
+        "tab_id": tab_id,
+        "fullscreen": fullscreen,
+        "mode": mode,
+    })).ok();
+
+    Ok(())
+}
+
+/// Vollbild verlassen und die gespeicherten Bounds wiederherstellen. Der
+/// State ändert sich erst, wenn das Fenster zurück ist - schlägt `exit` fehl,
+/// bleiben Modus und Bounds für einen neuen Versuch erhalten.
+fn leave(
+    state: &Mutex<WebTabState>,
+    main_window: &impl WindowGeometry,
+    webview: &impl WindowGeometry,
+    tab_id: &str,
+    mode: FullscreenMode,
+) -> Result<(), WorkspaceError> {
+    let saved = {
+        let tab_state = state.lock_or_recover();
+        tab_state.tabs.get(tab_id).and_then(|tab| tab.saved_bounds.clone())
+    };
+
+    match &saved {
+        Some(saved) => exit(main_window, webview, mode, saved)?,
+        None if mode == FullscreenMode::Os => webview.set_fullscreen(false)?,
+        None => {}
+    }
+
+    let mut tab_state = state.lock_or_recover();
+    if let Some(tab) = tab_state.tabs.get_mut(tab_id) {
+        // Im Vollbild führt das Layout die Bounds auf der freien Fläche nach
+        if let Some(saved) = saved {
+            tab.bounds = saved;
+        }
+        tab.is_fullscreen = false;
+        tab.fullscreen_mode = None;
+        tab.saved_bounds = None;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    /// Fenster im Speicher: `position` ist die äußere Position inkl. Titelleiste
+    struct MockWindow {
+        position: Cell<PhysicalPosition<i32>>,
+        size: Cell<PhysicalSize<u32>>,
+        title_bar: i32,
+        fullscreen: RefCell<Vec<bool>>,
+        /// `set_fullscreen` schlägt fehl (z.B. Fenster gerade im Übergang)
+        fail_fullscreen: Cell<bool>,
+    }
+
+    impl MockWindow {
+        fn new(x: i32, y: i32, width: u32, height: u32, title_bar: i32) -> Self {
+            Self {
+                position: Cell::new(PhysicalPosition::new(x, y)),
+                size: Cell::new(PhysicalSize::new(width, height)),
+                title_bar,
+                fullscreen: RefCell::new(Vec::new()),
+                fail_fullscreen: Cell::new(false),
+            }
+        }
+    }
+
+    impl WindowGeometry for MockWindow {
+        fn inner_position(&self) -> Result<PhysicalPosition<i32>, WorkspaceError> {
+            let outer = self.position.get();
+            Ok(PhysicalPosition::new(outer.x, outer.y + self.title_bar))
+        }
+
+        fn inner_size(&self) -> Result<PhysicalSize<u32>, WorkspaceError> {
+            Ok(self.size.get())
+        }
+
+        // Wie bei echten Fenstern: `set_position` setzt die äußere Position
+        fn set_position(&self, position: PhysicalPosition<i32>) -> Result<(), WorkspaceError> {
+            self.position.set(position);
+            Ok(())
+        }
+
+        fn set_size(&self, size: PhysicalSize<u32>) -> Result<(), WorkspaceError> {
+            self.size.set(size);
+            Ok(())
+        }
+
+        fn set_fullscreen(&self, fullscreen: bool) -> Result<(), WorkspaceError> {
+            if self.fail_fullscreen.get() {
+                return Err(WorkspaceError::window_op("set_fullscreen")("window is busy"));
+            }
+            self.fullscreen.borrow_mut().push(fullscreen);
+            Ok(())
+        }
+    }
+
+    fn bounds(x: i32, y: i32, width: u32, height: u32) -> TabBounds {
+        TabBounds { x, y, width, height }
+    }
+
+    #[test]
+    fn test_fill_canvas_roundtrip_has_no_title_bar_drift() {
+        // Hauptfenster mit 28px Titelleiste, Tab ohne Dekorationen
+        let main = MockWindow::new(100, 100, 1200, 800, 28);
+        let tab = MockWindow::new(0, 0, 1, 1, 0);
+        place(&main, &tab, &bounds(50, 60, 400, 300)).unwrap();
+
+        let fill = fill_bounds(&main, Some(bounds(0, 48, 1200, 752))).unwrap();
+        let saved = enter(&main, &tab, FullscreenMode::FillCanvas, &fill).unwrap();
+        assert_eq!(saved, bounds(50, 60, 400, 300));
+        assert_eq!(relative_bounds(&main, &tab).unwrap(), bounds(0, 48, 1200, 752));
+
+        exit(&main, &tab, FullscreenMode::FillCanvas, &saved).unwrap();
+        assert_eq!(relative_bounds(&main, &tab).unwrap(), bounds(50, 60, 400, 300));
+        assert!(tab.fullscreen.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_os_fullscreen_restores_after_main_window_moved() {
+        let main = MockWindow::new(100, 100, 1200, 800, 28);
+        let tab = MockWindow::new(0, 0, 1, 1, 0);
+        place(&main, &tab, &bounds(10, 20, 640, 480)).unwrap();
+
+        let saved = enter(&main, &tab, FullscreenMode::Os, &fill_bounds(&main, None).unwrap()).unwrap();
+        // Hauptfenster wird im Vollbild verschoben
+        main.set_position(PhysicalPosition::new(300, 200)).unwrap();
+        exit(&main, &tab, FullscreenMode::Os, &saved).unwrap();
+
+        assert_eq!(*tab.fullscreen.borrow(), vec![true, false]);
+        assert_eq!(tab.inner_position().unwrap(), PhysicalPosition::new(310, 248));
+        assert_eq!(relative_bounds(&main, &tab).unwrap(), bounds(10, 20, 640, 480));
+    }
+
+    #[test]
+    fn test_failed_exit_keeps_fullscreen_state() {
+        let main = MockWindow::new(100, 100, 1200, 800, 28);
+        let tab = MockWindow::new(0, 0, 1, 1, 0);
+        place(&main, &tab, &bounds(10, 20, 640, 480)).unwrap();
+        let saved = enter(&main, &tab, FullscreenMode::Os, &fill_bounds(&main, None).unwrap()).unwrap();
+
+        let info = crate::WebTabInfo {
+            id: "webtab-a".into(),
+            url: "https://example.com/".into(),
+            bounds: bounds(10, 20, 640, 480),
+            is_fullscreen: true,
+            fullscreen_mode: Some(FullscreenMode::Os),
+            saved_bounds: Some(saved.clone()),
+            history: crate::history::NavigationHistory::default(),
+            title: None,
+            last_navigated_at: None,
+            profile: None,
+            canvas_rect: None,
+        };
+        let state = Mutex::new(WebTabState {
+            tabs: [("webtab-a".to_string(), info)].into_iter().collect(),
+            ..Default::default()
+        });
+
+        tab.fail_fullscreen.set(true);
+        assert!(leave(&state, &main, &tab, "webtab-a", FullscreenMode::Os).is_err());
+        {
+            let tab_state = state.lock_or_recover();
+            let info = &tab_state.tabs["webtab-a"];
+            assert!(info.is_fullscreen);
+            assert_eq!(info.fullscreen_mode, Some(FullscreenMode::Os));
+            assert_eq!(info.saved_bounds, Some(saved));
+        }
+
+        // Zweiter Versuch stellt die gespeicherten Bounds wieder her
+        tab.fail_fullscreen.set(false);
+        leave(&state, &main, &tab, "webtab-a", FullscreenMode::Os).unwrap();
+        let tab_state = state.lock_or_recover();
+        let info = &tab_state.tabs["webtab-a"];
+        assert!(!info.is_fullscreen);
+        assert_eq!(info.fullscreen_mode, None);
+        assert_eq!(info.saved_bounds, None);
+        assert_eq!(relative_bounds(&main, &tab).unwrap(), bounds(10, 20, 640, 480));
+    }
+
+    #[test]
+    fn test_fill_bounds_defaults_to_content_area() {
+        let main = MockWindow::new(0, 0, 1024, 700, 30);
+        assert_eq!(fill_bounds(&main, None).unwrap(), bounds(0, 0, 1024, 700));
+    }
+}
@@ -0,0 +1,398 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::persistence::now_millis;
+use crate::error::{LockExt, WorkspaceError};
+use crate::policy::{self, PolicyPhase};
+use crate::{snapshot, WebTabState};
+
+// ============================================================================
+// NAVIGATION HISTORY - Zurück/Vor/Neu laden für native Web-Tabs
+// ============================================================================
+
+/// Maximale Anzahl an Einträgen pro Tab (älteste fallen raus)
+const MAX_HISTORY_ENTRIES: usize = 50;
+
+/// Holt das Hauptdokument am Cache vorbei und lädt dann die Seite neu.
+/// Kein harter Reload: Skripte, Styles und Bilder kommen weiter aus dem Cache,
+/// das kann das Webview von außen nicht erzwingen.
+const REVALIDATE_DOCUMENT_SCRIPT: &str =
+    "fetch(location.href, { cache: 'reload', credentials: 'include' }).finally(() => location.reload());";
+
+/// Verlauf eines Tabs - `entries[index]` ist die aktuelle Seite
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct NavigationHistory {
+    pub entries: Vec<String>,
+    pub index: usize,
+    /// Per `history.go()` angeforderter Sprung, bis sein Page-Load kommt
+    #[serde(skip)]
+    pending_go: Option<i64>,
+}
+
+impl NavigationHistory {
+    pub fn new(url: &str) -> Self {
+        Self {
+            entries: vec![url.to_string()],
+            index: 0,
+            pending_go: None,
+        }
+    }
+
+    pub fn current(&self) -> Option<&str> {
+        self.entries.get(self.index).map(String::as_str)
+    }
+
+    pub fn can_go_back(&self) -> bool {
+        self.index > 0
+    }
+
+    pub fn can_go_forward(&self) -> bool {
+        self.index + 1 < self.entries.len()
+    }
+
+    /// Neue Seite: Vorwärts-Einträge verwerfen und anhängen
+    pub fn push(&mut self, url: &str) {
+        if self.current() == Some(url) {
+            return;
+        }
+
+        self.entries.truncate(self.index + 1);
+        self.entries.push(url.to_string());
+
+        if self.entries.len() > MAX_HISTORY_ENTRIES {
+            let overflow = self.entries.len() - MAX_HISTORY_ENTRIES;
+            self.entries.drain(..overflow);
+        }
+        self.index = self.entries.len() - 1;
+    }
+
+    /// Vom Webview gemeldete Seite einordnen: Ziel eines angeforderten
+    /// Sprungs, aktueller Eintrag, direkter Nachbar (Zurück/Vor per Maustaste
+    /// oder JS) oder neue Navigation. Gibt `false` zurück wenn sich nichts
+    /// geändert hat.
+    pub fn record(&mut self, url: &str) -> bool {
+        if let Some(delta) = self.pending_go.take() {
+            if let Some(target) = self.target_index(delta) {
+                if self.entries[target] == url {
+                    self.index = target;
+                    return true;
+                }
+            }
+        }
+
+        if self.current() == Some(url) {
+            return false;
+        }
+
+        if self.index > 0 && self.entries[self.index - 1] == url {
+            self.index -= 1;
+        } else if self.entries.get(self.index + 1).map(String::as_str) == Some(url) {
+            self.index += 1;
+        } else {
+            self.push(url);
+        }
+        true
+    }
+
+    /// Sprung um `delta` Einträge (negativ = zurück) vormerken und das Ziel
+    /// liefern. Der Index wandert erst mit dem Page-Load (`record`).
+    /// `None` wenn das Ziel außerhalb des Verlaufs liegt.
+    pub fn go(&mut self, delta: i64) -> Option<&str> {
+        let target = self.target_index(delta)?;
+        self.pending_go = Some(delta);
+        self.entries.get(target).map(String::as_str)
+    }
+
+    fn target_index(&self, delta: i64) -> Option<usize> {
+        let target = self.index as i64 + delta;
+        (delta != 0 && target >= 0 && target < self.entries.len() as i64).then_some(target as usize)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WebTabHistoryEvent {
+    pub tab_id: String,
+    pub history: NavigationHistory,
+}
+
+/// Der Tab hat selbst navigiert (Link-Klick, Redirect, Formular, ...)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WebTabNavigatedEvent {
+    pub tab_id: String,
+    pub url: String,
+    pub title: Option<String>,
+    pub navigated_at: u64,
+}
+
+/// Springt im Webview per `history.go()` um `delta` Einträge. Den eigenen
+/// Verlauf zieht der Page-Load des Ziels nach (`record_page_load`); ein neu
+/// erstelltes Webview beginnt mit leerem Verlauf, dort startet auch der
+/// eigene wieder bei der aktuellen Seite (siehe `reconcile`).
+fn go_in_history(app: &AppHandle, tab_id: &str, delta: i64) -> Result<(), WorkspaceError> {
+    let webview = app
+        .get_webview_window(tab_id)
+        .ok_or_else(|| WorkspaceError::tab_not_found(tab_id))?;
+
+    let state = app.state::<Mutex<WebTabState>>();
+    let url = {
+        let mut tab_state = state.lock_or_recover();
+        let tab = tab_state
+            .tabs
+            .get_mut(tab_id)
+            .ok_or_else(|| WorkspaceError::tab_not_found(tab_id))?;
+
+        tab.history
+            .go(delta)
+            .ok_or_else(|| {
+                let message = if delta < 0 { "No previous page" } else { "No next page" };
+                WorkspaceError::InvalidInput(message.to_string())
+            })?
+            .to_string()
+    };
+
+    // Vorab prüfen liefert den genauen Policy-Fehler statt eines stillen Abbruchs
+    let parsed_url: tauri::Url = url.parse().map_err(WorkspaceError::invalid_url(&url))?;
+    policy::enforce(app, tab_id, &parsed_url, PolicyPhase::Navigate)?;
+
+    webview
+        .eval(&format!("history.go({});", delta))
+        .map_err(WorkspaceError::window_op("eval"))
+}
+
+/// Meldet einen geänderten Verlauf an das Frontend
+pub fn emit_history(app: &AppHandle, tab_id: &str, history: &NavigationHistory) {
+    app.emit("web-tab-history", WebTabHistoryEvent {
+        tab_id: tab_id.to_string(),
+        history: history.clone(),
+    }).ok();
+}
+
+// ============================================================================
+// PAGE LOAD TRACKING - Handler aus `build_web_tab_window`
+// ============================================================================
+
+/// Neue Seite beginnt zu laden - der Titel gehört noch zur alten. Beim
+/// Fertigladen ist es zu spät, bis dahin kam oft schon der neue Titel.
+pub fn record_load_started(app: &AppHandle, tab_id: &str, url: &str) {
+    let state = app.state::<Mutex<WebTabState>>();
+    let mut tab_state = state.lock_or_recover();
+    if let Some(tab) = tab_state.tabs.get_mut(tab_id) {
+        if tab.url != url {
+            tab.title = None;
+        }
+    }
+}
+
+/// Fertig geladene Seite in `WebTabState` übernehmen
+pub fn record_page_load(app: &AppHandle, tab_id: &str, url: &str) {
+    let (event, history) = {
+        let state = app.state::<Mutex<WebTabState>>();
+        let mut tab_state = state.lock_or_recover();
+        // Tab noch nicht eingetragen (erster Load beim Erstellen) oder schon geschlossen
+        let Some(tab) = tab_state.tabs.get_mut(tab_id) else {
+            return;
+        };
+
+        let history_changed = tab.history.record(url);
+        let navigated_at = now_millis();
+        tab.url = url.to_string();
+        tab.last_navigated_at = Some(navigated_at);
+
+        let event = WebTabNavigatedEvent {
+            tab_id: tab_id.to_string(),
+            url: tab.url.clone(),
+            title: tab.title.clone(),
+            navigated_at,
+        };
+        (event, history_changed.then(|| tab.history.clone()))
+    };
+
+    snapshot::schedule_snapshot(app);
+    app.emit("web-tab-navigated", event).ok();
+    if let Some(history) = history {
+        emit_history(app, tab_id, &history);
+    }
+}
+
+/// Neuen Dokumenttitel übernehmen
+pub fn record_title(app: &AppHandle, tab_id: &str, title: String) {
+    let event = {
+        let state = app.state::<Mutex<WebTabState>>();
+        let mut tab_state = state.lock_or_recover();
+        let Some(tab) = tab_state.tabs.get_mut(tab_id) else {
+            return;
+        };
+        if tab.title.as_deref() == Some(title.as_str()) {
+            return;
+        }
+
+        tab.title = Some(title);
+        WebTabNavigatedEvent {
+            tab_id: tab_id.to_string(),
+            url: tab.url.clone(),
+            title: tab.title.clone(),
+            navigated_at: tab.last_navigated_at.unwrap_or_default(),
+        }
+    };
+
+    snapshot::schedule_snapshot(app);
+    app.emit("web-tab-navigated", event).ok();
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Geht im Verlauf zurück (`steps` > 1 für das Verlauf-Dropdown). Der neue
+/// Verlauf kommt mit dem Page-Load per `web-tab-history`.
+#[tauri::command]
+pub async fn web_tab_go_back(
+    app: AppHandle,
+    tab_id: String,
+    steps: Option<u32>,
+) -> Result<(), WorkspaceError> {
+    go_in_history(&app, &tab_id, -(steps.unwrap_or(1).max(1) as i64))
+}
+
+/// Geht im Verlauf vor
+#[tauri::command]
+pub async fn web_tab_go_forward(
+    app: AppHandle,
+    tab_id: String,
+    steps: Option<u32>,
+) -> Result<(), WorkspaceError> {
+    go_in_history(&app, &tab_id, steps.unwrap_or(1).max(1) as i64)
+}
+
+/// Lädt die Seite neu, optional mit am Cache vorbei geholtem Hauptdokument
+#[tauri::command]
+pub async fn web_tab_reload(
+    app: AppHandle,
+    tab_id: String,
+    bypass_cache: Option<bool>,
+) -> Result<(), WorkspaceError> {
+    let webview = app
+        .get_webview_window(&tab_id)
+        .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))?;
+
+    if bypass_cache.unwrap_or(false) {
+        webview.eval(REVALIDATE_DOCUMENT_SCRIPT)
+    } else {
+        webview.reload()
+    }
+    .map_err(WorkspaceError::window_op("reload"))
+}
+
+/// Bricht das Laden der aktuellen Seite ab
+#[tauri::command]
+pub async fn web_tab_stop(app: AppHandle, tab_id: String) -> Result<(), WorkspaceError> {
+    let webview = app
+        .get_webview_window(&tab_id)
+        .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))?;
+
+    webview
+        .eval("window.stop();")
+        .map_err(WorkspaceError::window_op("eval"))
+}
+
+/// Verlauf eines Tabs (für das Zurück/Vor-Dropdown)
+#[tauri::command]
+pub fn get_web_tab_history(
+    state: tauri::State<'_, Mutex<WebTabState>>,
+    tab_id: String,
+) -> Result<NavigationHistory, WorkspaceError> {
+    let tab_state = state.lock_or_recover();
+    tab_state
+        .tabs
+        .get(&tab_id)
+        .map(|tab| tab.history.clone())
+        .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_truncates_forward_entries() {
+        let mut history = NavigationHistory::new("https://a.com");
+        history.push("https://b.com");
+        history.push("https://c.com");
+
+        assert_eq!(history.go(-2), Some("https://a.com"));
+        assert!(history.record("https://a.com"));
+        assert!(history.can_go_forward());
+
+        history.push("https://d.com");
+        assert_eq!(history.entries, vec!["https://a.com", "https://d.com"]);
+        assert!(!history.can_go_forward());
+        assert!(history.can_go_back());
+    }
+
+    #[test]
+    fn test_go_out_of_range_keeps_position() {
+        let mut history = NavigationHistory::new("https://a.com");
+        history.push("https://b.com");
+
+        assert_eq!(history.go(1), None);
+        assert_eq!(history.go(-2), None);
+        assert_eq!(history.current(), Some("https://b.com"));
+    }
+
+    #[test]
+    fn test_push_same_url_is_ignored() {
+        let mut history = NavigationHistory::new("https://a.com");
+        history.push("https://a.com");
+        assert_eq!(history.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_record_detects_back_forward_and_new_pages() {
+        let mut history = NavigationHistory::new("https://a.com/");
+        history.push("https://b.com/");
+        history.push("https://c.com/");
+
+        assert!(!history.record("https://c.com/"));
+        assert!(history.record("https://b.com/"));
+        assert_eq!(history.index, 1);
+        assert!(history.record("https://c.com/"));
+        assert_eq!(history.index, 2);
+
+        // Link-Klick auf eine neue Seite
+        assert!(history.record("https://d.com/"));
+        assert_eq!(history.entries.len(), 4);
+        assert_eq!(history.current(), Some("https://d.com/"));
+    }
+
+    #[test]
+    fn test_go_moves_only_with_the_page_load() {
+        let mut history = NavigationHistory::new("https://a.com/");
+        history.push("https://b.com/");
+        history.push("https://c.com/");
+
+        // Zwei Schritte zurück - kein direkter Nachbar, trotzdem kein neuer Eintrag
+        assert_eq!(history.go(-2), Some("https://a.com/"));
+        assert_eq!(history.index, 2);
+        assert!(history.record("https://a.com/"));
+        assert_eq!(history.index, 0);
+        assert_eq!(history.entries.len(), 3);
+
+        // Webview landet woanders (z.B. Redirect) - wie eine neue Navigation
+        history.go(2);
+        assert!(history.record("https://d.com/"));
+        assert_eq!(history.entries, vec!["https://a.com/", "https://d.com/"]);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        let mut history = NavigationHistory::new("https://0.com");
+        for i in 1..=MAX_HISTORY_ENTRIES + 5 {
+            history.push(&format!("https://{}.com", i));
+        }
+
+        assert_eq!(history.entries.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(history.index, MAX_HISTORY_ENTRIES - 1);
+        assert_eq!(history.entries[0], "https://6.com");
+    }
+}
@@ -0,0 +1,162 @@
+use tauri::ipc::Invoke;
+use tauri::Runtime;
+
+// ============================================================================
+// IPC ACCESS - App-Commands nur aus dem Hauptfenster
+// ============================================================================
+//
+// Web-Tabs (`webtab-*`) und Legacy-Fenster (`webview-*`) laden beliebige
+// externe Seiten. Die Capability `webtab-remote` gibt ihnen nur die
+// Permissions für `WEB_TAB_COMMANDS`; dieser Guard sorgt zusätzlich dafür,
+// dass sie keine anderen App-Commands aufrufen können - auch nicht über
+// Umwege wie lokale URLs im selben Fenster.
+
+/// Label des Hauptfensters (lädt die App selbst)
+pub const MAIN_WINDOW: &str = "main";
+
+/// Präfix der Web-Tab-Fenster
+pub const WEB_TAB_PREFIX: &str = "webtab-";
+
+/// Einzige Commands, die Web-Tabs aufrufen dürfen (Rückkanäle injizierter Scripts)
+pub const WEB_TAB_COMMANDS: &[&str] = &["report_webview_content"];
+
+/// Art des aufrufenden Fensters
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Caller {
+    Main,
+    WebTab,
+    /// Alles andere (z.B. Legacy-Fenster `webview-*`)
+    Other,
+}
+
+impl Caller {
+    pub fn from_label(label: &str) -> Self {
+        if label == MAIN_WINDOW {
+            Caller::Main
+        } else if label.starts_with(WEB_TAB_PREFIX) {
+            Caller::WebTab
+        } else {
+            Caller::Other
+        }
+    }
+}
+
+/// Darf dieses Fenster den App-Command aufrufen?
+pub fn is_allowed(caller: Caller, command: &str) -> bool {
+    match caller {
+        Caller::Main => true,
+        Caller::WebTab => WEB_TAB_COMMANDS.contains(&command),
+        Caller::Other => false,
+    }
+}
+
+/// Umschließt den `generate_handler!`-Handler und lehnt nicht erlaubte Aufrufe ab
+pub fn guard<R, F>(handler: F) -> impl Fn(Invoke<R>) -> bool + Send + Sync + 'static
+where
+    R: Runtime,
+    F: Fn(Invoke<R>) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        let webview = invoke.message.webview();
+        let command = invoke.message.command();
+        if !is_allowed(Caller::from_label(webview.label()), command) {
+            let error = format!("Command {} not allowed for window {}", command, webview.label());
+            log::warn!("Blocked IPC call: {}", error);
+            invoke.resolver.reject(error);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Alle Commands aus `app_commands![...]` in lib.rs (ohne Modulpfad)
+    fn registered_commands() -> Vec<String> {
+        crate::APP_COMMANDS
+            .iter()
+            .map(|entry| entry.rsplit("::").next().unwrap().trim().to_string())
+            .collect()
+    }
+
+    /// Von tauri-build erzeugte Permission für einen App-Command
+    fn allow_permission(command: &str) -> String {
+        format!("allow-{}", command.replace('_', "-"))
+    }
+
+    #[test]
+    fn test_command_access_audit() {
+        let commands = registered_commands();
+        assert!(commands.len() > 30, "found only {:?}", commands);
+
+        for command in &commands {
+            assert!(is_allowed(Caller::Main, command), "main must call {}", command);
+            assert!(!is_allowed(Caller::Other, command), "legacy windows must not call {}", command);
+            assert_eq!(
+                is_allowed(Caller::WebTab, command),
+                command == "report_webview_content",
+                "unexpected web tab access to {}",
+                command
+            );
+        }
+
+        // Jeder freigegebene Command muss auch registriert sein
+        for command in WEB_TAB_COMMANDS {
+            assert!(commands.iter().any(|c| c == command), "{} is not registered", command);
+        }
+    }
+
+    #[test]
+    fn test_caller_from_label() {
+        assert_eq!(Caller::from_label("main"), Caller::Main);
+        assert_eq!(Caller::from_label("webtab-1a2b"), Caller::WebTab);
+        assert_eq!(Caller::from_label("webview-1a2b"), Caller::Other);
+        assert_eq!(Caller::from_label("mainframe"), Caller::Other);
+    }
+
+    #[test]
+    fn test_capabilities_keep_web_tabs_minimal() {
+        let default: serde_json::Value =
+            serde_json::from_str(include_str!("../capabilities/default.json")).unwrap();
+        let remote: serde_json::Value =
+            serde_json::from_str(include_str!("../capabilities/webtab-remote.json")).unwrap();
+
+        for key in ["windows", "webviews"] {
+            assert_eq!(default[key], serde_json::json!([MAIN_WINDOW]), "default.{}", key);
+        }
+
+        // Web-Tabs bekommen genau die Rückkanäle, sonst nichts
+        let expected: Vec<String> = WEB_TAB_COMMANDS.iter().map(|c| allow_permission(c)).collect();
+        assert_eq!(remote["permissions"], serde_json::json!(expected));
+
+        // Das Hauptfenster braucht mit App-Manifest jede Freigabe explizit
+        let granted = default["permissions"].as_array().unwrap();
+        for command in registered_commands() {
+            let permission = allow_permission(&command);
+            assert!(granted.contains(&serde_json::json!(permission)), "main lacks {}", permission);
+        }
+    }
+
+    #[test]
+    fn test_app_manifest_declares_every_command() {
+        let build = include_str!("../build.rs");
+        for command in registered_commands() {
+            assert!(
+                build.contains(&format!("\"{}\",", command)),
+                "{} is missing from APP_COMMANDS in build.rs",
+                command
+            );
+        }
+    }
+
+    #[test]
+    fn test_main_window_has_csp() {
+        let config: serde_json::Value = serde_json::from_str(include_str!("../tauri.conf.json")).unwrap();
+        let csp = config["app"]["security"]["csp"].as_str().expect("csp must be set");
+
+        assert!(csp.contains("default-src 'self'"));
+        assert!(csp.contains("script-src 'self';"));
+    }
+}
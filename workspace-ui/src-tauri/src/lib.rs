@@ -1,29 +1,80 @@
 use tauri::{
-    DragDropEvent, Emitter, Manager, PhysicalPosition, PhysicalSize, WebviewUrl, WebviewWindowBuilder,
+    webview::PageLoadEvent, DragDropEvent, Emitter, Manager, PhysicalPosition, PhysicalSize,
+    WebviewUrl, WebviewWindowBuilder,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::sync::Mutex;
 
+mod backend;
+mod canvas;
+mod content;
+mod content_blocking;
+mod error;
+mod fullscreen;
+mod history;
+mod ipc_access;
+mod persistence;
+mod policy;
+mod profiles;
+mod reconcile;
+mod snapshot;
+mod thumbnail;
+
+use backend::WebTabBackendState;
+use canvas::{CanvasLayoutState, CanvasRect};
+use content::ContentExtractionState;
+use content_blocking::ContentBlockingState;
+use error::{LockExt, WorkspaceError};
+use history::NavigationHistory;
+use policy::{PolicyPhase, UrlPolicyState};
+use profiles::ProfileStore;
+use snapshot::TabSnapshotState;
+
 // ============================================================================
 // WEB TAB MANAGEMENT - Native Webviews als Canvas-Tabs
 // ============================================================================
 
 /// State für alle aktiven Web-Tabs
+#[derive(Default)]
 struct WebTabState {
     tabs: HashMap<String, WebTabInfo>,
+    /// Tabs, deren Fenster gerade gebaut wird - der Abgleich lässt sie in Ruhe
+    pending: HashSet<String>,
+    /// Board, zu dem die offenen Tabs gehören (gesetzt von `restore_web_tabs`)
+    board_id: Option<String>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
 struct WebTabInfo {
     id: String,
     url: String,
+    // Letzte Canvas-Bounds (relativ zum Hauptfenster)
+    bounds: TabBounds,
     is_fullscreen: bool,
-    // Gespeicherte Position vor Fullscreen
+    // Art des Vollbilds (nur gesetzt solange `is_fullscreen`)
+    #[serde(default)]
+    fullscreen_mode: Option<fullscreen::FullscreenMode>,
+    // Gespeicherte Position vor Fullscreen (relativ zum Inhaltsbereich des Hauptfensters)
     saved_bounds: Option<TabBounds>,
+    // Zurück/Vor-Verlauf (fehlt in alten Snapshots)
+    #[serde(default)]
+    history: NavigationHistory,
+    // Dokumenttitel der aktuellen Seite
+    #[serde(default)]
+    title: Option<String>,
+    // Zeitpunkt der letzten Navigation (ms seit Epoch)
+    #[serde(default)]
+    last_navigated_at: Option<u64>,
+    // Eigener Datenspeicher (None = geteiltes Standard-Profil)
+    #[serde(default)]
+    profile: Option<String>,
+    // Inhaltsbereich der Karte in Canvas-Koordinaten (Layout über set_canvas_viewport)
+    #[serde(default)]
+    canvas_rect: Option<CanvasRect>,
 }
 
-#[derive(Clone, Serialize, Deserialize, Debug)]
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
 pub struct TabBounds {
     pub x: i32,
     pub y: i32,
@@ -47,82 +98,108 @@ pub struct WebTabFocusEvent {
 // TAURI COMMANDS
 // ============================================================================
 
-/// Erstellt einen neuen Web-Tab als frameless Window
-#[tauri::command]
-async fn create_web_tab(
-    app: tauri::AppHandle,
-    state: tauri::State<'_, Mutex<WebTabState>>,
-    tab_id: String,
-    url: String,
-    bounds: TabBounds,
-) -> Result<String, String> {
-    // Prüfen ob bereits ein Fenster mit dieser ID existiert - falls ja, schließen
-    if let Some(existing_window) = app.get_webview_window(&tab_id) {
-        existing_window.close().ok();
-        // Aus State entfernen falls vorhanden
-        {
-            let mut tab_state = state.lock().unwrap();
-            tab_state.tabs.remove(&tab_id);
-        }
-    }
-
+/// Baut das frameless Tab-Fenster und registriert die Window-Events
+fn build_web_tab_window(
+    app: &tauri::AppHandle,
+    tab_id: &str,
+    url: &str,
+    bounds: &TabBounds,
+    visible: bool,
+    profile: Option<&str>,
+) -> Result<tauri::WebviewWindow, WorkspaceError> {
     let webview_url = WebviewUrl::External(
-        url.parse().map_err(|e| format!("Invalid URL: {}", e))?
+        url.parse().map_err(WorkspaceError::invalid_url(url))?
     );
 
     // Hauptfenster holen für relative Positionierung
     let main_window = app
         .get_webview_window("main")
-        .ok_or("Main window not found")?;
+        .ok_or_else(WorkspaceError::main_window_not_found)?;
     
     // Use inner_position to get coordinates relative to the content area (excluding title bar)
     let main_pos = main_window
         .inner_position()
-        .map_err(|e| format!("Failed to get main window position: {}", e))?;
+        .map_err(WorkspaceError::window_op("inner_position"))?;
 
     // Absolutes Positioning relativ zum Hauptfenster
     let abs_x = main_pos.x + bounds.x;
     let abs_y = main_pos.y + bounds.y;
 
+    // Bounds und inner_position sind physisch, der Builder nimmt logische Einheiten
+    let scale = main_window
+        .scale_factor()
+        .map_err(WorkspaceError::window_op("scale_factor"))?;
+
+    // Navigationen innerhalb der Seite (Links, Redirects) im State nachziehen
+    let load_app = app.clone();
+    let load_tab_id = tab_id.to_string();
+    let title_app = app.clone();
+    let title_tab_id = tab_id.to_string();
+    let policy_app = app.clone();
+    let policy_tab_id = tab_id.to_string();
+
     // Frameless Window erstellen - als Child des Hauptfensters
-    let webview = WebviewWindowBuilder::new(&app, &tab_id, webview_url)
+    let builder = WebviewWindowBuilder::new(app, tab_id, webview_url)
         .title("")
-        .inner_size(bounds.width as f64, bounds.height as f64)
-        .position(abs_x as f64, abs_y as f64)
+        .inner_size(bounds.width as f64 / scale, bounds.height as f64 / scale)
+        .position(abs_x as f64 / scale, abs_y as f64 / scale)
         .decorations(false)  // Kein OS-Frame
         .resizable(false)    // Resize über Canvas
         .skip_taskbar(true)  // Nicht in Taskbar zeigen
         .always_on_top(true) // Immer vor dem Hauptfenster
         .focused(false)      // Nicht sofort fokussieren
-        .visible(true)
+        .visible(visible)
+        // Links und Redirects innerhalb der Seite laufen durch die URL-Policy.
+        // wry meldet weder Frame noch Nutzer-Geste - daher nie extern öffnen;
+        // die Tab-Karte zeigt die Sperre an und bietet das Öffnen im Browser an.
+        .on_navigation(move |url| {
+            policy::enforce(&policy_app, &policy_tab_id, url, PolicyPhase::InPageAutomatic).is_ok()
+        })
+        .on_page_load(move |_webview, payload| match payload.event() {
+            PageLoadEvent::Started => {
+                history::record_load_started(&load_app, &load_tab_id, payload.url().as_str());
+            }
+            PageLoadEvent::Finished => {
+                history::record_page_load(&load_app, &load_tab_id, payload.url().as_str());
+            }
+        })
+        .on_document_title_changed(move |_webview, title| {
+            history::record_title(&title_app, &title_tab_id, title);
+        });
+
+    // Eigenes Profil: Cookies/Storage getrennt vom Standard-Speicher
+    let builder = match profile {
+        Some(name) => {
+            let store = ProfileStore::for_app(app)?;
+            let profile = store.ensure(name)?;
+            profiles::apply_profile(builder, &store, &profile)?
+        }
+        None => builder,
+    };
+
+    let webview = builder
         .parent(&main_window)
-        .map_err(|e| format!("Failed to set parent: {}", e))?
+        .map_err(WorkspaceError::window_op("set_parent"))?
         .build()
-        .map_err(|e| format!("Failed to create web tab: {}", e))?;
+        .map_err(WorkspaceError::window_op("create_window"))?;
 
-    // Tab in State speichern
-    {
-        let mut tab_state = state.lock().unwrap();
-        tab_state.tabs.insert(
-            tab_id.clone(),
-            WebTabInfo {
-                id: tab_id.clone(),
-                url: url.clone(),
-                is_fullscreen: false,
-                saved_bounds: None,
-            },
-        );
+    // Exakt in Pixeln nachziehen - liegt das Tab auf einem Monitor mit anderem
+    // Scale-Faktor, rechnet der Builder mit dessen Faktor um
+    let placed = webview
+        .set_position(PhysicalPosition::new(abs_x, abs_y))
+        .and_then(|_| webview.set_size(PhysicalSize::new(bounds.width, bounds.height)));
+    if let Err(e) = placed {
+        log::warn!("Failed to place web tab {}: {}", tab_id, e);
     }
 
-    // Event an Frontend senden
-    app.emit("web-tab-created", WebTabCreatedEvent {
-        tab_id: tab_id.clone(),
-        url,
-    }).ok();
+    // Tracker und Werbung blockieren (nur wo die Plattform einen Request-Hook hat)
+    if let Err(e) = content_blocking::install(app, &webview) {
+        log::error!("Failed to install content blocking for {}: {}", tab_id, e);
+    }
 
     // Focus-Events für diesen Tab
     let app_handle = app.clone();
-    let tab_id_clone = tab_id.clone();
+    let tab_id_clone = tab_id.to_string();
     webview.on_window_event(move |event| {
         match event {
             tauri::WindowEvent::Focused(focused) => {
@@ -139,141 +216,173 @@ async fn create_web_tab(
         }
     });
 
+    Ok(webview)
+}
+
+/// Erstellt einen neuen Web-Tab als frameless Window
+#[tauri::command]
+async fn create_web_tab(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<WebTabState>>,
+    tab_id: String,
+    url: String,
+    bounds: TabBounds,
+    profile: Option<String>,
+) -> Result<String, WorkspaceError> {
+    let profile = profiles::normalize_profile_name(profile)?;
+
+    // Normalisiert wie die URLs aus den Page-Load-Events
+    let parsed_url: tauri::Url = url.parse().map_err(WorkspaceError::invalid_url(&url))?;
+    policy::enforce(&app, &tab_id, &parsed_url, PolicyPhase::Create)?;
+    let url = parsed_url.to_string();
+
+    app.state::<Mutex<CanvasLayoutState>>().lock_or_recover().forget(&tab_id);
+
+    // Prüfen ob bereits ein Fenster mit dieser ID existiert - falls ja, schließen
+    if let Some(existing_window) = app.get_webview_window(&tab_id) {
+        existing_window.close().ok();
+        // Aus State entfernen falls vorhanden
+        {
+            let mut tab_state = state.lock_or_recover();
+            tab_state.tabs.remove(&tab_id);
+        }
+        app.state::<ContentBlockingState>().forget(&tab_id);
+    }
+
+    // Vor dem Erstellen eintragen, damit der erste Page-Load den Tab findet.
+    // Als `pending` markiert, damit ein paralleler Abgleich ihn nicht neu erstellt.
+    {
+        let mut tab_state = state.lock_or_recover();
+        tab_state.pending.insert(tab_id.clone());
+        tab_state.tabs.insert(
+            tab_id.clone(),
+            WebTabInfo {
+                id: tab_id.clone(),
+                url: url.clone(),
+                bounds: bounds.clone(),
+                is_fullscreen: false,
+                fullscreen_mode: None,
+                saved_bounds: None,
+                history: NavigationHistory::new(&url),
+                title: None,
+                last_navigated_at: Some(persistence::now_millis()),
+                profile: profile.clone(),
+                canvas_rect: None,
+            },
+        );
+    }
+
+    let built = build_web_tab_window(&app, &tab_id, &url, &bounds, true, profile.as_deref());
+    {
+        let mut tab_state = state.lock_or_recover();
+        tab_state.pending.remove(&tab_id);
+        if built.is_err() {
+            tab_state.tabs.remove(&tab_id);
+        }
+    }
+    built?;
+    snapshot::schedule_snapshot(&app);
+
+    // Event an Frontend senden
+    app.emit("web-tab-created", WebTabCreatedEvent {
+        tab_id: tab_id.clone(),
+        url,
+    }).ok();
+
     Ok(tab_id)
 }
 
+/// Stellt die Tabs des Boards aus der letzten Session wieder her (über den
+/// Abgleich) und liefert alle bekannten Tabs. Fenster starten versteckt und
+/// werden erst sichtbar, wenn die Canvas-Karte sie anzeigt.
+#[tauri::command]
+async fn restore_web_tabs(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<WebTabState>>,
+    board_id: String,
+) -> Result<Vec<WebTabInfo>, WorkspaceError> {
+    reconcile::reconcile(&app, Some(&board_id))?;
+
+    let mut restored: Vec<WebTabInfo> = state.lock_or_recover().tabs.values().cloned().collect();
+    restored.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let layout_state = app.state::<Mutex<CanvasLayoutState>>();
+    let mut layout = layout_state.lock_or_recover();
+    for tab in &restored {
+        layout.set_hidden(&tab.id, true);
+    }
+
+    Ok(restored)
+}
+
 /// Aktualisiert Position und Größe eines Web-Tabs
 #[tauri::command]
 async fn update_web_tab_bounds(
     app: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<WebTabState>>,
     tab_id: String,
     bounds: TabBounds,
-) -> Result<(), String> {
+) -> Result<(), WorkspaceError> {
     let webview = app
         .get_webview_window(&tab_id)
-        .ok_or("Web tab not found")?;
+        .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))?;
 
     // Hauptfenster für relative Positionierung
     let main_window = app
         .get_webview_window("main")
-        .ok_or("Main window not found")?;
+        .ok_or_else(WorkspaceError::main_window_not_found)?;
     
     // Use inner_position to account for title bar/decorations
     let main_pos = main_window
         .inner_position()
-        .map_err(|e| format!("Failed to get main window position: {}", e))?;
+        .map_err(WorkspaceError::window_op("inner_position"))?;
 
     let abs_x = main_pos.x + bounds.x;
     let abs_y = main_pos.y + bounds.y;
 
     webview
         .set_position(PhysicalPosition::new(abs_x, abs_y))
-        .map_err(|e| format!("Failed to set position: {}", e))?;
+        .map_err(WorkspaceError::window_op("set_position"))?;
 
     webview
         .set_size(PhysicalSize::new(bounds.width, bounds.height))
-        .map_err(|e| format!("Failed to set size: {}", e))?;
+        .map_err(WorkspaceError::window_op("set_size"))?;
+
+    {
+        let mut tab_state = state.lock_or_recover();
+        if let Some(tab) = tab_state.tabs.get_mut(&tab_id) {
+            tab.bounds = bounds;
+        }
+    }
+    snapshot::schedule_snapshot(&app);
 
     Ok(())
 }
 
 /// Fokussiert einen Web-Tab
 #[tauri::command]
-async fn focus_web_tab(app: tauri::AppHandle, tab_id: String) -> Result<(), String> {
+async fn focus_web_tab(app: tauri::AppHandle, tab_id: String) -> Result<(), WorkspaceError> {
     let webview = app
         .get_webview_window(&tab_id)
-        .ok_or("Web tab not found")?;
+        .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))?;
 
     webview
         .set_focus()
-        .map_err(|e| format!("Failed to focus: {}", e))?;
+        .map_err(WorkspaceError::window_op("set_focus"))?;
 
     Ok(())
 }
 
 /// Unfokussiert Web-Tab (fokussiert Hauptfenster)
 #[tauri::command]
-async fn unfocus_web_tabs(app: tauri::AppHandle) -> Result<(), String> {
+async fn unfocus_web_tabs(app: tauri::AppHandle) -> Result<(), WorkspaceError> {
     let main_window = app
         .get_webview_window("main")
-        .ok_or("Main window not found")?;
+        .ok_or_else(WorkspaceError::main_window_not_found)?;
 
     main_window
         .set_focus()
-        .map_err(|e| format!("Failed to focus main: {}", e))?;
-
-    Ok(())
-}
-
-/// Setzt Web-Tab in Fullscreen-Modus
-#[tauri::command]
-async fn set_web_tab_fullscreen(
-    app: tauri::AppHandle,
-    state: tauri::State<'_, Mutex<WebTabState>>,
-    tab_id: String,
-    fullscreen: bool,
-) -> Result<(), String> {
-    let webview = app
-        .get_webview_window(&tab_id)
-        .ok_or("Web tab not found")?;
-
-    let main_window = app
-        .get_webview_window("main")
-        .ok_or("Main window not found")?;
-
-    if fullscreen {
-        // Aktuelle Bounds speichern
-        let current_pos = webview.outer_position().unwrap_or(PhysicalPosition::new(0, 0));
-        let current_size = webview.outer_size().unwrap_or(PhysicalSize::new(800, 600));
-        let main_pos = main_window.outer_position().unwrap_or(PhysicalPosition::new(0, 0));
-
-        {
-            let mut tab_state = state.lock().unwrap();
-            if let Some(tab) = tab_state.tabs.get_mut(&tab_id) {
-                tab.saved_bounds = Some(TabBounds {
-                    x: current_pos.x - main_pos.x,
-                    y: current_pos.y - main_pos.y,
-                    width: current_size.width,
-                    height: current_size.height,
-                });
-                tab.is_fullscreen = true;
-            }
-        }
-
-        // Hauptfenster-Größe für Fullscreen
-        let main_size = main_window.outer_size().unwrap_or(PhysicalSize::new(1200, 800));
-        let main_pos = main_window.outer_position().unwrap_or(PhysicalPosition::new(0, 0));
-
-        webview.set_position(main_pos).ok();
-        webview.set_size(main_size).ok();
-        webview.set_focus().ok();
-    } else {
-        // Zurück zur gespeicherten Position
-        let saved_bounds = {
-            let mut tab_state = state.lock().unwrap();
-            if let Some(tab) = tab_state.tabs.get_mut(&tab_id) {
-                tab.is_fullscreen = false;
-                tab.saved_bounds.take()
-            } else {
-                None
-            }
-        };
-
-        if let Some(bounds) = saved_bounds {
-            let main_pos = main_window.outer_position().unwrap_or(PhysicalPosition::new(0, 0));
-            webview.set_position(PhysicalPosition::new(
-                main_pos.x + bounds.x,
-                main_pos.y + bounds.y,
-            )).ok();
-            webview.set_size(PhysicalSize::new(bounds.width, bounds.height)).ok();
-        }
-    }
-
-    // Event senden
-    app.emit("web-tab-fullscreen", serde_json::json!({
-        "tab_id": tab_id,
-        "fullscreen": fullscreen,
-    })).ok();
+        .map_err(WorkspaceError::window_op("set_focus"))?;
 
     Ok(())
 }
@@ -284,36 +393,42 @@ async fn close_web_tab(
     app: tauri::AppHandle,
     state: tauri::State<'_, Mutex<WebTabState>>,
     tab_id: String,
-) -> Result<(), String> {
+) -> Result<(), WorkspaceError> {
     // Aus State entfernen
     {
-        let mut tab_state = state.lock().unwrap();
+        let mut tab_state = state.lock_or_recover();
         tab_state.tabs.remove(&tab_id);
     }
+    app.state::<Mutex<CanvasLayoutState>>().lock_or_recover().forget(&tab_id);
+    app.state::<ContentBlockingState>().forget(&tab_id);
+    snapshot::schedule_snapshot(&app);
 
     // Window schließen
     if let Some(webview) = app.get_webview_window(&tab_id) {
-        webview.close().map_err(|e| format!("Failed to close: {}", e))?;
+        webview.close().map_err(WorkspaceError::window_op("close"))?;
     }
 
     Ok(())
 }
 
-/// Versteckt einen Web-Tab (z.B. beim Zoomen)
+/// Versteckt einen Web-Tab (z.B. beim Zoomen). Tabs, die das Canvas-Layout
+/// ausgeblendet hat, bleiben versteckt bis sie wieder im Bild sind.
 #[tauri::command]
 async fn set_web_tab_visible(
     app: tauri::AppHandle,
+    layout: tauri::State<'_, Mutex<CanvasLayoutState>>,
     tab_id: String,
     visible: bool,
-) -> Result<(), String> {
+) -> Result<(), WorkspaceError> {
     let webview = app
         .get_webview_window(&tab_id)
-        .ok_or("Web tab not found")?;
+        .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))?;
 
-    if visible {
-        webview.show().map_err(|e| format!("Failed to show: {}", e))?;
+    let show = layout.lock_or_recover().set_hidden(&tab_id, !visible);
+    if show {
+        webview.show().map_err(WorkspaceError::window_op("show"))?;
     } else {
-        webview.hide().map_err(|e| format!("Failed to hide: {}", e))?;
+        webview.hide().map_err(WorkspaceError::window_op("hide"))?;
     }
 
     Ok(())
@@ -326,23 +441,34 @@ async fn navigate_web_tab(
     state: tauri::State<'_, Mutex<WebTabState>>,
     tab_id: String,
     url: String,
-) -> Result<(), String> {
+) -> Result<(), WorkspaceError> {
     let webview = app
         .get_webview_window(&tab_id)
-        .ok_or("Web tab not found")?;
+        .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))?;
+
+    let parsed_url: tauri::Url = url.parse().map_err(WorkspaceError::invalid_url(&url))?;
+    policy::enforce(&app, &tab_id, &parsed_url, PolicyPhase::Navigate)?;
+    let url = parsed_url.to_string();
 
-    let parsed_url: tauri::Url = url.parse().map_err(|e| format!("Invalid URL: {}", e))?;
-    
     webview
         .navigate(parsed_url)
-        .map_err(|e| format!("Failed to navigate: {}", e))?;
+        .map_err(WorkspaceError::window_op("navigate"))?;
 
-    // URL in State aktualisieren
-    {
-        let mut tab_state = state.lock().unwrap();
-        if let Some(tab) = tab_state.tabs.get_mut(&tab_id) {
+    // URL und Verlauf in State aktualisieren
+    let history = {
+        let mut tab_state = state.lock_or_recover();
+        tab_state.tabs.get_mut(&tab_id).map(|tab| {
+            tab.history.push(&url);
             tab.url = url;
-        }
+            tab.title = None;
+            tab.last_navigated_at = Some(persistence::now_millis());
+            tab.history.clone()
+        })
+    };
+    snapshot::schedule_snapshot(&app);
+
+    if let Some(history) = history {
+        history::emit_history(&app, &tab_id, &history);
     }
 
     Ok(())
@@ -354,7 +480,7 @@ fn is_web_tab_fullscreen(
     state: tauri::State<'_, Mutex<WebTabState>>,
     tab_id: String,
 ) -> bool {
-    let tab_state = state.lock().unwrap();
+    let tab_state = state.lock_or_recover();
     tab_state.tabs.get(&tab_id).map(|t| t.is_fullscreen).unwrap_or(false)
 }
 
@@ -363,153 +489,39 @@ fn is_web_tab_fullscreen(
 async fn close_all_web_tabs(
     app: tauri::AppHandle,
     state: tauri::State<'_, Mutex<WebTabState>>,
-) -> Result<(), String> {
+) -> Result<(), WorkspaceError> {
     let tab_ids: Vec<String> = {
-        let tab_state = state.lock().unwrap();
+        let tab_state = state.lock_or_recover();
         tab_state.tabs.keys().cloned().collect()
     };
 
+    if tab_ids.is_empty() {
+        return Ok(());
+    }
+
     for tab_id in tab_ids {
         // Aus State entfernen
         {
-            let mut tab_state = state.lock().unwrap();
+            let mut tab_state = state.lock_or_recover();
             tab_state.tabs.remove(&tab_id);
         }
+        app.state::<Mutex<CanvasLayoutState>>().lock_or_recover().forget(&tab_id);
+        app.state::<ContentBlockingState>().forget(&tab_id);
         // Window schließen
         if let Some(webview) = app.get_webview_window(&tab_id) {
             webview.close().ok();
         }
     }
+    snapshot::schedule_snapshot(&app);
 
     Ok(())
 }
 
 /// Schließt alle verwaisten Webview-Fenster (die nicht im State sind)
 #[tauri::command]
-async fn close_all_orphaned_webviews(
-    app: tauri::AppHandle,
-    state: tauri::State<'_, Mutex<WebTabState>>,
-) -> Result<u32, String> {
-    let known_tab_ids: HashSet<String> = {
-        let tab_state = state.lock().unwrap();
-        tab_state.tabs.keys().cloned().collect()
-    };
-
-    let mut closed_count = 0;
-    
-    // Alle Webview-Fenster durchgehen
-    for window in app.webview_windows().values() {
-        let window_label = window.label();
-        
-        // Nur Fenster schließen die mit "webtab-" beginnen (unsere Web-Tabs)
-        // aber nicht "main" sind und nicht im State sind
-        if window_label.starts_with("webtab-") && !known_tab_ids.contains(window_label) {
-            window.close().ok();
-            closed_count += 1;
-        }
-    }
-
-    Ok(closed_count)
-}
-
-// ============================================================================
-// AI CONTENT EXTRACTION
-// ============================================================================
-
-#[derive(Clone, Serialize, Deserialize)]
-pub struct WebviewContent {
-    pub tab_id: String,
-    pub url: String,
-    pub title: Option<String>,
-    pub content: Option<String>,
-}
-
-/// Extrahiert Content aus einem Webview für AI-Analyse
-/// Hinweis: Content-Extraktion aus externen URLs ist limitiert durch Cross-Origin
-#[tauri::command]
-async fn extract_webview_content(
-    app: tauri::AppHandle,
-    _state: tauri::State<'_, Mutex<WebTabState>>,
-    tab_id: String,
-) -> Result<WebviewContent, String> {
-    let webview = app
-        .get_webview_window(&tab_id)
-        .ok_or("Web tab not found")?;
-
-    // URL aus Webview holen
-    let url = webview.url()
-        .map_err(|e| format!("Failed to get URL: {}", e))?
-        .to_string();
-
-    // Versuche Title zu extrahieren via eval
-    // Da eval() keinen Return-Wert hat, injizieren wir ein Script das den Title
-    // in den Window-Title setzt, den wir dann auslesen können
-    webview.eval("document.title && (document.title = document.title)")
-        .ok();
-
-    let title = webview.title().ok();
-
-    // Für Content-Extraktion: Versuche innerText zu bekommen
-    // Dies funktioniert nur wenn die Seite es erlaubt (Same-Origin oder permissive CORS)
-    // Wir nutzen einen Trick: Setze den Content temporär als data-attribute
-    let content_script = r#"
-        (function() {
-            try {
-                const text = document.body ? document.body.innerText.substring(0, 10000) : '';
-                document.documentElement.setAttribute('data-extracted-content', text);
-            } catch(e) {
-                document.documentElement.setAttribute('data-extracted-content', '');
-            }
-        })();
-    "#;
-
-    webview.eval(content_script).ok();
-
-    // Kurz warten damit Script ausgeführt wird
-    std::thread::sleep(std::time::Duration::from_millis(100));
-
-    // Content können wir leider nicht direkt auslesen ohne IPC
-    // Für MVP: Nur URL und Title zurückgeben, Content bleibt None
-    // Spätere Erweiterung: WebView IPC für Content-Extraktion
-
-    Ok(WebviewContent {
-        tab_id,
-        url,
-        title,
-        content: None, // TODO: Implement via IPC in future version
-    })
-}
-
-/// Extrahiert Content von allen offenen Webviews
-#[tauri::command]
-async fn extract_all_webview_contents(
-    app: tauri::AppHandle,
-    state: tauri::State<'_, Mutex<WebTabState>>,
-) -> Result<Vec<WebviewContent>, String> {
-    let tab_ids: Vec<String> = {
-        let tab_state = state.lock().unwrap();
-        tab_state.tabs.keys().cloned().collect()
-    };
-
-    let mut contents = Vec::new();
-
-    for tab_id in tab_ids {
-        if let Some(webview) = app.get_webview_window(&tab_id) {
-            let url = webview.url()
-                .map(|u| u.to_string())
-                .unwrap_or_default();
-            let title = webview.title().ok();
-
-            contents.push(WebviewContent {
-                tab_id,
-                url,
-                title,
-                content: None,
-            });
-        }
-    }
-
-    Ok(contents)
+async fn close_all_orphaned_webviews(app: tauri::AppHandle) -> Result<u32, WorkspaceError> {
+    let report = reconcile::reconcile(&app, None)?;
+    Ok(report.closed.len() as u32)
 }
 
 // ============================================================================
@@ -521,9 +533,9 @@ async fn extract_all_webview_contents(
 async fn bring_webviews_to_front(
     app: tauri::AppHandle,
     state: tauri::State<'_, Mutex<WebTabState>>,
-) -> Result<(), String> {
+) -> Result<(), WorkspaceError> {
     let tab_ids: Vec<String> = {
-        let tab_state = state.lock().unwrap();
+        let tab_state = state.lock_or_recover();
         tab_state.tabs.keys().cloned().collect()
     };
 
@@ -538,9 +550,9 @@ async fn bring_webviews_to_front(
 
 /// Fokussiert das Hauptfenster
 #[tauri::command]
-async fn focus_main_window(app: tauri::AppHandle) -> Result<(), String> {
+async fn focus_main_window(app: tauri::AppHandle) -> Result<(), WorkspaceError> {
     if let Some(main) = app.get_webview_window("main") {
-        main.set_focus().map_err(|e| format!("Failed to focus main window: {}", e))?;
+        main.set_focus().map_err(WorkspaceError::window_op("set_focus"))?;
     }
     Ok(())
 }
@@ -550,9 +562,9 @@ async fn focus_main_window(app: tauri::AppHandle) -> Result<(), String> {
 async fn bring_web_tab_to_front(
     app: tauri::AppHandle,
     tab_id: String,
-) -> Result<(), String> {
+) -> Result<(), WorkspaceError> {
     if let Some(window) = app.get_webview_window(&tab_id) {
-        window.set_focus().map_err(|e| format!("Failed to bring tab to front: {}", e))?;
+        window.set_focus().map_err(WorkspaceError::window_op("set_focus"))?;
     }
     Ok(())
 }
@@ -573,9 +585,9 @@ async fn open_webview_window(
     title: String,
     width: f64,
     height: f64,
-) -> Result<String, String> {
+) -> Result<String, WorkspaceError> {
     let window_id = format!("webview-{}", uuid::Uuid::new_v4());
-    let webview_url = WebviewUrl::External(url.parse().map_err(|e| format!("Invalid URL: {}", e))?);
+    let webview_url = WebviewUrl::External(url.parse().map_err(WorkspaceError::invalid_url(&url))?);
     
     WebviewWindowBuilder::new(&app, &window_id, webview_url)
         .title(&title)
@@ -584,57 +596,151 @@ async fn open_webview_window(
         .decorations(true)
         .center()
         .build()
-        .map_err(|e| format!("Failed to create window: {}", e))?;
+        .map_err(WorkspaceError::window_op("create_window"))?;
     
     Ok(window_id)
 }
 
 #[tauri::command]
-async fn close_webview_window(app: tauri::AppHandle, window_id: String) -> Result<(), String> {
+async fn close_webview_window(app: tauri::AppHandle, window_id: String) -> Result<(), WorkspaceError> {
     if let Some(window) = app.get_webview_window(&window_id) {
-        window.close().map_err(|e| format!("Failed to close window: {}", e))?;
+        window.close().map_err(WorkspaceError::window_op("close"))?;
     }
     Ok(())
 }
 
+// ============================================================================
+// COMMAND-REGISTRIERUNG
+// ============================================================================
+
+/// Registriert die App-Commands einmal für `generate_handler!` und das IPC-Audit
+macro_rules! app_commands {
+    ($($($segment:ident)::+),* $(,)?) => {
+        /// Alle registrierten Commands (mit Modulpfad, z.B. `history :: web_tab_go_back`)
+        #[cfg_attr(not(test), allow(dead_code))]
+        pub(crate) const APP_COMMANDS: &[&str] = &[$(stringify!($($segment)::+)),*];
+
+        fn app_command_handler<R: tauri::Runtime>() -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+            tauri::generate_handler![$($($segment)::+),*]
+        }
+    };
+}
+
+app_commands![
+    // Legacy
+    greet,
+    open_webview_window,
+    close_webview_window,
+    // Web Tab API
+    create_web_tab,
+    update_web_tab_bounds,
+    focus_web_tab,
+    unfocus_web_tabs,
+    fullscreen::set_web_tab_fullscreen,
+    close_web_tab,
+    close_all_web_tabs,
+    close_all_orphaned_webviews,
+    set_web_tab_visible,
+    navigate_web_tab,
+    is_web_tab_fullscreen,
+    restore_web_tabs,
+    // Canvas Layout
+    canvas::set_canvas_viewport,
+    canvas::set_web_tab_canvas_rect,
+    canvas::set_canvas_exclusion,
+    canvas::remove_canvas_exclusion,
+    // Web Tab History
+    history::web_tab_go_back,
+    history::web_tab_go_forward,
+    history::web_tab_reload,
+    history::web_tab_stop,
+    history::get_web_tab_history,
+    // URL Policy
+    policy::get_url_policy,
+    policy::set_url_policy,
+    policy::set_url_policy_board,
+    policy::check_web_tab_url,
+    // Content Blocking
+    content_blocking::reload_content_filters,
+    content_blocking::set_web_tab_content_blocking,
+    content_blocking::get_web_tab_content_blocking,
+    // Web Profiles
+    profiles::list_web_profiles,
+    profiles::delete_web_profile,
+    // Web Tab Thumbnails
+    thumbnail::capture_web_tab_thumbnail,
+    thumbnail::get_cached_web_tab_thumbnail,
+    // Web Tab Backends
+    backend::list_web_tab_backends,
+    backend::get_web_tab_backend,
+    backend::set_web_tab_backend,
+    // AI Content Extraction
+    content::extract_webview_content,
+    content::extract_all_webview_contents,
+    content::report_webview_content,
+    // Board Persistence
+    persistence::save_board,
+    persistence::load_board,
+    persistence::list_boards,
+    persistence::delete_board,
+    persistence::rename_board,
+    persistence::migrate_local_storage_boards,
+    // Window Management
+    bring_webviews_to_front,
+    focus_main_window,
+    bring_web_tab_to_front,
+];
+
 // ============================================================================
 // APP ENTRY
 // ============================================================================
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
-    tauri::Builder::default()
-        .plugin(tauri_plugin_opener::init())
-        .manage(Mutex::new(WebTabState {
-            tabs: HashMap::new(),
-        }))
-        .invoke_handler(tauri::generate_handler![
-            // Legacy
-            greet,
-            open_webview_window,
-            close_webview_window,
-            // Web Tab API
-            create_web_tab,
-            update_web_tab_bounds,
-            focus_web_tab,
-            unfocus_web_tabs,
-            set_web_tab_fullscreen,
-            close_web_tab,
-            close_all_web_tabs,
-            close_all_orphaned_webviews,
-            set_web_tab_visible,
-            navigate_web_tab,
-            is_web_tab_fullscreen,
-            // AI Content Extraction
-            extract_webview_content,
-            extract_all_webview_contents,
-            // Window Management
-            bring_webviews_to_front,
-            focus_main_window,
-            bring_web_tab_to_front,
-        ])
-        // Native File Drag-and-Drop Handler
+    // Logs aus Host und cef-plugin nach stdout und ins Log-Verzeichnis der App
+    let builder = tauri::Builder::default()
+        .plugin(
+            tauri_plugin_log::Builder::new()
+                .level(log::LevelFilter::Info)
+                .build(),
+        )
+        .plugin(tauri_plugin_opener::init());
+
+    // OSR-Browser nur wenn mit `--features cef` gebaut
+    #[cfg(feature = "cef")]
+    let builder = builder.plugin(cef_plugin::init());
+
+    builder
+        .manage(Mutex::new(WebTabState::default()))
+        .manage(ContentExtractionState::default())
+        .manage(TabSnapshotState::default())
+        .manage(WebTabBackendState::default())
+        .manage(Mutex::new(CanvasLayoutState::default()))
+        .manage(reconcile::ReconcileState::default())
+        .manage(UrlPolicyState::default())
+        .manage(ContentBlockingState::default())
+        // Externe Seiten in Web-Tabs erreichen nur ihre Rückkanäle
+        .invoke_handler(ipc_access::guard(app_command_handler()))
+        // Fenster, State und Snapshot abgleichen, sobald das Hauptfenster geladen ist
+        .on_page_load(|webview, payload| {
+            if webview.label() == ipc_access::MAIN_WINDOW && payload.event() == PageLoadEvent::Finished {
+                let app = webview.app_handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    match reconcile::reconcile(&app, None) {
+                        Ok(report) if !report.is_empty() => log::info!("Reconciled web tabs: {:?}", report),
+                        Ok(_) => {}
+                        Err(e) => log::error!("Failed to reconcile web tabs: {}", e),
+                    }
+                });
+            }
+        })
         .on_window_event(|window, event| {
+            // Web-Tabs folgen dem Hauptfenster (verschieben, skalieren, minimieren)
+            if window.label() == "main" {
+                canvas::handle_main_window_event(window.app_handle(), event);
+            }
+
+            // Native File Drag-and-Drop Handler
             if let tauri::WindowEvent::DragDrop(drag_event) = event {
                 if let DragDropEvent::Drop { paths, position } = drag_event {
                     // Konvertiere PathBuf zu Strings
@@ -653,35 +759,28 @@ pub fn run() {
             }
         })
         .setup(|app| {
-            // Beim App-Start alle verwaisten Webview-Fenster schließen
-            let app_handle = app.handle().clone();
-            
-            // Cleanup nach kurzer Verzögerung (damit alle Fenster initialisiert sind)
-            std::thread::spawn(move || {
-                std::thread::sleep(std::time::Duration::from_millis(500));
-                
-                // State aus AppHandle holen
-                let known_tab_ids: HashSet<String> = {
-                    if let Some(state) = app_handle.try_state::<Mutex<WebTabState>>() {
-                        let tab_state = state.lock().unwrap();
-                        tab_state.tabs.keys().cloned().collect()
-                    } else {
-                        HashSet::new()
-                    }
-                };
-                
-                // Alle Webview-Fenster durchgehen
-                for window in app_handle.webview_windows().values() {
-                    let window_label = window.label();
-                    
-                    // Nur Fenster schließen die mit "webtab-" beginnen (unsere Web-Tabs)
-                    // aber nicht "main" sind und nicht im State sind
-                    if window_label.starts_with("webtab-") && !known_tab_ids.contains(window_label) {
-                        window.close().ok();
-                    }
-                }
-            });
-            
+            // Das Hauptfenster darf die cef-plugin Commands aufrufen
+            #[cfg(feature = "cef")]
+            {
+                app.add_capability(
+                    tauri::ipc::CapabilityBuilder::new("cef")
+                        .window("main")
+                        .permission("cef:default"),
+                )?;
+
+                // OSR-Browser laufen durch dieselben Filterlisten wie native Tabs
+                let filter = content_blocking::CefRequestFilter::new(app.handle().clone());
+                app.state::<cef_plugin::CefPluginState>()
+                    .set_request_filter(Some(std::sync::Arc::new(filter)));
+
+                // ... und durch die URL-Policy, auch bei Links und Redirects
+                let navigation_filter = policy::CefNavigationFilter::new(app.handle().clone());
+                app.state::<cef_plugin::CefPluginState>()
+                    .set_navigation_filter(Some(std::sync::Arc::new(navigation_filter)));
+            }
+
+            // Filterlisten einlesen ohne den Start aufzuhalten
+            content_blocking::load_in_background(app.handle());
             Ok(())
         })
         .run(tauri::generate_context!())
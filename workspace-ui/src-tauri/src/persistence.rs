@@ -0,0 +1,425 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+
+// ============================================================================
+// BOARD PERSISTENCE - Dateibasiert im App-Data-Verzeichnis
+// ============================================================================
+
+/// Aktuelle Version des Dateiformats
+pub const BOARD_SCHEMA_VERSION: u32 = 1;
+
+/// Marker-Datei: LocalStorage-Migration wurde bereits ausgeführt
+const MIGRATION_MARKER: &str = ".localstorage-migrated";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Viewport {
+    pub x: f64,
+    pub y: f64,
+    pub scale: f64,
+}
+
+/// Board wie im Frontend (`models/board.ts`) - Items und Connections bleiben
+/// als JSON, damit das Frontend-Modell ohne Rust-Änderung wachsen kann
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Board {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub items: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub connections: Vec<serde_json::Value>,
+    pub viewport: Viewport,
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardMetadata {
+    pub id: String,
+    pub name: String,
+    pub item_count: usize,
+    pub updated_at: u64,
+}
+
+impl From<&Board> for BoardMetadata {
+    fn from(board: &Board) -> Self {
+        Self {
+            id: board.id.clone(),
+            name: board.name.clone(),
+            item_count: board.items.len(),
+            updated_at: board.updated_at,
+        }
+    }
+}
+
+/// Ergebnis der LocalStorage-Migration
+#[derive(Clone, Debug, Default, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    pub imported: u32,
+    /// Nicht übernommene Boards (kaputt oder Zieldatei unlesbar) - das Frontend
+    /// behält sie im LocalStorage, die Migration läuft beim nächsten Start erneut
+    pub skipped: Vec<String>,
+}
+
+/// Dateiinhalt: Board plus Schema-Version
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredBoard {
+    schema_version: u32,
+    #[serde(flatten)]
+    board: Board,
+}
+
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Board-Speicher in einem Verzeichnis (eine JSON-Datei pro Board)
+pub struct BoardStore {
+    dir: PathBuf,
+}
+
+impl BoardStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Board-Store im App-Data-Verzeichnis
+    pub fn for_app(app: &AppHandle) -> Result<Self, String> {
+        let dir = app
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to resolve app data dir: {}", e))?
+            .join("boards");
+        Ok(Self::new(dir))
+    }
+
+    fn board_path(&self, board_id: &str) -> Result<PathBuf, String> {
+        // IDs landen im Dateinamen - nur harmlose Zeichen zulassen
+        let valid = !board_id.is_empty()
+            && board_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(format!("Invalid board id: {}", board_id));
+        }
+        Ok(self.dir.join(format!("{}.json", board_id)))
+    }
+
+    fn ensure_dir(&self) -> Result<(), String> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create board directory: {}", e))
+    }
+
+    pub fn save(&self, mut board: Board) -> Result<BoardMetadata, String> {
+        let path = self.board_path(&board.id)?;
+        self.ensure_dir()?;
+
+        // createdAt vom bestehenden Board übernehmen
+        if let Ok(Some(existing)) = self.load(&board.id) {
+            board.created_at = existing.created_at;
+        }
+
+        let metadata = BoardMetadata::from(&board);
+        let stored = StoredBoard {
+            schema_version: BOARD_SCHEMA_VERSION,
+            board,
+        };
+        let json = serde_json::to_vec_pretty(&stored)
+            .map_err(|e| format!("Failed to serialize board: {}", e))?;
+
+        write_atomic(&path, &json)?;
+        Ok(metadata)
+    }
+
+    pub fn load(&self, board_id: &str) -> Result<Option<Board>, String> {
+        let path = self.board_path(board_id)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let data = fs::read(&path).map_err(|e| format!("Failed to read board: {}", e))?;
+        let stored: StoredBoard = serde_json::from_slice(&data)
+            .map_err(|e| format!("Failed to parse board {}: {}", board_id, e))?;
+
+        if stored.schema_version > BOARD_SCHEMA_VERSION {
+            return Err(format!(
+                "Board {} uses schema version {} (supported: {})",
+                board_id, stored.schema_version, BOARD_SCHEMA_VERSION
+            ));
+        }
+
+        Ok(Some(stored.board))
+    }
+
+    /// Alle Boards, neueste zuerst
+    pub fn list(&self) -> Result<Vec<BoardMetadata>, String> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(format!("Failed to read board directory: {}", e)),
+        };
+
+        let mut boards: Vec<BoardMetadata> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "json" {
+                    return None;
+                }
+                let board_id = path.file_stem()?.to_str()?.to_string();
+                // Kaputte Dateien überspringen statt die ganze Liste zu verlieren
+                self.load(&board_id).ok().flatten().map(|b| BoardMetadata::from(&b))
+            })
+            .collect();
+
+        boards.sort_by_key(|b| std::cmp::Reverse(b.updated_at));
+        Ok(boards)
+    }
+
+    pub fn delete(&self, board_id: &str) -> Result<(), String> {
+        let path = self.board_path(board_id)?;
+        match fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("Failed to delete board: {}", e)),
+        }
+    }
+
+    pub fn rename(&self, board_id: &str, name: &str) -> Result<BoardMetadata, String> {
+        let mut board = self
+            .load(board_id)?
+            .ok_or_else(|| format!("Board {} not found", board_id))?;
+        board.name = name.to_string();
+        board.updated_at = now_millis();
+        self.save(board)
+    }
+
+    /// Importiert LocalStorage-Boards, bis alle übernommen sind. Bereits
+    /// vorhandene Boards werden nicht überschrieben. Jedes Board wird einzeln
+    /// gelesen - ein kaputtes hält die übrigen nicht auf.
+    pub fn migrate(
+        &self,
+        boards: HashMap<String, serde_json::Value>,
+    ) -> Result<MigrationReport, String> {
+        let marker = self.dir.join(MIGRATION_MARKER);
+        if marker.exists() {
+            return Ok(MigrationReport::default());
+        }
+
+        let mut report = MigrationReport::default();
+        for (key, value) in boards {
+            let board: Board = match serde_json::from_value(value) {
+                Ok(board) => board,
+                Err(e) => {
+                    log::warn!("Skipping malformed board {} during migration: {}", key, e);
+                    report.skipped.push(key);
+                    continue;
+                }
+            };
+
+            match self.load(&board.id) {
+                Ok(None) => match self.save(board) {
+                    Ok(_) => report.imported += 1,
+                    Err(e) => {
+                        log::warn!("Skipping board {} during migration: {}", key, e);
+                        report.skipped.push(key);
+                    }
+                },
+                Ok(Some(_)) => {}
+                Err(e) => {
+                    log::warn!("Skipping board {} during migration: {}", key, e);
+                    report.skipped.push(key);
+                }
+            }
+        }
+        report.skipped.sort();
+
+        // Erst als erledigt markieren, wenn nichts mehr offen ist
+        if report.skipped.is_empty() {
+            self.ensure_dir()?;
+            write_atomic(&marker, now_millis().to_string().as_bytes())?;
+        }
+        Ok(report)
+    }
+}
+
+/// Schreibt erst in eine Temp-Datei und benennt dann um, damit ein Absturz
+/// nie eine halb geschriebene Board-Datei hinterlässt
+pub(crate) fn write_atomic(path: &Path, data: &[u8]) -> Result<(), String> {
+    let tmp_path = path.with_extension("tmp");
+
+    let mut file =
+        fs::File::create(&tmp_path).map_err(|e| format!("Failed to create temp file: {}", e))?;
+    file.write_all(data)
+        .and_then(|_| file.sync_all())
+        .map_err(|e| format!("Failed to write temp file: {}", e))?;
+    drop(file);
+
+    fs::rename(&tmp_path, path).map_err(|e| format!("Failed to replace file: {}", e))
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Führt einen Board-Store-Aufruf auf einem Blocking-Thread aus - Dateizugriffe
+/// mit fsync gehören nicht auf den Main- oder einen Async-Worker-Thread
+async fn with_store<T, F>(app: AppHandle, op: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce(BoardStore) -> Result<T, String> + Send + 'static,
+{
+    let store = BoardStore::for_app(&app)?;
+    tauri::async_runtime::spawn_blocking(move || op(store))
+        .await
+        .map_err(|e| format!("Board storage task failed: {}", e))?
+}
+
+#[tauri::command]
+pub async fn save_board(app: AppHandle, board: Board) -> Result<BoardMetadata, String> {
+    with_store(app, move |store| store.save(board)).await
+}
+
+#[tauri::command]
+pub async fn load_board(app: AppHandle, board_id: String) -> Result<Option<Board>, String> {
+    with_store(app, move |store| store.load(&board_id)).await
+}
+
+#[tauri::command]
+pub async fn list_boards(app: AppHandle) -> Result<Vec<BoardMetadata>, String> {
+    with_store(app, |store| store.list()).await
+}
+
+#[tauri::command]
+pub async fn delete_board(app: AppHandle, board_id: String) -> Result<(), String> {
+    with_store(app, move |store| store.delete(&board_id)).await
+}
+
+#[tauri::command]
+pub async fn rename_board(app: AppHandle, board_id: String, name: String) -> Result<BoardMetadata, String> {
+    with_store(app, move |store| store.rename(&board_id, &name)).await
+}
+
+/// Migration der alten LocalStorage-Boards (Boards roh, damit ein kaputtes
+/// nicht die ganze Anfrage scheitern lässt)
+#[tauri::command]
+pub async fn migrate_local_storage_boards(
+    app: AppHandle,
+    boards: HashMap<String, serde_json::Value>,
+) -> Result<MigrationReport, String> {
+    with_store(app, move |store| store.migrate(boards)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store() -> BoardStore {
+        let dir = std::env::temp_dir().join(format!("workspace-boards-{}", uuid::Uuid::new_v4()));
+        BoardStore::new(dir)
+    }
+
+    fn board(id: &str, updated_at: u64) -> Board {
+        Board {
+            id: id.to_string(),
+            name: format!("Board {}", id),
+            items: vec![serde_json::json!({ "id": "item_1", "content": "Hallo" })],
+            connections: Vec::new(),
+            viewport: Viewport { x: 0.0, y: 0.0, scale: 1.0 },
+            created_at: 1,
+            updated_at,
+        }
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let store = temp_store();
+        store.save(board("b1", 10)).unwrap();
+
+        let loaded = store.load("b1").unwrap().unwrap();
+        assert_eq!(loaded, board("b1", 10));
+        assert!(!store.dir.join("b1.tmp").exists());
+
+        let raw: serde_json::Value =
+            serde_json::from_slice(&fs::read(store.dir.join("b1.json")).unwrap()).unwrap();
+        assert_eq!(raw["schemaVersion"], BOARD_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_list_sorted_by_updated_at() {
+        let store = temp_store();
+        store.save(board("old", 10)).unwrap();
+        store.save(board("new", 20)).unwrap();
+
+        let ids: Vec<String> = store.list().unwrap().into_iter().map(|b| b.id).collect();
+        assert_eq!(ids, vec!["new", "old"]);
+    }
+
+    #[test]
+    fn test_rejects_path_traversal() {
+        let store = temp_store();
+        assert!(store.load("../secret").is_err());
+    }
+
+    fn legacy(boards: &[Board]) -> HashMap<String, serde_json::Value> {
+        boards
+            .iter()
+            .map(|b| (b.id.clone(), serde_json::to_value(b).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_migration_runs_once() {
+        let store = temp_store();
+        let boards = legacy(&[board("b1", 10)]);
+
+        assert_eq!(store.migrate(boards.clone()).unwrap().imported, 1);
+        assert_eq!(store.migrate(boards).unwrap(), MigrationReport::default());
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_migration_skips_corrupt_board() {
+        let store = temp_store();
+        store.ensure_dir().unwrap();
+        fs::write(store.dir.join("broken.json"), b"{ not json").unwrap();
+
+        let boards = legacy(&[board("broken", 10), board("b1", 10)]);
+
+        let report = store.migrate(boards.clone()).unwrap();
+        assert_eq!(report.imported, 1);
+        assert_eq!(report.skipped, vec!["broken".to_string()]);
+        assert!(store.load("b1").unwrap().is_some());
+        // Kaputte Datei bleibt unangetastet
+        assert_eq!(fs::read(store.dir.join("broken.json")).unwrap(), b"{ not json");
+
+        // Nicht als erledigt markiert: der nächste Start versucht es erneut
+        fs::remove_file(store.dir.join("broken.json")).unwrap();
+        let report = store.migrate(boards).unwrap();
+        assert_eq!(report, MigrationReport { imported: 1, skipped: Vec::new() });
+        assert_eq!(store.migrate(legacy(&[board("late", 10)])).unwrap().imported, 0);
+    }
+
+    #[test]
+    fn test_migration_parses_each_board_separately() {
+        let store = temp_store();
+        let mut boards = legacy(&[board("b1", 10)]);
+        boards.insert("bad".to_string(), serde_json::json!({ "id": "bad", "viewport": "nope" }));
+
+        let report = store.migrate(boards).unwrap();
+        assert_eq!(report, MigrationReport { imported: 1, skipped: vec!["bad".to_string()] });
+        assert!(store.load("b1").unwrap().is_some());
+    }
+}
@@ -0,0 +1,528 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, Url};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::error::{LockExt, WorkspaceError};
+use crate::persistence::write_atomic;
+
+// ============================================================================
+// URL POLICY - Welche Seiten Web-Tabs laden dürfen
+// ============================================================================
+//
+// Geprüft beim Erstellen, bei `navigate_web_tab` und bei jeder Navigation
+// innerhalb der Seite (Links, Redirects, Frames). Im System-Browser öffnet
+// `open_externally` nur, was der Nutzer selbst angestoßen hat - alles andere
+// wird nur abgebrochen. Native Tabs können das nicht unterscheiden (wry meldet
+// keine Nutzer-Geste); dort bietet die Tab-Karte das Öffnen per Klick an.
+// Reihenfolge pro Regel-Ebene:
+// deny -> open_externally -> allow. Board-Regeln werden vor den globalen
+// geprüft und überschreiben sie für passende Hosts.
+//
+// Host-Muster: `example.com` passt auf die Domain und alle Subdomains,
+// `*.example.com` nur auf Subdomains, `*` auf alles.
+
+/// Welche Regel gegriffen hat
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyRule {
+    /// Schema nicht erlaubt (z.B. `file`, `javascript`)
+    Scheme,
+    Deny,
+    /// Allowlist aktiv, aber kein Muster passt
+    NotAllowlisted,
+    /// Wird im System-Browser statt im Tab geöffnet
+    OpenExternally,
+}
+
+impl PolicyRule {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PolicyRule::Scheme => "scheme",
+            PolicyRule::Deny => "deny",
+            PolicyRule::NotAllowlisted => "not_allowlisted",
+            PolicyRule::OpenExternally => "open_externally",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PolicyViolation {
+    pub rule: PolicyRule,
+    /// Schema oder passendes Host-Muster
+    pub detail: Option<String>,
+}
+
+impl PolicyViolation {
+    fn new(rule: PolicyRule, detail: Option<&str>) -> Self {
+        Self {
+            rule,
+            detail: detail.map(str::to_string),
+        }
+    }
+}
+
+/// Host-Regeln einer Ebene (global oder Board)
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct HostRules {
+    /// Nicht leer = nur diese Hosts (Allowlist)
+    #[serde(default)]
+    pub allow: Vec<String>,
+    #[serde(default)]
+    pub deny: Vec<String>,
+    #[serde(default)]
+    pub open_externally: Vec<String>,
+}
+
+impl HostRules {
+    /// `None` wenn keine Regel dieser Ebene passt
+    fn decide(&self, host: &str) -> Option<Result<(), PolicyViolation>> {
+        if let Some(pattern) = find_match(&self.deny, host) {
+            return Some(Err(PolicyViolation::new(PolicyRule::Deny, Some(pattern))));
+        }
+        if let Some(pattern) = find_match(&self.open_externally, host) {
+            return Some(Err(PolicyViolation::new(PolicyRule::OpenExternally, Some(pattern))));
+        }
+        find_match(&self.allow, host).map(|_| Ok(()))
+    }
+
+    fn patterns(&self) -> impl Iterator<Item = &String> {
+        self.allow.iter().chain(&self.deny).chain(&self.open_externally)
+    }
+}
+
+/// Überschreibungen für ein Board
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct BoardPolicy {
+    /// Ersetzt die globalen Schemata (None = global übernehmen)
+    #[serde(default)]
+    pub allowed_schemes: Option<Vec<String>>,
+    #[serde(flatten)]
+    pub rules: HostRules,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct UrlPolicy {
+    #[serde(default = "default_schemes")]
+    pub allowed_schemes: Vec<String>,
+    #[serde(flatten)]
+    pub rules: HostRules,
+    /// Board-ID -> Überschreibungen
+    #[serde(default)]
+    pub boards: HashMap<String, BoardPolicy>,
+}
+
+fn default_schemes() -> Vec<String> {
+    vec!["https".to_string(), "http".to_string()]
+}
+
+impl Default for UrlPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_schemes: default_schemes(),
+            rules: HostRules::default(),
+            boards: HashMap::new(),
+        }
+    }
+}
+
+impl UrlPolicy {
+    /// Darf `url` auf dem Board `board_id` in einem Web-Tab geladen werden?
+    pub fn evaluate(&self, url: &Url, board_id: Option<&str>) -> Result<(), PolicyViolation> {
+        // Leere Seite beim Erstellen und nach `stop` - harmlos
+        if url.as_str() == "about:blank" {
+            return Ok(());
+        }
+
+        let board = board_id.and_then(|id| self.boards.get(id));
+        let schemes = board
+            .and_then(|b| b.allowed_schemes.as_ref())
+            .unwrap_or(&self.allowed_schemes);
+        if !schemes.iter().any(|s| s.eq_ignore_ascii_case(url.scheme())) {
+            return Err(PolicyViolation::new(PolicyRule::Scheme, Some(url.scheme())));
+        }
+
+        let host = url.host_str().unwrap_or("");
+        let layers: Vec<&HostRules> = board.map(|b| &b.rules).into_iter().chain([&self.rules]).collect();
+        for rules in &layers {
+            if let Some(result) = rules.decide(host) {
+                return result;
+            }
+        }
+
+        if layers.iter().any(|rules| !rules.allow.is_empty()) {
+            return Err(PolicyViolation::new(PolicyRule::NotAllowlisted, None));
+        }
+        Ok(())
+    }
+
+    /// Prüft Schemata und Host-Muster vor dem Speichern
+    pub fn validate(&self) -> Result<(), WorkspaceError> {
+        let boards = self.boards.values();
+        let scheme_lists = std::iter::once(&self.allowed_schemes)
+            .chain(boards.clone().filter_map(|b| b.allowed_schemes.as_ref()));
+        for scheme in scheme_lists.flatten() {
+            let valid = scheme.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+            if !valid {
+                return Err(WorkspaceError::InvalidInput(format!("Invalid URL scheme: {}", scheme)));
+            }
+        }
+
+        let patterns = self.rules.patterns().chain(boards.flat_map(|b| b.rules.patterns()));
+        for pattern in patterns {
+            if !is_valid_pattern(pattern) {
+                return Err(WorkspaceError::InvalidInput(format!("Invalid host pattern: {}", pattern)));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn is_valid_pattern(pattern: &str) -> bool {
+    let host = pattern.strip_prefix("*.").unwrap_or(pattern);
+    pattern == "*"
+        || (!host.is_empty()
+            && host
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_')))
+}
+
+/// Passt das Host-Muster auf `host`?
+pub fn host_matches(pattern: &str, host: &str) -> bool {
+    let pattern = pattern.trim_end_matches('.').to_ascii_lowercase();
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    if pattern == "*" {
+        return true;
+    }
+    if host.is_empty() {
+        return false;
+    }
+    match pattern.strip_prefix("*.") {
+        Some(domain) => host.ends_with(&format!(".{}", domain)),
+        None => host == pattern || host.ends_with(&format!(".{}", pattern)),
+    }
+}
+
+fn find_match<'a>(patterns: &'a [String], host: &str) -> Option<&'a str> {
+    patterns
+        .iter()
+        .find(|pattern| host_matches(pattern, host))
+        .map(String::as_str)
+}
+
+// ============================================================================
+// STATE & DURCHSETZUNG
+// ============================================================================
+
+/// Geladene Policy (lazy von der Platte) und das aktive Board
+#[derive(Default)]
+pub struct UrlPolicyState {
+    policy: Mutex<Option<UrlPolicy>>,
+    active_board: Mutex<Option<String>>,
+}
+
+/// Wo die Verletzung auftrat
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyPhase {
+    Create,
+    Navigate,
+    /// Link-Klick oder Formular des Nutzers im Hauptframe
+    InPage,
+    /// Redirect, Script- oder Frame-Navigation - und alles, wofür die WebView
+    /// keine Herkunft meldet (native Tabs)
+    InPageAutomatic,
+}
+
+impl PolicyPhase {
+    /// Darf `open_externally` hier den System-Browser öffnen? Sonst öffnete
+    /// jeder Redirect und jedes eingebettete Frame ein neues Browserfenster.
+    pub fn opens_externally(self) -> bool {
+        self != PolicyPhase::InPageAutomatic
+    }
+}
+
+/// Payload von `web-tab-policy-violation`
+#[derive(Clone, Serialize)]
+pub struct PolicyViolationEvent {
+    pub tab_id: String,
+    pub url: String,
+    pub rule: PolicyRule,
+    pub detail: Option<String>,
+    pub board_id: Option<String>,
+    pub phase: PolicyPhase,
+}
+
+fn policy_path(app: &AppHandle) -> Result<PathBuf, WorkspaceError> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(WorkspaceError::io("resolve_app_data_dir"))?
+        .join("url-policy.json"))
+}
+
+fn read_policy(app: &AppHandle) -> Result<UrlPolicy, WorkspaceError> {
+    let data = match std::fs::read(policy_path(app)?) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(UrlPolicy::default()),
+        Err(e) => return Err(WorkspaceError::io("read_url_policy")(e)),
+    };
+    serde_json::from_slice(&data).map_err(WorkspaceError::io("parse_url_policy"))
+}
+
+/// Aktuelle Policy - kaputte Datei fällt auf die Standard-Policy zurück
+fn current_policy(app: &AppHandle) -> UrlPolicy {
+    let state = app.state::<UrlPolicyState>();
+    let mut policy = state.policy.lock_or_recover();
+    policy
+        .get_or_insert_with(|| {
+            read_policy(app).unwrap_or_else(|e| {
+                log::warn!("Failed to load URL policy, using defaults: {}", e);
+                UrlPolicy::default()
+            })
+        })
+        .clone()
+}
+
+fn active_board(app: &AppHandle) -> Option<String> {
+    app.state::<UrlPolicyState>().active_board.lock_or_recover().clone()
+}
+
+/// Prüft `url` gegen die Policy des aktiven Boards (ohne Seiteneffekte)
+pub fn evaluate_for_app(app: &AppHandle, url: &Url) -> Result<(), PolicyViolation> {
+    current_policy(app).evaluate(url, active_board(app).as_deref())
+}
+
+/// Prüft `url` und setzt die Policy durch: Event an das Frontend,
+/// `open_externally` im System-Browser öffnen (nur für Nutzer-Navigationen)
+pub fn enforce(app: &AppHandle, tab_id: &str, url: &Url, phase: PolicyPhase) -> Result<(), WorkspaceError> {
+    let board_id = active_board(app);
+    let Err(violation) = current_policy(app).evaluate(url, board_id.as_deref()) else {
+        return Ok(());
+    };
+
+    if violation.rule == PolicyRule::OpenExternally && phase.opens_externally() {
+        if let Err(e) = app.opener().open_url(url.as_str(), None::<&str>) {
+            log::error!("Failed to open {} externally: {}", url, e);
+        }
+    }
+
+    app.emit(
+        "web-tab-policy-violation",
+        PolicyViolationEvent {
+            tab_id: tab_id.to_string(),
+            url: url.to_string(),
+            rule: violation.rule,
+            detail: violation.detail,
+            board_id,
+            phase,
+        },
+    )
+    .ok();
+
+    Err(WorkspaceError::PolicyViolation {
+        url: url.to_string(),
+        rule: violation.rule.as_str(),
+    })
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Liefert die aktuelle URL-Policy
+#[tauri::command]
+pub fn get_url_policy(app: AppHandle) -> UrlPolicy {
+    current_policy(&app)
+}
+
+/// Speichert eine neue URL-Policy (gilt ab der nächsten Navigation)
+#[tauri::command]
+pub fn set_url_policy(
+    app: AppHandle,
+    state: tauri::State<'_, UrlPolicyState>,
+    policy: UrlPolicy,
+) -> Result<(), WorkspaceError> {
+    policy.validate()?;
+
+    let path = policy_path(&app)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(WorkspaceError::io("create_app_data_dir"))?;
+    }
+    let json = serde_json::to_vec_pretty(&policy).map_err(WorkspaceError::io("serialize_url_policy"))?;
+    write_atomic(&path, &json).map_err(WorkspaceError::io("write_url_policy"))?;
+
+    *state.policy.lock_or_recover() = Some(policy);
+    Ok(())
+}
+
+/// Setzt das Board, dessen Überschreibungen gelten (None = nur global)
+#[tauri::command]
+pub fn set_url_policy_board(state: tauri::State<'_, UrlPolicyState>, board_id: Option<String>) {
+    *state.active_board.lock_or_recover() = board_id;
+}
+
+/// Prüft eine URL für Tabs, die nicht über `create_web_tab` laufen (CEF)
+#[tauri::command]
+pub fn check_web_tab_url(app: AppHandle, tab_id: String, url: String) -> Result<(), WorkspaceError> {
+    let parsed: Url = url.parse().map_err(WorkspaceError::invalid_url(&url))?;
+    enforce(&app, &tab_id, &parsed, PolicyPhase::Navigate)
+}
+
+/// Navigations-Filter für die OSR-Browser des cef-plugins (Browser-ID = Tab-ID)
+#[cfg(feature = "cef")]
+pub struct CefNavigationFilter {
+    app: AppHandle,
+}
+
+#[cfg(feature = "cef")]
+impl CefNavigationFilter {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+#[cfg(feature = "cef")]
+impl cef_plugin::NavigationFilter for CefNavigationFilter {
+    fn allow(&self, request: &cef_plugin::NavigationRequest<'_>) -> bool {
+        use cef_plugin::NavigationKind as Cef;
+
+        let phase = match request.kind {
+            Cef::Create => PolicyPhase::Create,
+            Cef::Navigate => PolicyPhase::Navigate,
+            Cef::UserInitiated => PolicyPhase::InPage,
+            Cef::Automatic => PolicyPhase::InPageAutomatic,
+        };
+        // Nicht parsebare URLs lädt auch CEF nicht
+        let Ok(url) = request.url.parse::<Url>() else {
+            return true;
+        };
+        enforce(&self.app, request.browser_id, &url, phase).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(value: &str) -> Url {
+        value.parse().unwrap()
+    }
+
+    fn rule(policy: &UrlPolicy, value: &str, board: Option<&str>) -> Option<PolicyRule> {
+        policy.evaluate(&url(value), board).err().map(|v| v.rule)
+    }
+
+    #[test]
+    fn test_host_patterns() {
+        assert!(host_matches("example.com", "example.com"));
+        assert!(host_matches("example.com", "docs.example.com"));
+        assert!(!host_matches("example.com", "badexample.com"));
+        assert!(!host_matches("*.example.com", "example.com"));
+        assert!(host_matches("*.example.com", "a.b.example.com"));
+        assert!(host_matches("Example.COM.", "example.com"));
+        assert!(host_matches("*", "anything.test"));
+        assert!(!host_matches("example.com", ""));
+    }
+
+    #[test]
+    fn test_default_policy_only_allows_web_schemes() {
+        let policy = UrlPolicy::default();
+        assert_eq!(rule(&policy, "https://notion.so/", None), None);
+        assert_eq!(rule(&policy, "http://localhost:3000/", None), None);
+        assert_eq!(rule(&policy, "about:blank", None), None);
+        assert_eq!(rule(&policy, "file:///etc/passwd", None), Some(PolicyRule::Scheme));
+        assert_eq!(rule(&policy, "javascript:alert(1)", None), Some(PolicyRule::Scheme));
+        assert_eq!(rule(&policy, "data:text/html,hi", None), Some(PolicyRule::Scheme));
+    }
+
+    #[test]
+    fn test_deny_open_externally_and_allowlist() {
+        let policy = UrlPolicy {
+            rules: HostRules {
+                allow: vec!["example.com".into(), "*.figma.com".into()],
+                deny: vec!["ads.example.com".into()],
+                open_externally: vec!["mail.example.com".into()],
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(rule(&policy, "https://example.com/", None), None);
+        assert_eq!(rule(&policy, "https://www.figma.com/file/1", None), None);
+        assert_eq!(rule(&policy, "https://ads.example.com/x", None), Some(PolicyRule::Deny));
+        assert_eq!(
+            rule(&policy, "https://mail.example.com/", None),
+            Some(PolicyRule::OpenExternally)
+        );
+        assert_eq!(rule(&policy, "https://figma.com/", None), Some(PolicyRule::NotAllowlisted));
+
+        let violation = policy.evaluate(&url("https://ads.example.com/"), None).unwrap_err();
+        assert_eq!(violation.detail.as_deref(), Some("ads.example.com"));
+    }
+
+    #[test]
+    fn test_only_user_navigations_open_externally() {
+        assert!(PolicyPhase::Create.opens_externally());
+        assert!(PolicyPhase::Navigate.opens_externally());
+        assert!(PolicyPhase::InPage.opens_externally());
+        assert!(!PolicyPhase::InPageAutomatic.opens_externally());
+        assert_eq!(serde_json::to_value(PolicyPhase::InPageAutomatic).unwrap(), "in_page_automatic");
+    }
+
+    #[test]
+    fn test_board_overrides_global_rules() {
+        let mut policy = UrlPolicy {
+            rules: HostRules {
+                deny: vec!["youtube.com".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        policy.boards.insert(
+            "video".into(),
+            BoardPolicy {
+                allowed_schemes: Some(vec!["https".into()]),
+                rules: HostRules {
+                    allow: vec!["youtube.com".into()],
+                    ..Default::default()
+                },
+            },
+        );
+
+        assert_eq!(rule(&policy, "https://youtube.com/", None), Some(PolicyRule::Deny));
+        assert_eq!(rule(&policy, "https://youtube.com/", Some("video")), None);
+        // Board-Allowlist gilt nur auf dem Board
+        assert_eq!(rule(&policy, "https://vimeo.com/", None), None);
+        assert_eq!(
+            rule(&policy, "https://vimeo.com/", Some("video")),
+            Some(PolicyRule::NotAllowlisted)
+        );
+        assert_eq!(rule(&policy, "http://youtube.com/", Some("video")), Some(PolicyRule::Scheme));
+        assert_eq!(rule(&policy, "https://youtube.com/", Some("other")), Some(PolicyRule::Deny));
+    }
+
+    #[test]
+    fn test_validate_and_parse_config() {
+        let policy: UrlPolicy = serde_json::from_value(serde_json::json!({
+            "deny": ["*.doubleclick.net"],
+            "boards": { "b1": { "allow": ["canva.com"] } },
+        }))
+        .unwrap();
+        assert_eq!(policy.allowed_schemes, default_schemes());
+        assert_eq!(policy.boards["b1"].allowed_schemes, None);
+        assert!(policy.validate().is_ok());
+
+        let mut invalid = policy.clone();
+        invalid.rules.allow.push("https://example.com/".into());
+        assert!(invalid.validate().is_err());
+
+        let mut invalid = policy;
+        invalid.allowed_schemes.push("1nvalid".into());
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::error::{LockExt, WorkspaceError};
+use crate::persistence::{now_millis, write_atomic};
+use crate::WebTabState;
+
+// ============================================================================
+// WEB PROFILES - Getrennte Browser-Daten (Cookies, Storage) pro Tab
+// ============================================================================
+
+/// Name des geteilten Standard-Datenspeichers (kein eigenes Verzeichnis)
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Metadaten im Profil-Verzeichnis
+const PROFILE_FILE: &str = "profile.json";
+
+/// Unterverzeichnis für die Webview-Daten (Linux/Windows)
+const WEBVIEW_DATA_DIR: &str = "webview";
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WebProfile {
+    pub name: String,
+    /// UUID des WKWebsiteDataStore (macOS hat keine Datenverzeichnisse)
+    pub store_id: String,
+    pub created_at: u64,
+}
+
+impl WebProfile {
+    #[cfg_attr(not(target_vendor = "apple"), allow(dead_code))]
+    pub fn store_id_bytes(&self) -> Result<[u8; 16], WorkspaceError> {
+        uuid::Uuid::parse_str(&self.store_id)
+            .map(|id| *id.as_bytes())
+            .map_err(|e| WorkspaceError::Internal(format!("Invalid store id for profile {}: {}", self.name, e)))
+    }
+}
+
+/// Profil inkl. Anzahl offener Tabs (für `list_web_profiles`)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WebProfileInfo {
+    #[serde(flatten)]
+    pub profile: WebProfile,
+    pub open_tabs: usize,
+}
+
+/// `None` und "default" stehen für den geteilten Datenspeicher
+pub fn normalize_profile_name(name: Option<String>) -> Result<Option<String>, WorkspaceError> {
+    match name {
+        None => Ok(None),
+        Some(name) if name == DEFAULT_PROFILE => Ok(None),
+        Some(name) => {
+            // Namen landen im Verzeichnisnamen - nur harmlose Zeichen zulassen
+            let valid = !name.is_empty()
+                && name.len() <= 64
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+            if !valid {
+                return Err(WorkspaceError::InvalidInput(format!("Invalid profile name: {}", name)));
+            }
+            Ok(Some(name))
+        }
+    }
+}
+
+/// Profil-Speicher im App-Data-Verzeichnis (ein Verzeichnis pro Profil)
+pub struct ProfileStore {
+    dir: PathBuf,
+}
+
+impl ProfileStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn for_app(app: &AppHandle) -> Result<Self, WorkspaceError> {
+        let dir = app
+            .path()
+            .app_data_dir()
+            .map_err(WorkspaceError::io("resolve_app_data_dir"))?
+            .join("web-profiles");
+        Ok(Self::new(dir))
+    }
+
+    fn profile_dir(&self, name: &str) -> Result<PathBuf, WorkspaceError> {
+        match normalize_profile_name(Some(name.to_string()))? {
+            Some(name) => Ok(self.dir.join(name)),
+            None => Err(WorkspaceError::InvalidInput(
+                "The default profile has no data directory".to_string(),
+            )),
+        }
+    }
+
+    /// Webview-Datenverzeichnis eines Profils
+    pub fn data_dir(&self, name: &str) -> Result<PathBuf, WorkspaceError> {
+        Ok(self.profile_dir(name)?.join(WEBVIEW_DATA_DIR))
+    }
+
+    pub fn load(&self, name: &str) -> Result<Option<WebProfile>, WorkspaceError> {
+        let path = self.profile_dir(name)?.join(PROFILE_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let data = fs::read(&path).map_err(WorkspaceError::io("read_profile"))?;
+        serde_json::from_slice(&data)
+            .map(Some)
+            .map_err(WorkspaceError::io("parse_profile"))
+    }
+
+    /// Lädt ein Profil oder legt es beim ersten Gebrauch an
+    pub fn ensure(&self, name: &str) -> Result<WebProfile, WorkspaceError> {
+        if let Some(profile) = self.load(name)? {
+            return Ok(profile);
+        }
+
+        let dir = self.profile_dir(name)?;
+        fs::create_dir_all(dir.join(WEBVIEW_DATA_DIR))
+            .map_err(WorkspaceError::io("create_profile_dir"))?;
+
+        let profile = WebProfile {
+            name: name.to_string(),
+            store_id: uuid::Uuid::new_v4().to_string(),
+            created_at: now_millis(),
+        };
+        let json = serde_json::to_vec_pretty(&profile)
+            .map_err(|e| WorkspaceError::Internal(format!("Failed to serialize profile: {}", e)))?;
+        write_atomic(&dir.join(PROFILE_FILE), &json).map_err(WorkspaceError::io("write_profile"))?;
+
+        Ok(profile)
+    }
+
+    /// Alle Profile, alphabetisch
+    pub fn list(&self) -> Result<Vec<WebProfile>, WorkspaceError> {
+        let entries = match fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(WorkspaceError::io("read_profile_dir")(e)),
+        };
+
+        let mut profiles: Vec<WebProfile> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_str()?.to_string();
+                self.load(&name).ok().flatten()
+            })
+            .collect();
+
+        profiles.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(profiles)
+    }
+
+    /// Löscht ein Profil samt aller Browser-Daten
+    pub fn delete(&self, name: &str) -> Result<Option<WebProfile>, WorkspaceError> {
+        let profile = self.load(name)?;
+        match fs::remove_dir_all(self.profile_dir(name)?) {
+            Ok(()) => Ok(profile),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(profile),
+            Err(e) => Err(WorkspaceError::io("delete_profile")(e)),
+        }
+    }
+}
+
+/// Trennt den Datenspeicher eines Web-Tab-Fensters vom Standard-Speicher
+pub fn apply_profile<'a, R: tauri::Runtime, M: Manager<R>>(
+    builder: tauri::WebviewWindowBuilder<'a, R, M>,
+    store: &ProfileStore,
+    profile: &WebProfile,
+) -> Result<tauri::WebviewWindowBuilder<'a, R, M>, WorkspaceError> {
+    #[cfg(target_vendor = "apple")]
+    {
+        let _ = store;
+        Ok(builder.data_store_identifier(profile.store_id_bytes()?))
+    }
+
+    #[cfg(not(target_vendor = "apple"))]
+    {
+        Ok(builder.data_directory(store.data_dir(&profile.name)?))
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn list_web_profiles(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<WebTabState>>,
+) -> Result<Vec<WebProfileInfo>, WorkspaceError> {
+    let profiles = ProfileStore::for_app(&app)?.list()?;
+    let tab_state = state.lock_or_recover();
+
+    Ok(profiles
+        .into_iter()
+        .map(|profile| WebProfileInfo {
+            open_tabs: tab_state
+                .tabs
+                .values()
+                .filter(|tab| tab.profile.as_deref() == Some(profile.name.as_str()))
+                .count(),
+            profile,
+        })
+        .collect())
+}
+
+/// Löscht ein Profil - nur wenn kein offener Tab es benutzt
+#[tauri::command]
+pub async fn delete_web_profile(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<WebTabState>>,
+    name: String,
+) -> Result<(), WorkspaceError> {
+    let name = normalize_profile_name(Some(name))?
+        .ok_or_else(|| WorkspaceError::InvalidInput("The default profile cannot be deleted".to_string()))?;
+
+    {
+        let tab_state = state.lock_or_recover();
+        if tab_state.tabs.values().any(|tab| tab.profile.as_deref() == Some(name.as_str())) {
+            return Err(WorkspaceError::Conflict(format!("Profile {} is used by an open web tab", name)));
+        }
+    }
+
+    let deleted = ProfileStore::for_app(&app)?.delete(&name)?;
+
+    // macOS hält die Daten im WKWebsiteDataStore, nicht im Verzeichnis
+    #[cfg(target_vendor = "apple")]
+    if let Some(profile) = deleted {
+        app.remove_data_store(profile.store_id_bytes()?)
+            .await
+            .map_err(WorkspaceError::io("remove_data_store"))?;
+    }
+    #[cfg(not(target_vendor = "apple"))]
+    let _ = deleted;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store() -> ProfileStore {
+        let dir = std::env::temp_dir().join(format!("workspace-profiles-{}", uuid::Uuid::new_v4()));
+        ProfileStore::new(dir)
+    }
+
+    #[test]
+    fn test_normalize_profile_name() {
+        assert_eq!(normalize_profile_name(None).unwrap(), None);
+        assert_eq!(normalize_profile_name(Some("default".into())).unwrap(), None);
+        assert_eq!(
+            normalize_profile_name(Some("github-work".into())).unwrap(),
+            Some("github-work".to_string())
+        );
+        assert!(normalize_profile_name(Some("../escape".into())).is_err());
+        assert!(normalize_profile_name(Some(String::new())).is_err());
+    }
+
+    #[test]
+    fn test_ensure_is_stable() {
+        let store = temp_store();
+        let first = store.ensure("work").unwrap();
+        let second = store.ensure("work").unwrap();
+
+        assert_eq!(first, second);
+        assert!(store.data_dir("work").unwrap().is_dir());
+        assert_eq!(first.store_id_bytes().unwrap().len(), 16);
+    }
+
+    #[test]
+    fn test_list_and_delete() {
+        let store = temp_store();
+        assert!(store.list().unwrap().is_empty());
+
+        store.ensure("private").unwrap();
+        store.ensure("client-a").unwrap();
+        let names: Vec<String> = store.list().unwrap().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["client-a", "private"]);
+
+        assert!(store.delete("private").unwrap().is_some());
+        assert!(store.delete("private").unwrap().is_none());
+        assert_eq!(store.list().unwrap().len(), 1);
+    }
+}
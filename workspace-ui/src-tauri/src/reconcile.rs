@@ -0,0 +1,386 @@
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::canvas::CanvasLayoutState;
+use crate::error::{LockExt, WorkspaceError};
+use crate::history::NavigationHistory;
+use crate::ipc_access::WEB_TAB_PREFIX;
+use crate::{build_web_tab_window, policy, snapshot, WebTabInfo, WebTabState};
+
+// ============================================================================
+// RECONCILE - Offene Fenster, WebTabState und Snapshot abgleichen
+// ============================================================================
+//
+// Läuft nach jedem fertigen Page-Load des Hauptfensters (App-Start und
+// Frontend-Reload) sowie aus `restore_web_tabs`. Planen ist serialisiert und
+// idempotent - wer zuerst kommt, macht die Arbeit. Fenster werden ohne Lock
+// gebaut; Tabs im Bau stehen in `WebTabState::pending` und werden übersprungen.
+//
+// Der Snapshot ist nach Boards getrennt. Gelesen wird er nur, wenn
+// `restore_web_tabs` ein anderes Board meldet als `WebTabState::board_id`.
+// Danach ist der In-Memory-State maßgeblich, sonst kämen gerade geschlossene
+// Tabs aus einem noch nicht geschriebenen Snapshot zurück.
+
+/// Woher ein Tab bekannt ist
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TabSource {
+    State,
+    Snapshot,
+}
+
+/// Geplanter Schritt für eine Tab-ID
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Action {
+    /// Fenster existiert und ist bekannt - übernehmen
+    Adopt { id: String, source: TabSource },
+    /// Fenster ohne State- oder Snapshot-Eintrag
+    Close(String),
+    /// Bekannter Tab ohne Fenster - neu erstellen (versteckt)
+    Recreate { id: String, source: TabSource },
+}
+
+/// Ergebnis eines Durchlaufs (Event `web-tabs-reconciled` und Rückgabewert)
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ReconcileReport {
+    pub adopted: Vec<String>,
+    pub closed: Vec<String>,
+    pub recreated: Vec<String>,
+    /// Neu erstellen fehlgeschlagen oder von der URL-Policy blockiert -
+    /// aus dem State entfernt
+    pub failed: Vec<String>,
+}
+
+impl ReconcileReport {
+    pub fn is_empty(&self) -> bool {
+        self.closed.is_empty() && self.recreated.is_empty() && self.failed.is_empty()
+    }
+}
+
+/// Serialisiert das Planen der Durchläufe
+#[derive(Default)]
+pub struct ReconcileState {
+    planning: Mutex<()>,
+}
+
+/// Plant den Abgleich, sortiert nach Tab-ID. Nur Fenster mit `webtab-`-Präfix
+/// werden betrachtet; der State hat Vorrang vor dem Snapshot. Tabs im Bau
+/// (`pending`) bleiben unangetastet.
+pub fn plan(
+    open_windows: &[String],
+    state_ids: &[String],
+    snapshot_ids: &[String],
+    pending: &HashSet<String>,
+) -> Vec<Action> {
+    let mut known: BTreeMap<&str, TabSource> = BTreeMap::new();
+    for id in snapshot_ids.iter().filter(|id| !pending.contains(*id)) {
+        known.insert(id, TabSource::Snapshot);
+    }
+    for id in state_ids.iter().filter(|id| !pending.contains(*id)) {
+        known.insert(id, TabSource::State);
+    }
+
+    let mut open: Vec<&str> = open_windows
+        .iter()
+        .map(String::as_str)
+        .filter(|label| label.starts_with(WEB_TAB_PREFIX) && !pending.contains(*label))
+        .collect();
+    open.sort_unstable();
+    open.dedup();
+
+    let mut actions: Vec<Action> = open
+        .iter()
+        .map(|&id| match known.get(id) {
+            Some(&source) => Action::Adopt { id: id.to_string(), source },
+            None => Action::Close(id.to_string()),
+        })
+        .collect();
+
+    actions.extend(
+        known
+            .iter()
+            .filter(|(id, _)| open.binary_search(id).is_err())
+            .map(|(&id, &source)| Action::Recreate { id: id.to_string(), source }),
+    );
+
+    actions.sort_by(|a, b| action_id(a).cmp(action_id(b)));
+    actions
+}
+
+fn action_id(action: &Action) -> &str {
+    match action {
+        Action::Adopt { id, .. } | Action::Recreate { id, .. } | Action::Close(id) => id,
+    }
+}
+
+/// Tab aus dem Snapshot für diese Session vorbereiten
+pub fn prepare_restored(mut tab: WebTabInfo) -> WebTabInfo {
+    // Fullscreen nicht wiederherstellen - zurück auf die Canvas-Bounds
+    if tab.is_fullscreen {
+        if let Some(bounds) = tab.saved_bounds.take() {
+            tab.bounds = bounds;
+        }
+        tab.is_fullscreen = false;
+        tab.fullscreen_mode = None;
+    }
+
+    if tab.history.current().is_none() {
+        tab.history = NavigationHistory::new(&tab.url);
+    }
+
+    tab
+}
+
+/// Führt den Abgleich aus und meldet das Ergebnis per `web-tabs-reconciled`.
+/// Mit `board` werden beim Wechsel auf dieses Board seine Tabs aus dem
+/// Snapshot wiederhergestellt.
+pub fn reconcile(app: &AppHandle, board: Option<&str>) -> Result<ReconcileReport, WorkspaceError> {
+    let state = app.state::<Mutex<WebTabState>>();
+    let mut report = ReconcileReport::default();
+    let mut state_changed = false;
+
+    // Planen und Beanspruchen unter dem Lock, Fenster erst danach bauen
+    let to_build: Vec<WebTabInfo> = {
+        let reconcile_state = app.state::<ReconcileState>();
+        let _planning = reconcile_state.planning.lock_or_recover();
+
+        let switched_board =
+            board.filter(|board| state.lock_or_recover().board_id.as_deref() != Some(*board));
+        let snapshot_tabs: HashMap<String, WebTabInfo> = match switched_board {
+            None => HashMap::new(),
+            Some(board) => {
+                // Erst nach erfolgreichem Lesen gehört der State dem neuen Board.
+                // Bis dahin keinem: Snapshots würden sonst das alte Board leeren.
+                let tabs = snapshot::read_snapshot(app, board).inspect_err(|_| {
+                    state.lock_or_recover().board_id = None;
+                })?;
+                state.lock_or_recover().board_id = Some(board.to_string());
+                tabs.into_iter().map(|tab| (tab.id.clone(), prepare_restored(tab))).collect()
+            }
+        };
+
+        // Fenster vor dem State lesen: ein Tab, der dazwischen fertig wird,
+        // steht dann im State und wird beim Beanspruchen erkannt
+        let open_windows: Vec<String> = app.webview_windows().into_keys().collect();
+        let (state_ids, pending): (Vec<String>, HashSet<String>) = {
+            let tab_state = state.lock_or_recover();
+            (tab_state.tabs.keys().cloned().collect(), tab_state.pending.clone())
+        };
+        let snapshot_ids: Vec<String> = snapshot_tabs.keys().cloned().collect();
+
+        let mut to_build = Vec::new();
+        for action in plan(&open_windows, &state_ids, &snapshot_ids, &pending) {
+            match action {
+                Action::Adopt { id, source } => {
+                    if source == TabSource::Snapshot {
+                        state.lock_or_recover().tabs.insert(id.clone(), snapshot_tabs[&id].clone());
+                        hide_in_layout(app, &id);
+                        state_changed = true;
+                    }
+                    report.adopted.push(id);
+                }
+                Action::Close(id) => {
+                    if let Some(window) = app.get_webview_window(&id) {
+                        window.close().ok();
+                    }
+                    report.closed.push(id);
+                }
+                Action::Recreate { id, source } => {
+                    let mut tab = match source {
+                        TabSource::Snapshot => snapshot_tabs[&id].clone(),
+                        TabSource::State => match state.lock_or_recover().tabs.get(&id) {
+                            Some(tab) => tab.clone(),
+                            None => continue,
+                        },
+                    };
+                    // Das neue Webview beginnt mit leerem Verlauf - `history.go()`
+                    // erreicht die alten Einträge nicht mehr
+                    tab.history = NavigationHistory::new(&tab.url);
+
+                    // Inzwischen von der URL-Policy blockiert - nicht still im System-Browser öffnen
+                    let allowed = tab
+                        .url
+                        .parse::<tauri::Url>()
+                        .map(|url| policy::evaluate_for_app(app, &url).is_ok())
+                        .unwrap_or(false);
+                    if !allowed {
+                        log::warn!("Web tab {} blocked by URL policy: {}", id, tab.url);
+                        state.lock_or_recover().tabs.remove(&id);
+                        report.failed.push(id);
+                        continue;
+                    }
+
+                    // Vor dem Erstellen eintragen, damit der erste Page-Load den Tab findet.
+                    // Wird er gerade von `create_web_tab` gebaut oder ist inzwischen fertig,
+                    // gehört er nicht mehr uns.
+                    {
+                        let mut tab_state = state.lock_or_recover();
+                        if tab_state.pending.contains(&id) || app.get_webview_window(&id).is_some() {
+                            continue;
+                        }
+                        tab_state.pending.insert(id.clone());
+                        tab_state.tabs.insert(id.clone(), tab.clone());
+                    }
+                    hide_in_layout(app, &id);
+                    state_changed = true;
+                    to_build.push(tab);
+                }
+            }
+        }
+        to_build
+    };
+
+    for tab in to_build {
+        let built = build_web_tab_window(
+            app,
+            &tab.id,
+            &tab.url,
+            &tab.bounds,
+            false,
+            tab.profile.as_deref(),
+        );
+
+        let mut tab_state = state.lock_or_recover();
+        tab_state.pending.remove(&tab.id);
+        match built {
+            Ok(_) => report.recreated.push(tab.id),
+            Err(e) => {
+                log::error!("Failed to recreate web tab {}: {}", tab.id, e);
+                tab_state.tabs.remove(&tab.id);
+                report.failed.push(tab.id);
+            }
+        }
+    }
+
+    if state_changed {
+        snapshot::schedule_snapshot(app);
+    }
+    app.emit("web-tabs-reconciled", &report).ok();
+
+    Ok(report)
+}
+
+/// Versteckt bis die Karte den Tab anzeigt - auch für das Canvas-Layout
+fn hide_in_layout(app: &AppHandle, tab_id: &str) {
+    app.state::<Mutex<CanvasLayoutState>>()
+        .lock_or_recover()
+        .set_hidden(tab_id, true);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TabBounds;
+
+    fn ids(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    fn adopt(id: &str, source: TabSource) -> Action {
+        Action::Adopt { id: id.to_string(), source }
+    }
+
+    fn recreate(id: &str, source: TabSource) -> Action {
+        Action::Recreate { id: id.to_string(), source }
+    }
+
+    #[test]
+    fn test_plan_startup_restores_snapshot() {
+        // App-Start: keine Fenster, leerer State
+        let actions = plan(&ids(&["main"]), &[], &ids(&["webtab-b", "webtab-a"]), &HashSet::new());
+        assert_eq!(
+            actions,
+            vec![
+                recreate("webtab-a", TabSource::Snapshot),
+                recreate("webtab-b", TabSource::Snapshot),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_frontend_reload() {
+        let actions = plan(
+            &ids(&["main", "webtab-a", "webtab-orphan", "webtab-snap", "webview-legacy"]),
+            &ids(&["webtab-a", "webtab-lost"]),
+            &ids(&["webtab-a", "webtab-snap"]),
+            &HashSet::new(),
+        );
+        assert_eq!(
+            actions,
+            vec![
+                adopt("webtab-a", TabSource::State),
+                recreate("webtab-lost", TabSource::State),
+                Action::Close("webtab-orphan".to_string()),
+                adopt("webtab-snap", TabSource::Snapshot),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plan_is_idempotent() {
+        let open = ids(&["main", "webtab-a", "webtab-b"]);
+        let state = ids(&["webtab-a", "webtab-b"]);
+        let actions = plan(&open, &state, &[], &HashSet::new());
+        assert!(actions.iter().all(|a| matches!(a, Action::Adopt { .. })));
+        assert_eq!(plan(&open, &state, &[], &HashSet::new()), actions);
+    }
+
+    #[test]
+    fn test_plan_skips_pending_tabs() {
+        let pending: HashSet<String> = ids(&["webtab-new", "webtab-building"]).into_iter().collect();
+
+        // `webtab-new`: Fenster schon offen, State noch nicht gelesen
+        // `webtab-building`: im State, Fenster noch nicht gebaut
+        let actions = plan(
+            &ids(&["main", "webtab-a", "webtab-new"]),
+            &ids(&["webtab-a", "webtab-building"]),
+            &[],
+            &pending,
+        );
+        assert_eq!(actions, vec![adopt("webtab-a", TabSource::State)]);
+    }
+
+    #[test]
+    fn test_prepare_restored_leaves_fullscreen() {
+        let canvas = TabBounds { x: 10, y: 20, width: 300, height: 200 };
+        let tab = WebTabInfo {
+            id: "webtab-a".into(),
+            url: "https://example.com/".into(),
+            bounds: TabBounds { x: 0, y: 0, width: 1920, height: 1080 },
+            is_fullscreen: true,
+            fullscreen_mode: Some(crate::fullscreen::FullscreenMode::Os),
+            saved_bounds: Some(canvas.clone()),
+            history: NavigationHistory::default(),
+            title: None,
+            last_navigated_at: None,
+            profile: None,
+            canvas_rect: None,
+        };
+
+        let tab = prepare_restored(tab);
+        assert!(!tab.is_fullscreen);
+        assert_eq!(tab.fullscreen_mode, None);
+        assert_eq!(tab.bounds, canvas);
+        assert_eq!(tab.history.current(), Some("https://example.com/"));
+    }
+
+    #[test]
+    fn test_report_serializes_snake_case() {
+        let report = ReconcileReport {
+            adopted: ids(&["webtab-a"]),
+            closed: ids(&["webtab-b"]),
+            ..Default::default()
+        };
+        assert!(!report.is_empty());
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            serde_json::json!({
+                "adopted": ["webtab-a"],
+                "closed": ["webtab-b"],
+                "recreated": [],
+                "failed": [],
+            })
+        );
+    }
+}
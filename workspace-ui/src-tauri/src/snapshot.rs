@@ -0,0 +1,269 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use crate::error::{LockExt, WorkspaceError};
+use crate::persistence::write_atomic;
+use crate::{WebTabInfo, WebTabState};
+
+// ============================================================================
+// WEB TAB SNAPSHOT - Offene Tabs über App-Neustarts hinweg merken
+// ============================================================================
+
+/// Aktuelle Version des Snapshot-Formats (2: Tabs pro Board)
+const SNAPSHOT_VERSION: u32 = 2;
+
+/// Bounds-Updates kommen pro Frame - erst schreiben wenn es ruhig wird
+const SNAPSHOT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+#[derive(Serialize, Deserialize)]
+struct TabSnapshot {
+    version: u32,
+    /// Tabs pro Board-ID - jedes Board stellt nur seine eigenen Tabs wieder her
+    #[serde(default)]
+    boards: BTreeMap<String, Vec<WebTabInfo>>,
+    /// Version 1 kannte keine Boards: diese Tabs übernimmt einmalig das erste
+    /// Board, das wiederhergestellt wird
+    #[serde(default, skip_serializing)]
+    tabs: Vec<WebTabInfo>,
+}
+
+impl Default for TabSnapshot {
+    fn default() -> Self {
+        Self {
+            version: SNAPSHOT_VERSION,
+            boards: BTreeMap::new(),
+            tabs: Vec::new(),
+        }
+    }
+}
+
+impl TabSnapshot {
+    /// Tabs eines Boards (ohne eigenen Eintrag: die Tabs aus Version 1)
+    fn take_board(&mut self, board_id: &str) -> Vec<WebTabInfo> {
+        self.boards
+            .remove(board_id)
+            .unwrap_or_else(|| std::mem::take(&mut self.tabs))
+    }
+
+    /// Ersetzt die Tabs eines Boards; Boards ohne Tabs fallen heraus
+    fn set_board(&mut self, board_id: &str, tabs: Vec<WebTabInfo>) {
+        if tabs.is_empty() {
+            self.boards.remove(board_id);
+        } else {
+            self.boards.insert(board_id.to_string(), tabs);
+        }
+    }
+}
+
+/// Zähler für Debouncing: nur der letzte geplante Write wird ausgeführt
+#[derive(Default)]
+pub struct TabSnapshotState {
+    generation: AtomicU64,
+    /// Serialisiert Lesen-Ändern-Schreiben der Datei (Debounce und Board-Wechsel)
+    write_lock: Mutex<()>,
+}
+
+fn snapshot_path(app: &AppHandle) -> Result<PathBuf, WorkspaceError> {
+    Ok(app
+        .path()
+        .app_data_dir()
+        .map_err(WorkspaceError::io("resolve_app_data_dir"))?
+        .join("web-tabs.json"))
+}
+
+/// Plant einen Snapshot des aktuellen `WebTabState` (debounced)
+pub fn schedule_snapshot(app: &AppHandle) {
+    let generation = app
+        .state::<TabSnapshotState>()
+        .generation
+        .fetch_add(1, Ordering::SeqCst)
+        + 1;
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(SNAPSHOT_DEBOUNCE).await;
+
+        let current = app.state::<TabSnapshotState>().generation.load(Ordering::SeqCst);
+        if current != generation {
+            return;
+        }
+
+        if let Err(e) = write_snapshot(&app) {
+            log::error!("Failed to write web tab snapshot: {}", e);
+        }
+    });
+}
+
+/// Schreibt die Tabs aus dem `WebTabState` sofort unter ihrem Board auf die
+/// Platte; die Einträge anderer Boards bleiben erhalten
+pub fn write_snapshot(app: &AppHandle) -> Result<(), WorkspaceError> {
+    let (board_id, mut tabs): (Option<String>, Vec<WebTabInfo>) = {
+        let state = app.state::<Mutex<WebTabState>>();
+        let tab_state = state.lock_or_recover();
+        (tab_state.board_id.clone(), tab_state.tabs.values().cloned().collect())
+    };
+
+    // Noch kein Board wiederhergestellt - es gibt nichts, dem die Tabs gehören
+    let Some(board_id) = board_id else {
+        return Ok(());
+    };
+    tabs.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let snapshot_state = app.state::<TabSnapshotState>();
+    let _write = snapshot_state.write_lock.lock_or_recover();
+
+    let path = snapshot_path(app)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(WorkspaceError::io("create_app_data_dir"))?;
+    }
+
+    // Unlesbare Datei nicht mit einem einzelnen Board überschreiben
+    let mut snapshot = read_file(&path)?;
+    snapshot.version = SNAPSHOT_VERSION;
+    snapshot.set_board(&board_id, tabs);
+
+    let json = serde_json::to_vec_pretty(&snapshot).map_err(WorkspaceError::io("serialize_snapshot"))?;
+
+    write_atomic(&path, &json).map_err(WorkspaceError::io("write_snapshot"))
+}
+
+/// Liest die Snapshot-Datei (leer wenn keine existiert)
+fn read_file(path: &Path) -> Result<TabSnapshot, WorkspaceError> {
+    match read_bytes(path)? {
+        Some(data) => parse(&data),
+        None => Ok(TabSnapshot::default()),
+    }
+}
+
+/// Rohdaten der Snapshot-Datei (`None` wenn keine existiert)
+fn read_bytes(path: &Path) -> Result<Option<Vec<u8>>, WorkspaceError> {
+    match std::fs::read(path) {
+        Ok(data) => Ok(Some(data)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(WorkspaceError::io("read_snapshot")(e)),
+    }
+}
+
+fn parse(data: &[u8]) -> Result<TabSnapshot, WorkspaceError> {
+    let snapshot: TabSnapshot = serde_json::from_slice(data)
+        .map_err(WorkspaceError::io("parse_snapshot"))?;
+
+    if snapshot.version > SNAPSHOT_VERSION {
+        return Err(WorkspaceError::Unsupported(format!(
+            "Web tab snapshot uses version {} (supported: {})",
+            snapshot.version, SNAPSHOT_VERSION
+        )));
+    }
+
+    Ok(snapshot)
+}
+
+/// Liest die Tabs eines Boards aus dem letzten Snapshot (leer wenn keiner
+/// existiert). Eine unlesbare Datei wandert nach `web-tabs.json.bad` - das
+/// Board startet leer und der nächste Write legt eine neue an.
+pub fn read_snapshot(app: &AppHandle, board_id: &str) -> Result<Vec<WebTabInfo>, WorkspaceError> {
+    let path = snapshot_path(app)?;
+
+    let snapshot_state = app.state::<TabSnapshotState>();
+    let _write = snapshot_state.write_lock.lock_or_recover();
+    read_board(&path, board_id)
+}
+
+fn read_board(path: &Path, board_id: &str) -> Result<Vec<WebTabInfo>, WorkspaceError> {
+    let Some(data) = read_bytes(path)? else {
+        return Ok(Vec::new());
+    };
+
+    match parse(&data) {
+        Ok(mut snapshot) => Ok(snapshot.take_board(board_id)),
+        Err(e) => {
+            log::warn!("Moving unreadable web tab snapshot aside: {}", e);
+            std::fs::rename(path, path.with_extension("json.bad"))
+                .map_err(WorkspaceError::io("move_bad_snapshot"))?;
+            Ok(Vec::new())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::NavigationHistory;
+    use crate::TabBounds;
+
+    fn tab(id: &str) -> WebTabInfo {
+        WebTabInfo {
+            id: id.into(),
+            url: "https://example.com/".into(),
+            bounds: TabBounds { x: 0, y: 0, width: 300, height: 200 },
+            is_fullscreen: false,
+            fullscreen_mode: None,
+            saved_bounds: None,
+            history: NavigationHistory::default(),
+            title: None,
+            last_navigated_at: None,
+            profile: None,
+            canvas_rect: None,
+        }
+    }
+
+    fn tab_ids(tabs: &[WebTabInfo]) -> Vec<&str> {
+        tabs.iter().map(|tab| tab.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_boards_restore_only_their_own_tabs() {
+        let mut snapshot = TabSnapshot::default();
+        snapshot.set_board("board-a", vec![tab("webtab-a")]);
+        snapshot.set_board("board-b", vec![tab("webtab-b")]);
+
+        let json = serde_json::to_vec(&snapshot).unwrap();
+        let mut snapshot: TabSnapshot = serde_json::from_slice(&json).unwrap();
+        assert_eq!(tab_ids(&snapshot.take_board("board-b")), ["webtab-b"]);
+        assert!(snapshot.take_board("board-new").is_empty());
+
+        // Leere Boards verschwinden aus der Datei
+        snapshot.set_board("board-a", Vec::new());
+        assert!(snapshot.boards.is_empty());
+    }
+
+    #[test]
+    fn test_version_1_tabs_go_to_the_first_board() {
+        let v1 = serde_json::json!({ "version": 1, "tabs": [tab("webtab-old")] });
+        let mut snapshot: TabSnapshot = serde_json::from_value(v1).unwrap();
+
+        assert_eq!(tab_ids(&snapshot.take_board("board-a")), ["webtab-old"]);
+        assert!(snapshot.take_board("board-b").is_empty());
+
+        // Beim nächsten Schreiben nur noch im Format mit Boards
+        snapshot.set_board("board-a", vec![tab("webtab-old")]);
+        let json = serde_json::to_value(&snapshot).unwrap();
+        assert!(json.get("tabs").is_none());
+        assert_eq!(json["boards"]["board-a"][0]["id"], "webtab-old");
+    }
+
+    #[test]
+    fn test_unreadable_snapshot_is_moved_aside() {
+        let dir = std::env::temp_dir().join(format!("workspace-snapshot-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("web-tabs.json");
+
+        // Keine Datei: leeres Board
+        assert!(read_board(&path, "board-a").unwrap().is_empty());
+
+        std::fs::write(&path, b"{ not json").unwrap();
+        assert!(read_board(&path, "board-a").unwrap().is_empty());
+        assert!(!path.exists());
+        assert_eq!(std::fs::read(dir.join("web-tabs.json.bad")).unwrap(), b"{ not json");
+
+        // Der nächste Write beginnt mit einer leeren Datei
+        assert!(read_file(&path).unwrap().boards.is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
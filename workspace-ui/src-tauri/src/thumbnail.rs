@@ -0,0 +1,503 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use image_scale::fit_size;
+use tauri::ipc::Response;
+use tauri::{AppHandle, Manager};
+
+use crate::error::{LockExt, WorkspaceError};
+use crate::persistence::write_atomic;
+use crate::WebTabState;
+
+// ============================================================================
+// THUMBNAILS - Vorschaubilder der Web-Tabs (Zoom-out, Minimap, Board-Liste)
+// ============================================================================
+
+/// Standard-Bounding-Box der Vorschaubilder
+const THUMBNAIL_MAX_WIDTH: u32 = 480;
+const THUMBNAIL_MAX_HEIGHT: u32 = 360;
+
+/// Native Snapshots laufen asynchron im Webview - nicht ewig warten
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Stabiler Hash der URL für den Dateinamen (FNV-1a, unabhängig von der Rust-Version)
+fn url_key(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// Vorschaubilder auf der Platte - eine PNG-Datei pro Tab und URL
+pub struct ThumbnailCache {
+    dir: PathBuf,
+}
+
+impl ThumbnailCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn for_app(app: &AppHandle) -> Result<Self, WorkspaceError> {
+        let dir = app
+            .path()
+            .app_cache_dir()
+            .map_err(WorkspaceError::io("resolve_app_cache_dir"))?
+            .join("thumbnails");
+        Ok(Self::new(dir))
+    }
+
+    fn validate_tab_id(tab_id: &str) -> Result<(), WorkspaceError> {
+        // IDs landen im Dateinamen - nur harmlose Zeichen zulassen
+        let valid = !tab_id.is_empty()
+            && tab_id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(WorkspaceError::InvalidInput(format!("Invalid tab id: {}", tab_id)));
+        }
+        Ok(())
+    }
+
+    fn path(&self, tab_id: &str, url: &str) -> Result<PathBuf, WorkspaceError> {
+        Self::validate_tab_id(tab_id)?;
+        Ok(self.dir.join(format!("{}.{}.png", tab_id, url_key(url))))
+    }
+
+    /// Gecachtes Bild, falls vorhanden und nicht älter als `max_age`
+    pub fn get(&self, tab_id: &str, url: &str, max_age: Option<Duration>) -> Result<Option<Vec<u8>>, WorkspaceError> {
+        self.get_at(tab_id, url, max_age, SystemTime::now())
+    }
+
+    /// Wie `get`, mit `now` als aktueller Zeit
+    fn get_at(
+        &self,
+        tab_id: &str,
+        url: &str,
+        max_age: Option<Duration>,
+        now: SystemTime,
+    ) -> Result<Option<Vec<u8>>, WorkspaceError> {
+        let path = self.path(tab_id, url)?;
+        let metadata = match fs::metadata(&path) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(WorkspaceError::io("read_thumbnail")(e)),
+        };
+
+        if let Some(max_age) = max_age {
+            let age = metadata
+                .modified()
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok())
+                .unwrap_or(Duration::MAX);
+            if age > max_age {
+                return Ok(None);
+            }
+        }
+
+        fs::read(&path)
+            .map(Some)
+            .map_err(WorkspaceError::io("read_thumbnail"))
+    }
+
+    /// Speichert ein Bild und verwirft ältere URLs desselben Tabs
+    pub fn put(&self, tab_id: &str, url: &str, png: &[u8]) -> Result<(), WorkspaceError> {
+        let path = self.path(tab_id, url)?;
+        fs::create_dir_all(&self.dir)
+            .map_err(WorkspaceError::io("create_thumbnail_dir"))?;
+
+        for stale in self.tab_files(tab_id).into_iter().filter(|p| *p != path) {
+            fs::remove_file(stale).ok();
+        }
+        write_atomic(&path, png).map_err(WorkspaceError::io("write_thumbnail"))
+    }
+
+    fn tab_files(&self, tab_id: &str) -> Vec<PathBuf> {
+        let prefix = format!("{}.", tab_id);
+        fs::read_dir(&self.dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| {
+                        path.file_name()
+                            .and_then(|name| name.to_str())
+                            .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".png"))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+// ============================================================================
+// NATIVE CAPTURE - Snapshot des Webviews über die Plattform-API
+// ============================================================================
+
+/// WebKitGTK: `webkit_web_view_get_snapshot`, skaliert mit Cairo
+#[cfg(target_os = "linux")]
+async fn capture_native(
+    webview: &tauri::WebviewWindow,
+    max_width: u32,
+    max_height: u32,
+) -> Result<Vec<u8>, WorkspaceError> {
+    use webkit2gtk::{SnapshotOptions, SnapshotRegion, WebViewExt};
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    webview
+        .with_webview(move |platform| {
+            platform.inner().snapshot(
+                SnapshotRegion::Visible,
+                SnapshotOptions::NONE,
+                None::<&webkit2gtk::gio::Cancellable>,
+                move |result| {
+                    let png = result
+                        .map_err(|e| format!("Snapshot failed: {}", e))
+                        .and_then(|surface| surface_to_png(surface, max_width, max_height))
+                        .map_err(WorkspaceError::Internal);
+                    tx.send(png).ok();
+                },
+            );
+        })
+        .map_err(WorkspaceError::window_op("with_webview"))?;
+
+    await_capture(webview, rx).await
+}
+
+#[cfg(target_os = "linux")]
+fn surface_to_png(surface: cairo::Surface, max_width: u32, max_height: u32) -> Result<Vec<u8>, String> {
+    let image = cairo::ImageSurface::try_from(surface)
+        .map_err(|_| "Snapshot is not an image surface".to_string())?;
+    let (width, height) = (image.width() as u32, image.height() as u32);
+    let (thumb_width, thumb_height) = fit_size(width, height, max_width, max_height);
+
+    let target = cairo::ImageSurface::create(cairo::Format::ARgb32, thumb_width as i32, thumb_height as i32)
+        .map_err(|e| format!("Failed to create thumbnail surface: {}", e))?;
+    {
+        let cr = cairo::Context::new(&target).map_err(|e| format!("Cairo error: {}", e))?;
+        cr.scale(thumb_width as f64 / width as f64, thumb_height as f64 / height as f64);
+        cr.set_source_surface(&image, 0.0, 0.0)
+            .map_err(|e| format!("Cairo error: {}", e))?;
+        cr.source().set_filter(cairo::Filter::Good);
+        cr.paint().map_err(|e| format!("Cairo error: {}", e))?;
+    }
+
+    let mut png = Vec::new();
+    target
+        .write_to_png(&mut png)
+        .map_err(|e| format!("PNG encoding failed: {}", e))?;
+    Ok(png)
+}
+
+/// WebView2: `ICoreWebView2::CapturePreview` als PNG in einen Speicher-Stream,
+/// danach auf die Bounding-Box verkleinert (WebView2 kann nicht skalieren)
+#[cfg(windows)]
+async fn capture_native(
+    webview: &tauri::WebviewWindow,
+    max_width: u32,
+    max_height: u32,
+) -> Result<Vec<u8>, WorkspaceError> {
+    use webview2_com::CapturePreviewCompletedHandler;
+    use webview2_com::Microsoft::Web::WebView2::Win32::COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG;
+    use windows::Win32::Foundation::E_OUTOFMEMORY;
+    use windows::Win32::UI::Shell::SHCreateMemStream;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    webview
+        .with_webview(move |platform| unsafe {
+            let started = (|| -> windows::core::Result<()> {
+                let core = platform.controller().CoreWebView2()?;
+                let stream = SHCreateMemStream(None).ok_or_else(|| windows::core::Error::from(E_OUTOFMEMORY))?;
+                let output = stream.clone();
+                let handler = CapturePreviewCompletedHandler::create(Box::new(move |result| {
+                    let png = result
+                        .and_then(|_| read_stream(&output))
+                        .map_err(|e| format!("Snapshot failed: {}", e))
+                        .and_then(|png| downscale_png(&png, max_width, max_height))
+                        .map_err(WorkspaceError::Internal);
+                    tx.send(png).ok();
+                    Ok(())
+                }));
+                core.CapturePreview(COREWEBVIEW2_CAPTURE_PREVIEW_IMAGE_FORMAT_PNG, &stream, &handler)
+            })();
+            // Ohne Handler-Aufruf endet `rx` als abgebrochen
+            if let Err(e) = started {
+                log::error!("Failed to start thumbnail capture: {}", e);
+            }
+        })
+        .map_err(WorkspaceError::window_op("with_webview"))?;
+
+    await_capture(webview, rx).await
+}
+
+#[cfg(windows)]
+unsafe fn read_stream(stream: &windows::Win32::System::Com::IStream) -> windows::core::Result<Vec<u8>> {
+    use windows::Win32::System::Com::STREAM_SEEK_SET;
+
+    stream.Seek(0, STREAM_SEEK_SET, None)?;
+    let mut data = Vec::new();
+    let mut chunk = vec![0u8; 64 * 1024];
+    loop {
+        let mut read = 0u32;
+        stream
+            .Read(chunk.as_mut_ptr().cast(), chunk.len() as u32, Some(&mut read as *mut u32))
+            .ok()?;
+        if read == 0 {
+            return Ok(data);
+        }
+        data.extend_from_slice(&chunk[..read as usize]);
+    }
+}
+
+/// PNG dekodieren, per Box-Filter verkleinern und wieder kodieren
+#[cfg(windows)]
+fn downscale_png(png_data: &[u8], max_width: u32, max_height: u32) -> Result<Vec<u8>, String> {
+    use image_scale::{resize_bgra, ResizeFilter};
+
+    let mut decoder = png::Decoder::new(std::io::Cursor::new(png_data));
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder
+        .read_info()
+        .map_err(|e| format!("PNG decoding failed: {}", e))?;
+    let mut buffer = vec![0; reader.output_buffer_size().ok_or("PNG is too large")?];
+    let info = reader
+        .next_frame(&mut buffer)
+        .map_err(|e| format!("PNG decoding failed: {}", e))?;
+    buffer.truncate(info.buffer_size());
+
+    let rgba = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer.chunks_exact(3).flat_map(|p| [p[0], p[1], p[2], 255]).collect(),
+        png::ColorType::GrayscaleAlpha => buffer.chunks_exact(2).flat_map(|p| [p[0], p[0], p[0], p[1]]).collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|&g| [g, g, g, 255]).collect(),
+        png::ColorType::Indexed => return Err("Unexpected indexed PNG after expansion".to_string()),
+    };
+
+    let (width, height) = fit_size(info.width, info.height, max_width, max_height);
+    // Gleicher Box-Filter wie bei den OSR-Vorschaubildern (Kanalreihenfolge egal)
+    let pixels = resize_bgra(&rgba, info.width, info.height, width, height, ResizeFilter::Box);
+
+    let mut png_data = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_data, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&pixels))
+        .map_err(|e| format!("PNG encoding failed: {}", e))?;
+    Ok(png_data)
+}
+
+/// WKWebView: `takeSnapshotWithConfiguration` - WebKit skaliert selbst, die
+/// Breite wird in Punkten angegeben (Retina: Pixel / Backing-Scale)
+#[cfg(target_os = "macos")]
+async fn capture_native(
+    webview: &tauri::WebviewWindow,
+    max_width: u32,
+    max_height: u32,
+) -> Result<Vec<u8>, WorkspaceError> {
+    use block2::RcBlock;
+    use objc2::MainThreadMarker;
+    use objc2_app_kit::NSImage;
+    use objc2_foundation::{NSError, NSNumber};
+    use objc2_web_kit::{WKSnapshotConfiguration, WKWebView};
+    use std::cell::Cell;
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    webview
+        .with_webview(move |platform| unsafe {
+            // `with_webview` läuft auf dem Main-Thread
+            let mtm = MainThreadMarker::new_unchecked();
+            let wk_webview: &WKWebView = &*platform.inner().cast();
+
+            let scale = wk_webview.window().map(|w| w.backingScaleFactor()).unwrap_or(1.0);
+            let bounds = wk_webview.bounds();
+            let (width, _) = fit_size(
+                (bounds.size.width * scale) as u32,
+                (bounds.size.height * scale) as u32,
+                max_width,
+                max_height,
+            );
+            let config = WKSnapshotConfiguration::new(mtm);
+            config.setSnapshotWidth(Some(&NSNumber::new_f64(width as f64 / scale)));
+
+            let tx = Cell::new(Some(tx));
+            let handler = RcBlock::new(move |image: *mut NSImage, error: *mut NSError| {
+                let png = match image.as_ref() {
+                    Some(image) => image_to_png(image),
+                    None => Err(error
+                        .as_ref()
+                        .map(|e| format!("Snapshot failed: {}", e.localizedDescription()))
+                        .unwrap_or_else(|| "Snapshot failed".to_string())),
+                };
+                if let Some(tx) = tx.take() {
+                    tx.send(png.map_err(WorkspaceError::Internal)).ok();
+                }
+            });
+            wk_webview.takeSnapshotWithConfiguration_completionHandler(Some(&config), &handler);
+        })
+        .map_err(WorkspaceError::window_op("with_webview"))?;
+
+    await_capture(webview, rx).await
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn image_to_png(image: &objc2_app_kit::NSImage) -> Result<Vec<u8>, String> {
+    use objc2_app_kit::{NSBitmapImageFileType, NSBitmapImageRep};
+    use objc2_foundation::NSDictionary;
+
+    let tiff = image.TIFFRepresentation().ok_or("Snapshot has no bitmap data")?;
+    let bitmap = NSBitmapImageRep::imageRepWithData(&tiff).ok_or("Snapshot has no bitmap data")?;
+    let png = bitmap
+        .representationUsingType_properties(NSBitmapImageFileType::PNG, &NSDictionary::new())
+        .ok_or("PNG encoding failed")?;
+    Ok(png.to_vec())
+}
+
+/// Andere Plattformen (z.B. BSD mit WebKitGTK ohne Bindings hier) bleiben ohne Vorschau
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+async fn capture_native(
+    _webview: &tauri::WebviewWindow,
+    _max_width: u32,
+    _max_height: u32,
+) -> Result<Vec<u8>, WorkspaceError> {
+    Err(WorkspaceError::Unsupported(
+        "Native thumbnail capture is not supported on this platform".to_string(),
+    ))
+}
+
+/// Wartet auf das Ergebnis des Snapshot-Callbacks (mit Timeout)
+#[cfg(any(target_os = "linux", windows, target_os = "macos"))]
+async fn await_capture(
+    webview: &tauri::WebviewWindow,
+    rx: tokio::sync::oneshot::Receiver<Result<Vec<u8>, WorkspaceError>>,
+) -> Result<Vec<u8>, WorkspaceError> {
+    tokio::time::timeout(CAPTURE_TIMEOUT, rx)
+        .await
+        .map_err(|_| WorkspaceError::Timeout {
+            op: "capture_thumbnail",
+            id: webview.label().to_string(),
+        })?
+        .map_err(|_| WorkspaceError::Internal("Thumbnail capture was cancelled".to_string()))?
+}
+
+/// OSR-Tabs: Frame aus dem `FrameBuffer` des cef-plugins
+#[cfg(feature = "cef")]
+fn capture_osr(app: &AppHandle, tab_id: &str, max_width: u32, max_height: u32) -> Result<(String, Vec<u8>), WorkspaceError> {
+    let state = app
+        .try_state::<cef_plugin::CefPluginState>()
+        .ok_or_else(|| WorkspaceError::tab_not_found(tab_id))?;
+    let thumbnail = state.capture_thumbnail(tab_id, Some(max_width), Some(max_height))?;
+    Ok((thumbnail.url, thumbnail.png))
+}
+
+#[cfg(not(feature = "cef"))]
+fn capture_osr(_app: &AppHandle, tab_id: &str, _max_width: u32, _max_height: u32) -> Result<(String, Vec<u8>), WorkspaceError> {
+    Err(WorkspaceError::tab_not_found(tab_id))
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Vorschaubild eines offenen Tabs als PNG-Bytes. Native Tabs werden nur neu
+/// aufgenommen, wenn der Cache älter als `max_age_ms` ist; versteckte Tabs
+/// rendern nicht und liefern immer das letzte gecachte Bild.
+#[tauri::command]
+pub async fn capture_web_tab_thumbnail(
+    app: AppHandle,
+    state: tauri::State<'_, Mutex<WebTabState>>,
+    tab_id: String,
+    max_width: Option<u32>,
+    max_height: Option<u32>,
+    max_age_ms: Option<u64>,
+) -> Result<Response, WorkspaceError> {
+    let cache = ThumbnailCache::for_app(&app)?;
+    let max_width = max_width.unwrap_or(THUMBNAIL_MAX_WIDTH);
+    let max_height = max_height.unwrap_or(THUMBNAIL_MAX_HEIGHT);
+
+    let native_url = {
+        let tab_state = state.lock_or_recover();
+        tab_state.tabs.get(&tab_id).map(|tab| tab.url.clone())
+    };
+
+    let Some(url) = native_url else {
+        let (url, png) = capture_osr(&app, &tab_id, max_width, max_height)?;
+        cache.put(&tab_id, &url, &png)?;
+        return Ok(Response::new(png));
+    };
+
+    if let Some(png) = cache.get(&tab_id, &url, max_age_ms.map(Duration::from_millis))? {
+        return Ok(Response::new(png));
+    }
+
+    let webview = app
+        .get_webview_window(&tab_id)
+        .ok_or_else(|| WorkspaceError::tab_not_found(&tab_id))?;
+    if !webview.is_visible().unwrap_or(false) {
+        return cache
+            .get(&tab_id, &url, None)?
+            .map(Response::new)
+            .ok_or_else(|| WorkspaceError::NotFound { kind: "thumbnail", id: tab_id });
+    }
+
+    let png = capture_native(&webview, max_width, max_height).await?;
+    cache.put(&tab_id, &url, &png)?;
+    Ok(Response::new(png))
+}
+
+/// Gecachtes Vorschaubild ohne offenen Tab (z.B. für die Board-Liste)
+#[tauri::command]
+pub fn get_cached_web_tab_thumbnail(app: AppHandle, tab_id: String, url: String) -> Result<Response, WorkspaceError> {
+    ThumbnailCache::for_app(&app)?
+        .get(&tab_id, &url, None)?
+        .map(Response::new)
+        .ok_or_else(|| WorkspaceError::NotFound { kind: "thumbnail", id: tab_id })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache() -> ThumbnailCache {
+        let dir = std::env::temp_dir().join(format!("workspace-thumbnails-{}", uuid::Uuid::new_v4()));
+        ThumbnailCache::new(dir)
+    }
+
+    #[test]
+    fn test_put_replaces_older_url_of_same_tab() {
+        let cache = temp_cache();
+        cache.put("webtab-a", "https://one.com/", b"one").unwrap();
+        cache.put("webtab-b", "https://one.com/", b"other tab").unwrap();
+        cache.put("webtab-a", "https://two.com/", b"two").unwrap();
+
+        assert_eq!(cache.get("webtab-a", "https://one.com/", None).unwrap(), None);
+        assert_eq!(cache.get("webtab-a", "https://two.com/", None).unwrap(), Some(b"two".to_vec()));
+        assert_eq!(cache.get("webtab-b", "https://one.com/", None).unwrap(), Some(b"other tab".to_vec()));
+    }
+
+    #[test]
+    fn test_max_age_and_invalid_ids() {
+        let cache = temp_cache();
+        cache.put("webtab-a", "https://one.com/", b"png").unwrap();
+        let written = fs::metadata(cache.path("webtab-a", "https://one.com/").unwrap())
+            .unwrap()
+            .modified()
+            .unwrap();
+        let max_age = Some(Duration::from_secs(60));
+
+        let fresh = written + Duration::from_secs(59);
+        assert!(cache.get_at("webtab-a", "https://one.com/", max_age, fresh).unwrap().is_some());
+        let stale = written + Duration::from_secs(61);
+        assert!(cache.get_at("webtab-a", "https://one.com/", max_age, stale).unwrap().is_none());
+        assert!(cache.get_at("webtab-a", "https://one.com/", None, stale).unwrap().is_some());
+        assert!(cache.put("../escape", "https://one.com/", b"png").is_err());
+    }
+
+    #[test]
+    fn test_url_key() {
+        assert_eq!(url_key("https://a.com/"), url_key("https://a.com/"));
+        assert_ne!(url_key("https://a.com/"), url_key("https://b.com/"));
+    }
+}
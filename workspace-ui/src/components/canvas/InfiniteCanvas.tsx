@@ -55,16 +55,49 @@ export const InfiniteCanvas: React.FC = () => {
   const closeContextMenu = useUIStore((s) => s.closeContextMenu);
   const { isModifierPressed, handlers: navHandlers } = useCanvasNavigation();
   const { isSelecting, startSelection, updateSelection, endSelection } = useSelection();
-  const { closeAllTabs } = useWebTabStore();
+  const { closeAllTabs, restoreBoardTabs, setCanvasViewport } = useWebTabStore();
+  const previousBoardIdRef = useRef<string | null>(null);
   
   useItemDrag();
   useItemResize();
   useFileDrop(); // Native OS File Drag-and-Drop
 
-  // Schließe alle Tabs beim Board-Wechsel
+  // Tabs gehören zum Board: beim ersten Board nur wiederherstellen, bei einem
+  // echten Wechsel erst die Tabs des alten Boards schließen
   useEffect(() => {
-    closeAllTabs();
-  }, [boardId, closeAllTabs]);
+    if (!boardId || boardId === previousBoardIdRef.current) return;
+    const previousBoardId = previousBoardIdRef.current;
+    previousBoardIdRef.current = boardId;
+
+    (async () => {
+      if (previousBoardId) await closeAllTabs();
+      await restoreBoardTabs(boardId);
+    })();
+  }, [boardId, closeAllTabs, restoreBoardTabs]);
+
+  // Viewport an das Rust-Layout der nativen Web-Tabs melden (auch bei Fenster-Resize)
+  useEffect(() => {
+    const el = canvasRef.current;
+    if (!el) return;
+
+    const report = () => {
+      const rect = el.getBoundingClientRect();
+      setCanvasViewport({
+        offsetX: viewport.x,
+        offsetY: viewport.y,
+        scale: viewport.scale,
+        originX: rect.left,
+        originY: rect.top,
+        width: rect.width,
+        height: rect.height,
+      });
+    };
+
+    report();
+    const observer = new ResizeObserver(report);
+    observer.observe(el);
+    return () => observer.disconnect();
+  }, [viewport.x, viewport.y, viewport.scale, setCanvasViewport]);
 
   useEffect(() => {
     const el = canvasRef.current;
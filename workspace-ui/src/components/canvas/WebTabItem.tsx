@@ -1,9 +1,11 @@
 import React, { useEffect, useRef, useCallback, useState, memo, useMemo } from 'react';
-import { Globe, Maximize2, X } from 'lucide-react';
+import { ExternalLink, Globe, Maximize2, ShieldAlert, ShieldOff, X } from 'lucide-react';
+import { openUrl } from '@tauri-apps/plugin-opener';
 import { CanvasItem as CanvasItemType } from '@/models';
 import { useCanvasStore, useUIStore } from '@/stores';
 import { useWebTabStore } from '@/stores/webTabStore';
 import { useItemDrag, useItemResize } from '@/hooks';
+import { getWebTabContentBlocking } from '@/services/contentBlocking';
 import { isItemInFocusZone } from '@/utils/geometry';
 
 // ============================================================================
@@ -19,12 +21,15 @@ const WebTabItemComponent: React.FC<WebTabItemProps> = ({ item }) => {
   const {
     createTab,
     updateTabBounds,
+    setTabCanvasRect,
     closeTab,
     focusTab,
     unfocusAllTabs,
     setTabVisible,
     getTabByItemId,
+    clearPolicyViolation,
   } = useWebTabStore();
+  const lastPolicyViolation = useWebTabStore((s) => s.lastPolicyViolation);
 
   const focusMode = useUIStore((s) => s.focusMode);
   const focusZone = useUIStore((s) => s.focusZone);
@@ -41,6 +46,7 @@ const WebTabItemComponent: React.FC<WebTabItemProps> = ({ item }) => {
   const [urlInput, setUrlInput] = useState(item.url || 'https://');
   const [isUrlFocused, setIsUrlFocused] = useState(false);
   const [tabCreated, setTabCreated] = useState(false);
+  const [blockingUnsupported, setBlockingUnsupported] = useState(false);
 
   // URL-Input synchronisieren wenn item.url sich ändert
   useEffect(() => {
@@ -51,6 +57,14 @@ const WebTabItemComponent: React.FC<WebTabItemProps> = ({ item }) => {
 
   const isSelected = selectedIds.has(item.id);
   const tab = getTabByItemId(item.id);
+
+  // Native Tabs können Link-Klicks nicht von Redirects unterscheiden (wry meldet
+  // keine Nutzer-Geste) - blockierte Navigationen daher hier anzeigen und das
+  // Öffnen im System-Browser dem Nutzer überlassen
+  const blockedNavigation =
+    tab && lastPolicyViolation?.tab_id === tab.id && lastPolicyViolation.phase === 'in_page_automatic'
+      ? lastPolicyViolation
+      : null;
   const lastBoundsRef = useRef<string>('');
   const creatingTabRef = useRef<boolean>(false);
 
@@ -88,16 +102,23 @@ const WebTabItemComponent: React.FC<WebTabItemProps> = ({ item }) => {
   useEffect(() => {
     const existingTab = getTabByItemId(item.id);
 
+    // Profilwechsel braucht ein neues Fenster (Datenspeicher ist fix)
+    const sameProfile = existingTab?.profile === item.profile;
+    // Verglichen wird die Item-URL, mit der der Tab erstellt wurde - die
+    // aktuelle Tab-URL wandert mit Links und Redirects. Wiederhergestellte
+    // Tabs gehören bereits zu diesem Item.
+    const sameUrl = (existingTab?.sourceUrl ?? item.url) === item.url;
+
     // Wenn Tab bereits existiert und URL gleich ist, nichts tun
-    if (existingTab && existingTab.url === item.url) {
+    if (existingTab && sameUrl && sameProfile) {
       if (!tabCreated) {
         setTabCreated(true);
       }
       return;
     }
 
-    // Wenn Tab existiert aber URL anders ist, Tab schließen und neu erstellen
-    if (existingTab && existingTab.url !== item.url) {
+    // Wenn Tab existiert aber URL oder Profil anders ist, Tab schließen und neu erstellen
+    if (existingTab && (!sameUrl || !sameProfile)) {
       closeTab(existingTab.id).then(() => {
         setTabCreated(false);
       });
@@ -118,7 +139,7 @@ const WebTabItemComponent: React.FC<WebTabItemProps> = ({ item }) => {
       creatingTabRef.current = true;
       const bounds = calculateScreenBounds();
 
-      createTab(item.id, item.url, bounds).then((tabId) => {
+      createTab(item.id, item.url, bounds, undefined, item.profile).then((tabId) => {
         creatingTabRef.current = false;
         if (tabId) {
           setTabCreated(true);
@@ -127,12 +148,25 @@ const WebTabItemComponent: React.FC<WebTabItemProps> = ({ item }) => {
         creatingTabRef.current = false;
       });
     }
-  }, [item.url, item.id, tabCreated, createTab, getTabByItemId, closeTab, calculateScreenBounds]);
+  }, [item.url, item.id, item.profile, tabCreated, createTab, getTabByItemId, closeTab, calculateScreenBounds]);
+
+  // Native Tabs: nur der Canvas-Rect der Karte geht an Rust, Pan/Zoom
+  // kommt gesammelt über setCanvasViewport (InfiniteCanvas)
+  useEffect(() => {
+    if (!tab || tab.backend !== 'native') return;
 
-  // Bounds synchronisieren wenn sich Position/Größe/Viewport ändert
+    setTabCanvasRect(tab.id, {
+      x: item.x,
+      y: item.y + HEADER_HEIGHT,
+      width: item.width,
+      height: item.height - HEADER_HEIGHT,
+    });
+  }, [tab?.id, tab?.backend, setTabCanvasRect, item.x, item.y, item.width, item.height]);
+
+  // OSR-Tabs: Bounds synchronisieren wenn sich Position/Größe/Viewport ändert
   // WICHTIG: Sofortige Updates ohne RAF für bessere Synchronisation beim Panning
   useEffect(() => {
-    if (!tab) return;
+    if (!tab || tab.backend === 'native') return;
 
     const bounds = calculateScreenBounds();
     const boundsKey = JSON.stringify(bounds);
@@ -145,6 +179,27 @@ const WebTabItemComponent: React.FC<WebTabItemProps> = ({ item }) => {
     updateTabBounds(tab.id, bounds);
   }, [tab, calculateScreenBounds, updateTabBounds, item.x, item.y, item.width, item.height, viewport.x, viewport.y, viewport.scale]);
 
+  // Native Tabs unter WebKitGTK/WKWebView haben keinen Request-Hook - das
+  // Content Blocking greift dort nicht, die Karte zeigt das an
+  useEffect(() => {
+    if (!tab || tab.backend !== 'native') {
+      setBlockingUnsupported(false);
+      return;
+    }
+
+    let cancelled = false;
+    getWebTabContentBlocking(tab.id)
+      .then((stats) => {
+        if (!cancelled) setBlockingUnsupported(!stats.supported);
+      })
+      .catch((error) => {
+        console.error('Failed to get content blocking state:', error);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [tab?.id, tab?.backend]);
+
   // Tab schließen wenn Item entfernt wird
   useEffect(() => {
     return () => {
@@ -196,6 +251,20 @@ const WebTabItemComponent: React.FC<WebTabItemProps> = ({ item }) => {
     removeItem(item.id);
   };
 
+  const handleOpenBlockedExternally = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    if (!blockedNavigation) return;
+    openUrl(blockedNavigation.url).catch((error) => {
+      console.error('Failed to open URL externally:', error);
+    });
+    clearPolicyViolation();
+  };
+
+  const handleDismissBlocked = (e: React.MouseEvent) => {
+    e.stopPropagation();
+    clearPolicyViolation();
+  };
+
   const handleZoomToFit = (e: React.MouseEvent) => {
     e.stopPropagation();
     zoomToItem(item.id);
@@ -289,6 +358,48 @@ const WebTabItemComponent: React.FC<WebTabItemProps> = ({ item }) => {
           `}
         />
 
+        {/* Content Blocking auf dieser Plattform nicht verfügbar */}
+        {blockingUnsupported && (
+          <span
+            className="shrink-0 text-gray-400"
+            title="Werbe- und Tracker-Blocker wird in diesem Tab nicht unterstützt"
+          >
+            <ShieldOff size={12} />
+          </span>
+        )}
+
+        {/* Blockierte Navigation (nur im Header sichtbar - der Inhalt liegt unter dem nativen Fenster) */}
+        {blockedNavigation && (
+          <div
+            className="flex items-center gap-1 shrink-0 text-xs text-amber-700 bg-amber-50 border border-amber-200 rounded px-1.5 py-0.5"
+            title={blockedNavigation.url}
+          >
+            {blockedNavigation.rule === 'open_externally' ? (
+              <button
+                onClick={handleOpenBlockedExternally}
+                onMouseDown={(e) => e.stopPropagation()}
+                className="flex items-center gap-1 hover:text-amber-900"
+              >
+                <ExternalLink size={12} />
+                Im Browser öffnen
+              </button>
+            ) : (
+              <span className="flex items-center gap-1">
+                <ShieldAlert size={12} />
+                Blockiert
+              </span>
+            )}
+            <button
+              onClick={handleDismissBlocked}
+              onMouseDown={(e) => e.stopPropagation()}
+              className="p-0.5 rounded hover:bg-amber-100"
+              title="Ausblenden"
+            >
+              <X size={10} />
+            </button>
+          </div>
+        )}
+
         {/* Action Buttons */}
         <div className="flex items-center gap-1 opacity-0 group-hover:opacity-100 transition-opacity">
           <button
@@ -332,6 +443,15 @@ const WebTabItemComponent: React.FC<WebTabItemProps> = ({ item }) => {
           </div>
         ) : (
           <div className="absolute inset-0">
+            {/* Versteckter Tab: letztes Vorschaubild statt leerer Fläche */}
+            {tab && (!tab.isVisible || tab.isOnScreen === false) && tab.thumbnailUrl && (
+              <img
+                src={tab.thumbnailUrl}
+                alt=""
+                draggable={false}
+                className="absolute inset-0 w-full h-full object-cover object-top"
+              />
+            )}
             {!tab?.isFocused && (
               <div className="absolute inset-0 hover:bg-black/5 flex items-center justify-center opacity-0 hover:opacity-100 transition-opacity cursor-pointer">
                 <div className="bg-white/95 px-4 py-2 rounded-lg shadow-lg border border-gray-200">
@@ -1,13 +1,18 @@
-import React, { useState } from 'react';
+import React, { useState, useRef } from 'react';
 import { useCanvasStore, useUIStore } from '@/stores';
 import { generateId } from '@/utils';
 import { DEFAULT_ITEM_WIDTH, DEFAULT_ITEM_HEIGHT, DEFAULT_GROUP_WIDTH, DEFAULT_GROUP_HEIGHT } from '@/models';
+import { useCanvasExclusion } from '@/hooks';
 
 export const AddModal: React.FC = () => {
   const { addModal, closeAddModal } = useUIStore();
   const { addItem, viewport } = useCanvasStore();
   const [inputValue, setInputValue] = useState('');
 
+  // Native Web-Tabs nicht über dem Overlay malen lassen
+  const exclusionRef = useRef<HTMLDivElement>(null);
+  useCanvasExclusion(exclusionRef, addModal.open);
+
   if (!addModal.open) return null;
 
   const detectBadge = (text: string) => {
@@ -63,7 +68,7 @@ export const AddModal: React.FC = () => {
   ];
 
   return (
-    <div className="fixed inset-0 bg-white/90 backdrop-blur-sm z-50 flex items-center justify-center p-4">
+    <div ref={exclusionRef} className="fixed inset-0 bg-white/90 backdrop-blur-sm z-50 flex items-center justify-center p-4">
       <div className="w-full max-w-2xl text-center space-y-6 relative z-10">
         <input
           autoFocus
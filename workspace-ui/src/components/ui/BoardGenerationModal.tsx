@@ -1,4 +1,4 @@
-import React, { useState, useCallback } from 'react';
+import React, { useState, useCallback, useRef } from 'react';
 import {
   X,
   Sparkles,
@@ -11,7 +11,7 @@ import {
   ChevronRight,
 } from 'lucide-react';
 import { useUIStore, useCanvasStore } from '@/stores';
-import { useToolProfileStore } from '@/stores/toolProfileStore';
+import { useToolProfileStore, resolveToolBrowserProfile } from '@/stores/toolProfileStore';
 import {
   generateBoardFromPrompt,
   GeneratedBoard,
@@ -21,6 +21,7 @@ import {
 } from '@/services/ai';
 import { generateId } from '@/utils';
 import { GRID_SIZE } from '@/constants/canvas';
+import { useCanvasExclusion } from '@/hooks';
 
 // ============================================
 // Layout Constants - Workspace Style
@@ -442,6 +443,10 @@ export const BoardGenerationModal: React.FC = () => {
           badge,
           color,
           url: itemLayout.resolvedUrl,
+          // Gepinntes Browser-Profil des Tools (z.B. zweiter GitHub-Account)
+          profile: itemLayout.item.toolId
+            ? resolveToolBrowserProfile(itemLayout.item.toolId)
+            : undefined,
           createdAt: Date.now(),
           updatedAt: Date.now(),
         });
@@ -472,12 +477,16 @@ export const BoardGenerationModal: React.FC = () => {
     clearBoardGenerationResult();
   }, [setBoardGenerationError, clearBoardGenerationResult]);
 
+  // Native Web-Tabs nicht über dem Overlay malen lassen
+  const exclusionRef = useRef<HTMLDivElement>(null);
+  useCanvasExclusion(exclusionRef, boardGenerationModal.open);
+
   if (!boardGenerationModal.open) return null;
 
   const board = boardGenerationModal.board;
 
   return (
-    <div className="fixed inset-0 bg-black/40 backdrop-blur-sm z-50 flex items-center justify-center p-4">
+    <div ref={exclusionRef} className="fixed inset-0 bg-black/40 backdrop-blur-sm z-50 flex items-center justify-center p-4">
       <div className="bg-white rounded-2xl shadow-2xl w-full max-w-3xl max-h-[85vh] flex flex-col overflow-hidden">
         {/* Header */}
         <div className="flex items-center justify-between px-6 py-4 border-b border-gray-100">
@@ -1,10 +1,11 @@
-import React, { useState, useCallback, useEffect } from 'react';
+import React, { useState, useCallback, useEffect, useRef } from 'react';
 import { Plus, BrainCircuit, Globe, Search, MoreHorizontal, Sparkles, LayoutGrid, ArrowRight, X, Focus, Grid3X3, Keyboard, ListTodo, Layout, Settings2 } from 'lucide-react';
 import { useUIStore, useCanvasStore } from '@/stores';
 import { autoClusterItems } from '@/services/clustering';
 import { generateTasksFromItems, extractAllWebviewContents, loadApiKey, saveApiKey, WebviewContent } from '@/services/ai';
 import { generateId } from '@/utils';
 import { DEFAULT_WEBVIEW_WIDTH, DEFAULT_WEBVIEW_HEIGHT } from '@/models';
+import { useCanvasExclusion } from '@/hooks';
 
 export const BottomToolbar: React.FC = () => {
   const {
@@ -167,8 +168,12 @@ export const BottomToolbar: React.FC = () => {
     }
   };
 
+  // Native Web-Tabs nicht über der Toolbar malen lassen
+  const exclusionRef = useRef<HTMLDivElement>(null);
+  useCanvasExclusion(exclusionRef);
+
   return (
-    <div className="fixed bottom-6 left-1/2 -translate-x-1/2 z-40">
+    <div ref={exclusionRef} className="fixed bottom-6 left-1/2 -translate-x-1/2 z-40">
       <div className="flex items-center gap-0.5 px-3 py-1.5 bg-white border border-gray-200 shadow-sm rounded-full transition-all hover:shadow-md">
         {/* Plus - Main Action */}
         <button
@@ -1,6 +1,7 @@
 import React, { useEffect, useRef } from 'react';
 import { Copy, Trash2, Link2, Edit3, Layers } from 'lucide-react';
 import { useUIStore, useCanvasStore } from '@/stores';
+import { useCanvasExclusion } from '@/hooks';
 
 export const ContextMenu: React.FC = () => {
   const contextMenu = useUIStore((s) => s.contextMenu);
@@ -42,6 +43,8 @@ export const ContextMenu: React.FC = () => {
     }
   }, [contextMenu.open, closeContextMenu]);
 
+  useCanvasExclusion(menuRef, contextMenu.open);
+
   if (!contextMenu.open) return null;
 
   const handleDuplicate = () => {
@@ -1,12 +1,17 @@
-import React from 'react';
+import React, { useRef } from 'react';
 import { X, Keyboard } from 'lucide-react';
 import { useUIStore } from '@/stores';
 import { isMac } from '@/utils';
+import { useCanvasExclusion } from '@/hooks';
 
 export const HelpModal: React.FC = () => {
   const helpModal = useUIStore((s) => s.helpModal);
   const closeHelpModal = useUIStore((s) => s.closeHelpModal);
 
+  // Native Web-Tabs nicht über dem Overlay malen lassen
+  const exclusionRef = useRef<HTMLDivElement>(null);
+  useCanvasExclusion(exclusionRef, helpModal.open);
+
   if (!helpModal.open) return null;
 
   const modifier = isMac() ? '⌘' : 'Ctrl';
@@ -39,7 +44,7 @@ export const HelpModal: React.FC = () => {
   ];
 
   return (
-    <div className="fixed inset-0 z-50 flex items-center justify-center">
+    <div ref={exclusionRef} className="fixed inset-0 z-50 flex items-center justify-center">
       {/* Backdrop */}
       <div
         className="absolute inset-0 bg-black/50 backdrop-blur-sm"
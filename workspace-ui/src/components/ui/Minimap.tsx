@@ -1,5 +1,6 @@
 import React, { useMemo, useCallback, useRef } from 'react';
 import { useCanvasStore, useUIStore } from '@/stores';
+import { useCanvasExclusion } from '@/hooks';
 
 const MINIMAP_WIDTH = 180;
 const MINIMAP_HEIGHT = 120;
@@ -120,6 +121,8 @@ export const Minimap: React.FC = () => {
     window.addEventListener('mouseup', handleMouseUp);
   }, [minimapScale, bounds, viewport.scale, setViewport]);
 
+  useCanvasExclusion(minimapRef, showMinimap);
+
   if (!showMinimap) return null;
 
   const itemsArray = Array.from(items.values());
@@ -1,6 +1,7 @@
 import React, { useEffect, useRef, useMemo } from 'react';
 import { Search, X, ArrowRight } from 'lucide-react';
 import { useUIStore, useCanvasStore } from '@/stores';
+import { useCanvasExclusion } from '@/hooks';
 
 export const SearchPanel: React.FC = () => {
   const searchOpen = useUIStore((s) => s.searchOpen);
@@ -54,10 +55,14 @@ export const SearchPanel: React.FC = () => {
     closeSearch();
   };
 
+  // Native Web-Tabs nicht über dem Overlay malen lassen
+  const exclusionRef = useRef<HTMLDivElement>(null);
+  useCanvasExclusion(exclusionRef, searchOpen);
+
   if (!searchOpen) return null;
 
   return (
-    <div className="fixed inset-0 z-50 flex items-start justify-center pt-[15vh]">
+    <div ref={exclusionRef} className="fixed inset-0 z-50 flex items-start justify-center pt-[15vh]">
       {/* Backdrop */}
       <div
         className="absolute inset-0 bg-black/50 backdrop-blur-sm"
@@ -1,6 +1,7 @@
-import React from 'react';
+import React, { useRef } from 'react';
 import { useCanvasStore, useUIStore } from '@/stores';
 import { ItemStatus } from '@/models';
+import { useCanvasExclusion } from '@/hooks';
 
 const CONTAINER_COLORS = [
   { label: 'Standard', value: 'transparent' },
@@ -17,6 +18,10 @@ export const SidePanel: React.FC = () => {
   const { sidePanel, closeSidePanel } = useUIStore();
   const { items, updateItem } = useCanvasStore();
 
+  // Native Web-Tabs nicht über dem Overlay malen lassen
+  const exclusionRef = useRef<HTMLDivElement>(null);
+  useCanvasExclusion(exclusionRef, sidePanel.open);
+
   if (!sidePanel.open || !sidePanel.itemId) return null;
 
   const item = items.get(sidePanel.itemId);
@@ -37,7 +42,7 @@ export const SidePanel: React.FC = () => {
   };
 
   return (
-    <div className="absolute right-0 top-0 bottom-0 w-96 bg-white shadow-xl border-l border-gray-100 p-6 flex flex-col z-20">
+    <div ref={exclusionRef} className="absolute right-0 top-0 bottom-0 w-96 bg-white shadow-xl border-l border-gray-100 p-6 flex flex-col z-20">
       <div className="flex justify-between items-start mb-6">
         <input
           type="text"
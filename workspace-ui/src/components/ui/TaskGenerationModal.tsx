@@ -1,9 +1,10 @@
-import React, { useState, useCallback } from 'react';
+import React, { useState, useCallback, useRef } from 'react';
 import { X, Sparkles, CheckCircle2, AlertCircle, Loader2, Trash2, ArrowUp, ArrowRight, ArrowDown } from 'lucide-react';
 import { useUIStore, useCanvasStore } from '@/stores';
 import { GeneratedTask } from '@/services/ai';
 import { generateId } from '@/utils';
 import { DEFAULT_ITEM_WIDTH, DEFAULT_ITEM_HEIGHT } from '@/models';
+import { useCanvasExclusion } from '@/hooks';
 
 export const TaskGenerationModal: React.FC = () => {
   const { taskGenerationModal, closeTaskGenerationModal } = useUIStore();
@@ -66,6 +67,10 @@ export const TaskGenerationModal: React.FC = () => {
     handleClose();
   }, [editedTasks, viewport, addItem, handleClose]);
 
+  // Native Web-Tabs nicht über dem Overlay malen lassen
+  const exclusionRef = useRef<HTMLDivElement>(null);
+  useCanvasExclusion(exclusionRef, taskGenerationModal.open);
+
   if (!taskGenerationModal.open) return null;
 
   const priorityColors = {
@@ -81,7 +86,7 @@ export const TaskGenerationModal: React.FC = () => {
   };
 
   return (
-    <div className="fixed inset-0 bg-black/40 backdrop-blur-sm z-50 flex items-center justify-center p-4">
+    <div ref={exclusionRef} className="fixed inset-0 bg-black/40 backdrop-blur-sm z-50 flex items-center justify-center p-4">
       <div className="bg-white rounded-2xl shadow-2xl w-full max-w-2xl max-h-[80vh] flex flex-col overflow-hidden">
         {/* Header */}
         <div className="flex items-center justify-between px-6 py-4 border-b border-gray-100">
@@ -1,4 +1,4 @@
-import React, { useState, useCallback } from 'react';
+import React, { useState, useCallback, useRef } from 'react';
 import {
   X,
   Settings2,
@@ -24,6 +24,7 @@ import {
 } from 'lucide-react';
 import { useUIStore } from '@/stores';
 import { useToolProfileStore, ToolProfile } from '@/stores/toolProfileStore';
+import { useCanvasExclusion } from '@/hooks';
 
 // ============================================
 // Icon Mapping
@@ -196,10 +197,14 @@ export const ToolProfilesModal: React.FC = () => {
     return acc;
   }, {} as Record<string, ToolProfile[]>);
 
+  // Native Web-Tabs nicht über dem Overlay malen lassen
+  const exclusionRef = useRef<HTMLDivElement>(null);
+  useCanvasExclusion(exclusionRef, toolProfilesModal.open);
+
   if (!toolProfilesModal.open) return null;
 
   return (
-    <div className="fixed inset-0 bg-black/40 backdrop-blur-sm z-50 flex items-center justify-center p-4">
+    <div ref={exclusionRef} className="fixed inset-0 bg-black/40 backdrop-blur-sm z-50 flex items-center justify-center p-4">
       <div className="bg-white rounded-2xl shadow-2xl w-full max-w-xl max-h-[80vh] flex flex-col overflow-hidden">
         {/* Header */}
         <div className="flex items-center justify-between px-6 py-4 border-b border-gray-100">
@@ -5,3 +5,4 @@ export { useItemResize } from './useItemResize';
 export { useKeyboard } from './useKeyboard';
 export { useProximity } from './useProximity';
 export { useFileDrop } from './useFileDrop';
+export { useCanvasExclusion } from './useCanvasExclusion';
@@ -0,0 +1,43 @@
+import { RefObject, useEffect, useId } from 'react';
+import { setCanvasExclusion, removeCanvasExclusion } from '@/services/webTabBackends';
+
+// ============================================================================
+// CANVAS EXCLUSION HOOK - App-Oberfläche vor nativen Web-Tabs schützen
+// ============================================================================
+
+/**
+ * Meldet den Bereich eines Elements (Panel, Modal, Menü) an Rust, solange
+ * `active` ist. Native Web-Tabs schrumpfen oder verstecken sich dann, statt
+ * über das Element zu malen.
+ */
+export const useCanvasExclusion = (ref: RefObject<HTMLElement | null>, active = true) => {
+  const id = useId();
+
+  useEffect(() => {
+    const el = ref.current;
+    if (!active || !el) return;
+
+    const report = () => {
+      const rect = el.getBoundingClientRect();
+      setCanvasExclusion(id, {
+        x: rect.left,
+        y: rect.top,
+        width: rect.width,
+        height: rect.height,
+      }).catch((error) => console.error('Failed to set canvas exclusion:', error));
+    };
+
+    report();
+    const observer = new ResizeObserver(report);
+    observer.observe(el);
+    window.addEventListener('resize', report);
+
+    return () => {
+      observer.disconnect();
+      window.removeEventListener('resize', report);
+      removeCanvasExclusion(id).catch((error) =>
+        console.error('Failed to remove canvas exclusion:', error)
+      );
+    };
+  }, [id, ref, active]);
+};
@@ -2,20 +2,17 @@ import React from "react";
 import ReactDOM from "react-dom/client";
 import App from "./App";
 import { initWebTabListeners } from "./stores";
-import { clearAllWorkspaceData } from "./services/persistence";
+import { initBoardStorage } from "./services/persistence";
 
 // Initialize Web Tab event listeners
 initWebTabListeners();
 
-// Alle Boards löschen für sauberen Test-Start
-// TODO: Entfernen nach Testing
-if (localStorage.getItem('workspace_boards')) {
-  clearAllWorkspaceData();
-  console.log('All boards cleared for testing');
-}
-
-ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
-  <React.StrictMode>
-    <App />
-  </React.StrictMode>,
-);
+// Boards von der Platte laden (inkl. einmaliger LocalStorage-Migration),
+// bevor das erste Board geöffnet wird
+initBoardStorage().finally(() => {
+  ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
+    <React.StrictMode>
+      <App />
+    </React.StrictMode>,
+  );
+});
@@ -12,6 +12,7 @@ export interface BaseItem {
   badge: ItemBadge;
   color?: string; // Optional: Hex-Code oder Farbname
   url?: string; // Optional: URL für Webview-Items
+  profile?: string; // Optional: Browser-Profil für Webview-Items
   createdAt: number;
   updatedAt: number;
 }
@@ -30,6 +30,9 @@ export interface WebviewContent {
   url: string;
   title: string | null;
   content: string | null;
+  description: string | null;
+  headings: string[];
+  selected_text: string | null;
 }
 
 // ============================================
@@ -112,6 +115,15 @@ function formatContextForGPT(
 
     if (webview) {
       parts.push(`Webview-Titel: ${webview.title || 'Unbekannt'}`);
+      if (webview.description) {
+        parts.push(`Webview-Beschreibung: ${webview.description}`);
+      }
+      if (webview.headings.length > 0) {
+        parts.push(`Webview-Überschriften: ${webview.headings.slice(0, 20).join(' | ')}`);
+      }
+      if (webview.selected_text) {
+        parts.push(`Markierter Text: ${webview.selected_text.substring(0, 1000)}`);
+      }
       if (webview.content) {
         // Content auf 2000 Zeichen pro Webview limitieren
         const truncated = webview.content.substring(0, 2000);
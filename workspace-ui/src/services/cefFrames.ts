@@ -0,0 +1,289 @@
+// CEF Frame Stream - Binäre Frame-Pakete vom cef-plugin empfangen
+// Layout siehe `encode_frame_packet` in cef-plugin/src/transfer.rs,
+// Codecs siehe cef-plugin/src/codec.rs
+
+import { Channel, invoke } from '@tauri-apps/api/core';
+
+const PACKET_VERSION = 3;
+const PACKET_HEADER_LEN = 28;
+const RECT_ENTRY_LEN = 20;
+const FLAG_FULL_FRAME = 0b0000_0001;
+
+export type FrameFormat = 'BGRA8' | 'BGRA8+LZ4' | 'BGRA8+XOR-RLE';
+
+// Format-Byte im Paket-Header -> Format-String
+const FORMAT_CODES: FrameFormat[] = ['BGRA8', 'BGRA8+LZ4', 'BGRA8+XOR-RLE'];
+
+// Bevorzugte Reihenfolge beim Aushandeln
+const DEFAULT_FORMATS: FrameFormat[] = ['BGRA8+XOR-RLE', 'BGRA8+LZ4', 'BGRA8'];
+
+export interface FrameRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface FramePacket {
+  // Abo, über das das Paket kam - Acks gehen an dieses
+  subscriptionId: number;
+  browserId: string;
+  seq: number;
+  width: number;
+  height: number;
+  format: FrameFormat;
+  full: boolean;
+  rects: FrameRect[];
+  // Kodierte Daten je Rect (gleiche Reihenfolge wie `rects`)
+  regions: Uint8Array[];
+}
+
+const textDecoder = new TextDecoder();
+
+export function decodeFramePacket(buffer: ArrayBuffer): FramePacket {
+  const view = new DataView(buffer);
+  if (view.getUint8(0) !== PACKET_VERSION) {
+    throw new Error(`Unsupported frame packet version ${view.getUint8(0)}`);
+  }
+
+  const format = FORMAT_CODES[view.getUint8(1)];
+  if (!format) {
+    throw new Error(`Unknown frame format ${view.getUint8(1)}`);
+  }
+  const full = (view.getUint8(2) & FLAG_FULL_FRAME) !== 0;
+  const idLen = view.getUint8(3);
+  const subscriptionId = view.getUint32(4, true);
+  const seq = Number(view.getBigUint64(8, true));
+  const width = view.getUint32(16, true);
+  const height = view.getUint32(20, true);
+  const rectCount = view.getUint16(24, true);
+
+  const browserId = textDecoder.decode(new Uint8Array(buffer, PACKET_HEADER_LEN, idLen));
+
+  const rects: FrameRect[] = [];
+  const lengths: number[] = [];
+  let offset = PACKET_HEADER_LEN + idLen;
+  for (let i = 0; i < rectCount; i++) {
+    rects.push({
+      x: view.getUint32(offset, true),
+      y: view.getUint32(offset + 4, true),
+      width: view.getUint32(offset + 8, true),
+      height: view.getUint32(offset + 12, true),
+    });
+    lengths.push(view.getUint32(offset + 16, true));
+    offset += RECT_ENTRY_LEN;
+  }
+
+  const regions = lengths.map((length) => {
+    const region = new Uint8Array(buffer, offset, length);
+    offset += length;
+    return region;
+  });
+
+  return { subscriptionId, browserId, seq, width, height, format, full, rects, regions };
+}
+
+// ============================================================================
+// CODECS
+// ============================================================================
+
+// LZ4-Block mit vorangestellter Größe (lz4_flex `compress_prepend_size`)
+function decodeLz4(data: Uint8Array): Uint8Array {
+  const size = (data[0] | (data[1] << 8) | (data[2] << 16) | (data[3] << 24)) >>> 0;
+  const out = new Uint8Array(size);
+  let i = 4;
+  let o = 0;
+
+  const readLength = (base: number) => {
+    let length = base;
+    if (base === 15) {
+      let byte;
+      do {
+        byte = data[i++];
+        length += byte;
+      } while (byte === 255);
+    }
+    return length;
+  };
+
+  while (i < data.length) {
+    const token = data[i++];
+    const literals = readLength(token >> 4);
+    out.set(data.subarray(i, i + literals), o);
+    i += literals;
+    o += literals;
+    if (i >= data.length) break;
+
+    const matchOffset = data[i] | (data[i + 1] << 8);
+    i += 2;
+    const matchLength = readLength(token & 0x0f) + 4;
+    for (let k = 0; k < matchLength; k++, o++) {
+      out[o] = out[o - matchOffset];
+    }
+  }
+
+  return out;
+}
+
+// RLE über ganze Pixel (siehe `rle_encode`)
+function decodeRle(data: Uint8Array): Uint8Array {
+  const chunks: Uint8Array[] = [];
+  let total = 0;
+  let i = 0;
+
+  const readVarint = () => {
+    let value = 0;
+    let shift = 0;
+    let byte;
+    do {
+      byte = data[i++];
+      value += (byte & 0x7f) * 2 ** shift;
+      shift += 7;
+    } while (byte & 0x80);
+    return value;
+  };
+
+  while (i < data.length) {
+    const tag = data[i++];
+    const count = readVarint();
+    if (tag === 0) {
+      const run = new Uint8Array(count * 4);
+      const pixel = data.subarray(i, i + 4);
+      for (let p = 0; p < run.length; p += 4) run.set(pixel, p);
+      chunks.push(run);
+      i += 4;
+    } else {
+      chunks.push(data.subarray(i, i + count * 4));
+      i += count * 4;
+    }
+    total += count * 4;
+  }
+
+  const out = new Uint8Array(total);
+  let o = 0;
+  for (const chunk of chunks) {
+    out.set(chunk, o);
+    o += chunk.length;
+  }
+  return out;
+}
+
+interface ClientFrame {
+  width: number;
+  height: number;
+  pixels: Uint8Array;
+}
+
+function cropRect(frame: ClientFrame, rect: FrameRect): Uint8Array {
+  const out = new Uint8Array(rect.width * rect.height * 4);
+  const rowBytes = rect.width * 4;
+  for (let row = 0; row < rect.height; row++) {
+    const start = ((rect.y + row) * frame.width + rect.x) * 4;
+    out.set(frame.pixels.subarray(start, start + rowBytes), row * rowBytes);
+  }
+  return out;
+}
+
+function pasteRect(frame: ClientFrame, rect: FrameRect, region: Uint8Array) {
+  const rowBytes = rect.width * 4;
+  for (let row = 0; row < rect.height; row++) {
+    const start = ((rect.y + row) * frame.width + rect.x) * 4;
+    frame.pixels.set(region.subarray(row * rowBytes, (row + 1) * rowBytes), start);
+  }
+}
+
+// Dekodiert Pakete zu rohen BGRA-Regionen und hält dafür pro Browser den
+// aktuellen Frame (Referenz für XOR-RLE)
+export class FrameDecoder {
+  private frames = new Map<string, ClientFrame>();
+
+  decode(packet: FramePacket): FramePacket {
+    let frame = this.frames.get(packet.browserId);
+    if (!frame || frame.width !== packet.width || frame.height !== packet.height) {
+      frame = {
+        width: packet.width,
+        height: packet.height,
+        pixels: new Uint8Array(packet.width * packet.height * 4),
+      };
+      this.frames.set(packet.browserId, frame);
+    } else if (packet.full) {
+      // Volle Frames hängen nicht von früheren Paketen ab (XOR gegen leeren Frame)
+      frame.pixels.fill(0);
+    }
+
+    const regions = packet.rects.map((rect, i) => {
+      const data = packet.regions[i];
+      let pixels: Uint8Array;
+      switch (packet.format) {
+        case 'BGRA8':
+          pixels = data;
+          break;
+        case 'BGRA8+LZ4':
+          pixels = decodeLz4(data);
+          break;
+        case 'BGRA8+XOR-RLE': {
+          pixels = decodeRle(data);
+          const previous = cropRect(frame, rect);
+          for (let p = 0; p < pixels.length; p++) pixels[p] ^= previous[p];
+          break;
+        }
+      }
+      pasteRect(frame, rect, pixels);
+      return pixels;
+    });
+
+    return { ...packet, format: 'BGRA8', regions };
+  }
+
+  // Vollständiger Frame nach allen bisher dekodierten Paketen
+  frame(browserId: string): Uint8Array | undefined {
+    return this.frames.get(browserId)?.pixels;
+  }
+
+  forget(browserId: string) {
+    this.frames.delete(browserId);
+  }
+}
+
+// ============================================================================
+// SUBSCRIPTION
+// ============================================================================
+
+export interface FrameSubscription {
+  format: FrameFormat;
+  unsubscribe: () => Promise<void>;
+}
+
+// Frames abonnieren - `onFrame` bekommt rohe BGRA-Regionen, jedes Paket wird
+// danach bestätigt (Back-Pressure). Die Abo-ID steht im Paket-Header, weil
+// die ersten Pakete vor der Antwort von `cef_subscribe_frames` ankommen.
+export async function subscribeFrames(
+  browserIds: string[] | null,
+  onFrame: (packet: FramePacket, decoder: FrameDecoder) => void,
+  formats: FrameFormat[] = DEFAULT_FORMATS
+): Promise<FrameSubscription> {
+  const channel = new Channel<ArrayBuffer>();
+  const decoder = new FrameDecoder();
+
+  channel.onmessage = (buffer) => {
+    const packet = decoder.decode(decodeFramePacket(buffer));
+    onFrame(packet, decoder);
+    invoke('plugin:cef|cef_ack_frame', {
+      subscriptionId: packet.subscriptionId,
+      id: packet.browserId,
+      seq: packet.seq,
+    }).catch((e) => console.error('Failed to ack frame:', e));
+  };
+
+  const info = await invoke<{ subscription_id: number; format: FrameFormat }>(
+    'plugin:cef|cef_subscribe_frames',
+    { ids: browserIds, formats, channel }
+  );
+
+  return {
+    format: info.format,
+    unsubscribe: async () => {
+      await invoke('plugin:cef|cef_unsubscribe_frames', { subscriptionId: info.subscription_id });
+    },
+  };
+}
@@ -0,0 +1,35 @@
+// Content Blocking für Web-Tabs - Filterlisten (EasyList-Format) aus <app_data>/filters/*.txt
+// Greift in CEF-Tabs und nativen Tabs unter Windows; WebKitGTK/WKWebView bleiben
+// ungefiltert und melden `supported: false`
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface FilterListInfo {
+  name: string;
+  rules: number;
+  // Nicht unterstützte Zeilen (Regex, unbekannte Optionen)
+  skipped: number;
+}
+
+export interface ContentBlockingStats {
+  tab_id: string;
+  // false: Plattform ohne Request-Hook, der Tab wird nicht gefiltert
+  supported: boolean;
+  enabled: boolean;
+  blocked_requests: number;
+  // Regeln aller geladenen Listen
+  rules: number;
+}
+
+// Listen neu einlesen, z.B. nach einem Update der Dateien
+export async function reloadContentFilters(): Promise<FilterListInfo[]> {
+  return invoke<FilterListInfo[]>('reload_content_filters');
+}
+
+export async function setWebTabContentBlocking(tabId: string, enabled: boolean): Promise<void> {
+  await invoke('set_web_tab_content_blocking', { tabId, enabled });
+}
+
+export async function getWebTabContentBlocking(tabId: string): Promise<ContentBlockingStats> {
+  return invoke<ContentBlockingStats>('get_web_tab_content_blocking', { tabId });
+}
@@ -0,0 +1,45 @@
+// Fehler der Web-Tab- und CEF-Commands - Rust liefert { code, message, context }
+// Ältere Commands (z.B. Board-Persistenz) werfen noch reine Strings
+
+export type WorkspaceErrorCode =
+  | 'not_found'
+  | 'invalid_url'
+  | 'invalid_input'
+  | 'window_op'
+  | 'not_initialized'
+  | 'already_exists'
+  | 'conflict'
+  | 'unsupported'
+  | 'timeout'
+  | 'io'
+  | 'policy_violation'
+  // cef-plugin: Navigations-Filter des Hosts hat abgelehnt
+  | 'blocked'
+  | 'internal';
+
+export interface WorkspaceError {
+  code: WorkspaceErrorCode;
+  message: string;
+  // z.B. { kind: 'web_tab', id } bei not_found oder { url } bei invalid_url
+  context: Record<string, unknown> | null;
+}
+
+export function isWorkspaceError(error: unknown): error is WorkspaceError {
+  return (
+    typeof error === 'object' &&
+    error !== null &&
+    typeof (error as WorkspaceError).code === 'string' &&
+    typeof (error as WorkspaceError).message === 'string'
+  );
+}
+
+// Normalisiert alles, was ein invoke() werfen kann
+export function toWorkspaceError(error: unknown): WorkspaceError {
+  if (isWorkspaceError(error)) return error;
+  const message = error instanceof Error ? error.message : String(error);
+  return { code: 'internal', message, context: null };
+}
+
+export function hasErrorCode(error: unknown, code: WorkspaceErrorCode): boolean {
+  return isWorkspaceError(error) && error.code === code;
+}
@@ -2,4 +2,8 @@ export * from './persistence';
 export * from './clustering';
 export * from './realtime';
 export * from './ai';
-
+export * from './webTabBackends';
+export * from './cefFrames';
+export * from './errors';
+export * from './urlPolicy';
+export * from './contentBlocking';
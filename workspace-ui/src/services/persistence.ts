@@ -1,31 +1,79 @@
-// Persistence Service - LocalStorage basierte Speicherung
+// Persistence Service - Boards liegen als Dateien im App-Data-Verzeichnis (Rust),
+// hier wird nur ein synchroner In-Memory-Cache gehalten
 
+import { invoke } from '@tauri-apps/api/core';
 import { Board, BoardMetadata } from '@/models/board';
 import { CanvasItem } from '@/models/item';
 import { Connection } from '@/models/connection';
 import { Viewport, DEFAULT_VIEWPORT } from '@/models/viewport';
 import { generateId } from '@/utils';
 
-const STORAGE_KEY = 'workspace_boards';
+// Legacy-Key: Boards lagen früher komplett im LocalStorage
+const LEGACY_STORAGE_KEY = 'workspace_boards';
 const CURRENT_BOARD_KEY = 'workspace_current_board';
 
-// Hilfsfunktion: Alle Boards aus LocalStorage laden
-const getAllBoards = (): Record<string, Board> => {
+// Cache aller Boards (wird beim Start von der Platte geladen)
+let boardCache: Record<string, Board> = {};
+
+// Hilfsfunktion: Alle Boards aus dem Cache
+const getAllBoards = (): Record<string, Board> => boardCache;
+
+// Hilfsfunktion: Board auf die Platte schreiben (fire-and-forget)
+const persistBoard = (board: Board): void => {
+  invoke('save_board', { board }).catch((e) => {
+    console.error('Fehler beim Speichern des Boards:', e);
+  });
+};
+
+// Ergebnis von migrate_local_storage_boards
+interface MigrationReport {
+  imported: number;
+  skipped: string[];
+}
+
+// Migration der alten LocalStorage-Boards ins App-Data-Verzeichnis. Nicht
+// übernommene Boards bleiben im Legacy-Key und werden beim nächsten Start
+// erneut versucht.
+const migrateLegacyBoards = async (): Promise<void> => {
+  const data = localStorage.getItem(LEGACY_STORAGE_KEY);
+  if (!data) return;
+
   try {
-    const data = localStorage.getItem(STORAGE_KEY);
-    return data ? JSON.parse(data) : {};
+    const boards = JSON.parse(data) as Record<string, unknown>;
+    const report = await invoke<MigrationReport>('migrate_local_storage_boards', { boards });
+
+    if (report.skipped.length === 0) {
+      localStorage.removeItem(LEGACY_STORAGE_KEY);
+      return;
+    }
+
+    console.warn('Boards konnten nicht migriert werden:', report.skipped);
+    const remaining: Record<string, unknown> = {};
+    report.skipped.forEach((id) => {
+      if (id in boards) remaining[id] = boards[id];
+    });
+    localStorage.setItem(LEGACY_STORAGE_KEY, JSON.stringify(remaining));
   } catch (e) {
-    console.error('Fehler beim Laden der Boards:', e);
-    return {};
+    console.error('Fehler bei der Board-Migration:', e);
   }
 };
 
-// Hilfsfunktion: Alle Boards in LocalStorage speichern
-const saveAllBoards = (boards: Record<string, Board>): void => {
+// Boards von der Platte in den Cache laden - muss vor dem ersten Render laufen
+export const initBoardStorage = async (): Promise<void> => {
+  await migrateLegacyBoards();
+
   try {
-    localStorage.setItem(STORAGE_KEY, JSON.stringify(boards));
+    const metadata = await invoke<BoardMetadata[]>('list_boards');
+    const boards = await Promise.all(
+      metadata.map((meta) => invoke<Board | null>('load_board', { boardId: meta.id }))
+    );
+
+    boardCache = {};
+    boards.forEach((board) => {
+      if (board) boardCache[board.id] = board;
+    });
   } catch (e) {
-    console.error('Fehler beim Speichern der Boards:', e);
+    console.error('Fehler beim Laden der Boards:', e);
   }
 };
 
@@ -51,7 +99,7 @@ export const saveBoard = (
   };
   
   boards[boardId] = board;
-  saveAllBoards(boards);
+  persistBoard(board);
   localStorage.setItem(CURRENT_BOARD_KEY, boardId);
   
   return board;
@@ -72,7 +120,7 @@ export const createNewBoard = (name: string = 'Neues Board'): Board => {
   
   const boards = getAllBoards();
   boards[boardId] = board;
-  saveAllBoards(boards);
+  persistBoard(board);
   localStorage.setItem(CURRENT_BOARD_KEY, boardId);
   
   return board;
@@ -113,7 +161,9 @@ export const loadCurrentBoard = (): Board | null => {
 export const deleteBoard = (boardId: string): void => {
   const boards = getAllBoards();
   delete boards[boardId];
-  saveAllBoards(boards);
+  invoke('delete_board', { boardId }).catch((e) => {
+    console.error('Fehler beim Löschen des Boards:', e);
+  });
   
   // Falls das gelöschte Board das aktuelle war, aktuelles Board zurücksetzen
   const currentBoardId = localStorage.getItem(CURRENT_BOARD_KEY);
@@ -148,7 +198,9 @@ export const renameBoard = (boardId: string, newName: string): void => {
   if (boards[boardId]) {
     boards[boardId].name = newName;
     boards[boardId].updatedAt = Date.now();
-    saveAllBoards(boards);
+    invoke('rename_board', { boardId, name: newName }).catch((e) => {
+      console.error('Fehler beim Umbenennen des Boards:', e);
+    });
   }
 };
 
@@ -177,7 +229,7 @@ export const importBoard = (jsonString: string): Board | null => {
     
     const boards = getAllBoards();
     boards[newBoard.id] = newBoard;
-    saveAllBoards(boards);
+    persistBoard(newBoard);
     
     return newBoard;
   } catch (e) {
@@ -204,14 +256,19 @@ export const createAutoSave = (delayMs: number = 1000) => {
 
 // Alle Workspace-Daten löschen
 export const clearAllWorkspaceData = (): void => {
-  localStorage.removeItem(STORAGE_KEY);
-  localStorage.removeItem(CURRENT_BOARD_KEY);
+  deleteAllBoards();
   console.log('Workspace data cleared');
 };
 
 // Alle Boards löschen (für Testing)
 export const deleteAllBoards = (): void => {
-  localStorage.removeItem(STORAGE_KEY);
+  Object.keys(boardCache).forEach((boardId) => {
+    invoke('delete_board', { boardId }).catch((e) => {
+      console.error('Fehler beim Löschen des Boards:', e);
+    });
+  });
+  boardCache = {};
+  localStorage.removeItem(LEGACY_STORAGE_KEY);
   localStorage.removeItem(CURRENT_BOARD_KEY);
   console.log('All boards deleted');
 };
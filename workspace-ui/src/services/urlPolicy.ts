@@ -0,0 +1,54 @@
+// URL-Policy für Web-Tabs - welche Seiten ein Tab laden darf
+// Host-Muster: `example.com` (inkl. Subdomains), `*.example.com` (nur Subdomains), `*`
+
+import { invoke } from '@tauri-apps/api/core';
+
+export interface HostRules {
+  // Nicht leer = nur diese Hosts
+  allow: string[];
+  deny: string[];
+  // Im System-Browser statt im Tab öffnen
+  open_externally: string[];
+}
+
+export interface BoardUrlPolicy extends HostRules {
+  // Ersetzt die globalen Schemata (null = global übernehmen)
+  allowed_schemes: string[] | null;
+}
+
+export interface UrlPolicy extends HostRules {
+  allowed_schemes: string[];
+  // Board-ID -> Überschreibungen
+  boards: Record<string, BoardUrlPolicy>;
+}
+
+export type UrlPolicyRule = 'scheme' | 'deny' | 'not_allowlisted' | 'open_externally';
+
+// Payload von web-tab-policy-violation
+export interface UrlPolicyViolation {
+  tab_id: string;
+  url: string;
+  rule: UrlPolicyRule;
+  // Schema oder passendes Host-Muster
+  detail: string | null;
+  board_id: string | null;
+  phase: 'create' | 'navigate' | 'in_page' | 'in_page_automatic';
+}
+
+export async function getUrlPolicy(): Promise<UrlPolicy> {
+  return invoke<UrlPolicy>('get_url_policy');
+}
+
+export async function setUrlPolicy(policy: UrlPolicy): Promise<void> {
+  await invoke('set_url_policy', { policy });
+}
+
+// Board, dessen Überschreibungen gelten (null = nur globale Regeln)
+export async function setUrlPolicyBoard(boardId: string | null): Promise<void> {
+  await invoke('set_url_policy_board', { boardId });
+}
+
+// Für Tabs, die nicht über create_web_tab laufen (OSR)
+export async function checkWebTabUrl(tabId: string, url: string): Promise<void> {
+  await invoke('check_web_tab_url', { tabId, url });
+}
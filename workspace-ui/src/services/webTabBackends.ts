@@ -0,0 +1,314 @@
+// Web Tab Backends - Native webtab-* Fenster oder CEF OSR-Browser
+// Beide bieten dieselbe API: create / navigate / Verlauf / updateBounds / setVisible / close
+
+import { invoke } from '@tauri-apps/api/core';
+import { checkWebTabUrl } from './urlPolicy';
+import type { WorkspaceError } from './errors';
+
+export type WebTabBackendKind = 'native' | 'osr';
+
+export interface BackendBounds {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+export interface WebTabBackend {
+  // `profile`: eigener Datenspeicher (Cookies, Storage), sonst der geteilte.
+  // Nur native Tabs - OSR lehnt Profile mit `unsupported` ab
+  create: (tabId: string, url: string, bounds: BackendBounds, profile?: string) => Promise<void>;
+  navigate: (tabId: string, url: string) => Promise<void>;
+  goBack: (tabId: string, steps?: number) => Promise<void>;
+  goForward: (tabId: string, steps?: number) => Promise<void>;
+  reload: (tabId: string, bypassCache?: boolean) => Promise<void>;
+  stop: (tabId: string) => Promise<void>;
+  updateBounds: (tabId: string, bounds: BackendBounds) => Promise<void>;
+  setVisible: (tabId: string, visible: boolean) => Promise<void>;
+  close: (tabId: string) => Promise<void>;
+}
+
+const nativeBackend: WebTabBackend = {
+  create: async (tabId, url, bounds, profile) => {
+    await invoke('create_web_tab', { tabId, url, bounds, profile });
+  },
+  navigate: async (tabId, url) => {
+    await invoke('navigate_web_tab', { tabId, url });
+  },
+  goBack: async (tabId, steps = 1) => {
+    await invoke('web_tab_go_back', { tabId, steps });
+  },
+  goForward: async (tabId, steps = 1) => {
+    await invoke('web_tab_go_forward', { tabId, steps });
+  },
+  // bypassCache holt nur das Hauptdokument neu, Unterressourcen bleiben gecacht
+  reload: async (tabId, bypassCache = false) => {
+    await invoke('web_tab_reload', { tabId, bypassCache });
+  },
+  stop: async (tabId) => {
+    await invoke('web_tab_stop', { tabId });
+  },
+  updateBounds: async (tabId, bounds) => {
+    await invoke('update_web_tab_bounds', { tabId, bounds });
+  },
+  setVisible: async (tabId, visible) => {
+    await invoke('set_web_tab_visible', { tabId, visible });
+  },
+  close: async (tabId) => {
+    await invoke('close_web_tab', { tabId });
+  },
+};
+
+// CEF muss einmalig initialisiert werden, bevor Browser erstellt werden
+let cefInitPromise: Promise<void> | null = null;
+
+const ensureCefInitialized = (): Promise<void> => {
+  if (!cefInitPromise) {
+    cefInitPromise = invoke<void>('plugin:cef|cef_init').catch((error) => {
+      cefInitPromise = null;
+      throw error;
+    });
+  }
+  return cefInitPromise;
+};
+
+const osrBackend: WebTabBackend = {
+  create: async (tabId, url, bounds, profile) => {
+    // Das cef-plugin teilt einen CefRequestContext für alle Browser - ein
+    // Profil würde still ignoriert und Cookies mit anderen Tabs teilen
+    if (profile) {
+      const error: WorkspaceError = {
+        code: 'unsupported',
+        message: 'Profiles are not supported for OSR web tabs',
+        context: null,
+      };
+      throw error;
+    }
+    // Das cef-plugin setzt die URL-Policy selbst durch (auch für Links und
+    // Redirects); die Vorab-Prüfung liefert den genauen Policy-Fehler
+    await checkWebTabUrl(tabId, url);
+    await ensureCefInitialized();
+    await invoke('plugin:cef|cef_create_browser', { id: tabId, url, bounds });
+  },
+  navigate: async (tabId, url) => {
+    await checkWebTabUrl(tabId, url);
+    await invoke('plugin:cef|cef_navigate', { id: tabId, url });
+  },
+  // CEF kennt nur Einzelschritte
+  goBack: async (tabId, steps = 1) => {
+    for (let i = 0; i < steps; i++) {
+      await invoke('plugin:cef|cef_go_back', { id: tabId });
+    }
+  },
+  goForward: async (tabId, steps = 1) => {
+    for (let i = 0; i < steps; i++) {
+      await invoke('plugin:cef|cef_go_forward', { id: tabId });
+    }
+  },
+  reload: async (tabId, bypassCache = false) => {
+    await invoke('plugin:cef|cef_reload', { id: tabId, bypassCache });
+  },
+  stop: async (tabId) => {
+    await invoke('plugin:cef|cef_stop', { id: tabId });
+  },
+  updateBounds: async (tabId, bounds) => {
+    await invoke('plugin:cef|cef_update_bounds', { id: tabId, bounds });
+  },
+  // Versteckte OSR-Browser werden pausiert
+  setVisible: async (tabId, visible) => {
+    await invoke('plugin:cef|cef_set_visibility', { id: tabId, visible });
+  },
+  close: async (tabId) => {
+    await invoke('plugin:cef|cef_close_browser', { id: tabId });
+  },
+};
+
+// Sichtbarer Canvas-Bereich - OSR-Browser außerhalb oder hinter einem
+// Fullscreen-Tab rendern nur noch mit ~1 fps
+export async function setOsrViewport(
+  viewport: BackendBounds,
+  fullscreenTabId: string | null = null
+): Promise<void> {
+  if (!cefInitPromise) return;
+  await invoke('plugin:cef|cef_set_viewport', { viewport, fullscreenId: fullscreenTabId });
+}
+
+// Canvas-Transformation: screen = canvas * scale + offset
+export interface CanvasTransform {
+  offsetX: number;
+  offsetY: number;
+  scale: number;
+}
+
+// DOM-Maus-/Wheel-Event an einen OSR-Browser weiterleiten - Koordinaten,
+// Klickanzahl und Modifier rechnet Rust aus
+export async function sendOsrMouseEvent(
+  tabId: string,
+  event: MouseEvent | WheelEvent,
+  transform: CanvasTransform
+): Promise<void> {
+  const wheel = event instanceof WheelEvent ? event : null;
+  await invoke('plugin:cef|cef_send_dom_mouse_event', {
+    id: tabId,
+    transform,
+    event: {
+      type: event.type,
+      clientX: event.clientX,
+      clientY: event.clientY,
+      button: event.button,
+      buttons: event.buttons,
+      deltaX: wheel?.deltaX ?? 0,
+      deltaY: wheel?.deltaY ?? 0,
+      deltaMode: wheel?.deltaMode ?? 0,
+      shiftKey: event.shiftKey,
+      ctrlKey: event.ctrlKey,
+      altKey: event.altKey,
+      metaKey: event.metaKey,
+      timeStamp: event.timeStamp,
+    },
+  });
+}
+
+export async function sendOsrKeyEvent(tabId: string, event: KeyboardEvent): Promise<void> {
+  await invoke('plugin:cef|cef_send_dom_key_event', {
+    id: tabId,
+    event: {
+      type: event.type,
+      key: event.key,
+      code: event.code,
+      keyCode: event.keyCode,
+      location: event.location,
+      repeat: event.repeat,
+      shiftKey: event.shiftKey,
+      ctrlKey: event.ctrlKey,
+      altKey: event.altKey,
+      metaKey: event.metaKey,
+      capsLock: event.getModifierState('CapsLock'),
+    },
+  });
+}
+
+// ============================================================================
+// CANVAS LAYOUT - Native Tabs positioniert Rust aus dem Viewport
+// ============================================================================
+
+// Inhaltsbereich einer Karte in Canvas-Koordinaten
+export interface CanvasRect {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+// Viewport plus Lage/Größe der Canvas-Fläche im Hauptfenster (CSS-Pixel)
+export interface CanvasLayoutViewport extends CanvasTransform {
+  originX: number;
+  originY: number;
+  width: number;
+  height: number;
+}
+
+// Ein Aufruf pro Pan/Zoom für alle nativen Tabs (DPI rechnet Rust)
+export async function setCanvasViewport(viewport: CanvasLayoutViewport): Promise<void> {
+  await invoke('set_canvas_viewport', {
+    viewport: {
+      x: viewport.offsetX,
+      y: viewport.offsetY,
+      scale: viewport.scale,
+      origin_x: viewport.originX,
+      origin_y: viewport.originY,
+      width: viewport.width,
+      height: viewport.height,
+    },
+  });
+}
+
+export async function setWebTabCanvasRect(tabId: string, rect: CanvasRect): Promise<void> {
+  await invoke('set_web_tab_canvas_rect', { tabId, rect });
+}
+
+// Bereich der App-Oberfläche (CSS-Pixel im Hauptfenster), über den Tabs nie malen
+export async function setCanvasExclusion(id: string, rect: CanvasRect): Promise<void> {
+  await invoke('set_canvas_exclusion', { id, rect });
+}
+
+export async function removeCanvasExclusion(id: string): Promise<void> {
+  await invoke('remove_canvas_exclusion', { id });
+}
+
+export function getWebTabBackend(kind: WebTabBackendKind): WebTabBackend {
+  return kind === 'osr' ? osrBackend : nativeBackend;
+}
+
+// Backends, die dieser Build unterstützt (OSR nur mit `cef` Feature)
+export async function listWebTabBackends(): Promise<WebTabBackendKind[]> {
+  return invoke<WebTabBackendKind[]>('list_web_tab_backends');
+}
+
+export async function getDefaultWebTabBackend(): Promise<WebTabBackendKind> {
+  return invoke<WebTabBackendKind>('get_web_tab_backend');
+}
+
+export async function setDefaultWebTabBackend(backend: WebTabBackendKind): Promise<void> {
+  await invoke('set_web_tab_backend', { backend });
+}
+
+// ============================================================================
+// WEB PROFILES - Getrennte Browser-Daten für native Tabs
+// ============================================================================
+
+export interface WebProfile {
+  name: string;
+  store_id: string;
+  created_at: number;
+  open_tabs: number;
+}
+
+// Profile werden beim ersten Tab mit diesem Namen angelegt
+export async function listWebProfiles(): Promise<WebProfile[]> {
+  return invoke<WebProfile[]>('list_web_profiles');
+}
+
+// Löscht Cookies/Storage des Profils - schlägt fehl solange ein Tab es nutzt
+export async function deleteWebProfile(name: string): Promise<void> {
+  await invoke('delete_web_profile', { name });
+}
+
+// ============================================================================
+// THUMBNAILS - Vorschaubilder (native Tabs und OSR-Browser)
+// ============================================================================
+
+export interface ThumbnailOptions {
+  maxWidth?: number;
+  maxHeight?: number;
+  // Gecachtes Bild verwenden, solange es jünger ist (nur native Tabs)
+  maxAgeMs?: number;
+}
+
+// PNG-Bytes als Object-URL - Aufrufer gibt sie mit URL.revokeObjectURL frei
+const pngToObjectUrl = (data: ArrayBuffer): string =>
+  URL.createObjectURL(new Blob([data], { type: 'image/png' }));
+
+// Aktuelles Vorschaubild eines offenen Tabs (wird auf der Platte gecacht)
+export async function captureWebTabThumbnail(
+  tabId: string,
+  options: ThumbnailOptions = {}
+): Promise<string> {
+  const data = await invoke<ArrayBuffer>('capture_web_tab_thumbnail', {
+    tabId,
+    maxWidth: options.maxWidth,
+    maxHeight: options.maxHeight,
+    maxAgeMs: options.maxAgeMs,
+  });
+  return pngToObjectUrl(data);
+}
+
+// Gecachtes Vorschaubild ohne offenen Tab (z.B. Board-Liste), null wenn keins existiert
+export async function getCachedWebTabThumbnail(tabId: string, url: string): Promise<string | null> {
+  try {
+    const data = await invoke<ArrayBuffer>('get_cached_web_tab_thumbnail', { tabId, url });
+    return pngToObjectUrl(data);
+  } catch {
+    return null;
+  }
+}
@@ -9,6 +9,8 @@ export interface ToolProfile {
   name: string;
   defaultUrl: string;
   userUrl?: string;
+  // Browser-Profil für Web-Tabs dieses Tools (getrennte Logins)
+  browserProfile?: string;
   icon: string;
   category: 'automation' | 'development' | 'design' | 'productivity' | 'database';
 }
@@ -20,9 +22,11 @@ interface ToolProfileState {
   getToolUrl: (toolId: string) => string | null;
   setToolUrl: (toolId: string, url: string) => void;
   resetToolUrl: (toolId: string) => void;
+  getToolBrowserProfile: (toolId: string) => string | undefined;
+  setToolBrowserProfile: (toolId: string, profile: string | null) => void;
   getAllTools: () => ToolProfile[];
   getToolById: (toolId: string) => ToolProfile | undefined;
-  addCustomTool: (tool: Omit<ToolProfile, 'userUrl'>) => void;
+  addCustomTool: (tool: Omit<ToolProfile, 'userUrl' | 'browserProfile'>) => void;
 }
 
 // ============================================
@@ -63,10 +67,11 @@ const DEFAULT_TOOLS: ToolProfile[] = [
 // ============================================
 
 const STORAGE_KEY = 'workspace-tool-profiles';
+const BROWSER_PROFILE_STORAGE_KEY = 'workspace-tool-browser-profiles';
 
-function loadFromStorage(): Map<string, string> {
+function loadFromStorage(key: string = STORAGE_KEY): Map<string, string> {
   try {
-    const stored = localStorage.getItem(STORAGE_KEY);
+    const stored = localStorage.getItem(key);
     if (stored) {
       const parsed = JSON.parse(stored);
       return new Map(Object.entries(parsed));
@@ -77,10 +82,10 @@ function loadFromStorage(): Map<string, string> {
   return new Map();
 }
 
-function saveToStorage(userUrls: Map<string, string>): void {
+function saveToStorage(userUrls: Map<string, string>, key: string = STORAGE_KEY): void {
   try {
     const obj = Object.fromEntries(userUrls);
-    localStorage.setItem(STORAGE_KEY, JSON.stringify(obj));
+    localStorage.setItem(key, JSON.stringify(obj));
   } catch (e) {
     console.warn('Failed to save tool profiles to storage:', e);
   }
@@ -93,11 +98,13 @@ function saveToStorage(userUrls: Map<string, string>): void {
 export const useToolProfileStore = create<ToolProfileState>((set, get) => {
   // Load user URLs from storage on init
   const storedUserUrls = loadFromStorage();
+  const storedBrowserProfiles = loadFromStorage(BROWSER_PROFILE_STORAGE_KEY);
 
-  // Merge stored URLs with default tools
+  // Merge stored URLs and profiles with default tools
   const initialTools = DEFAULT_TOOLS.map(tool => ({
     ...tool,
     userUrl: storedUserUrls.get(tool.id),
+    browserProfile: storedBrowserProfiles.get(tool.id),
   }));
 
   return {
@@ -147,11 +154,34 @@ export const useToolProfileStore = create<ToolProfileState>((set, get) => {
       });
     },
 
+    getToolBrowserProfile: (toolId: string) => {
+      return get().tools.find(t => t.id === toolId)?.browserProfile;
+    },
+
+    setToolBrowserProfile: (toolId: string, profile: string | null) => {
+      set((state) => {
+        const updatedTools = state.tools.map(tool =>
+          tool.id === toolId ? { ...tool, browserProfile: profile ?? undefined } : tool
+        );
+
+        // Save to storage
+        const profiles = new Map<string, string>();
+        updatedTools.forEach(tool => {
+          if (tool.browserProfile) {
+            profiles.set(tool.id, tool.browserProfile);
+          }
+        });
+        saveToStorage(profiles, BROWSER_PROFILE_STORAGE_KEY);
+
+        return { tools: updatedTools };
+      });
+    },
+
     getAllTools: () => get().tools,
 
     getToolById: (toolId: string) => get().tools.find(t => t.id === toolId),
 
-    addCustomTool: (tool: Omit<ToolProfile, 'userUrl'>) => {
+    addCustomTool: (tool: Omit<ToolProfile, 'userUrl' | 'browserProfile'>) => {
       set((state) => ({
         tools: [...state.tools, { ...tool, userUrl: undefined }],
       }));
@@ -167,6 +197,10 @@ export function resolveToolUrl(toolId: string): string | null {
   return useToolProfileStore.getState().getToolUrl(toolId);
 }
 
+export function resolveToolBrowserProfile(toolId: string): string | undefined {
+  return useToolProfileStore.getState().getToolBrowserProfile(toolId);
+}
+
 // Export list of known tool IDs for GPT prompt
 export function getKnownToolIds(): string[] {
   return useToolProfileStore.getState().tools.map(t => t.id);
@@ -1,11 +1,28 @@
 import { create } from 'zustand';
 import { invoke } from '@tauri-apps/api/core';
 import { listen } from '@tauri-apps/api/event';
+import {
+  WebTabBackendKind,
+  getWebTabBackend,
+  getDefaultWebTabBackend,
+  setDefaultWebTabBackend,
+  captureWebTabThumbnail,
+  setCanvasViewport,
+  setWebTabCanvasRect,
+  CanvasRect,
+  CanvasLayoutViewport,
+} from '@/services/webTabBackends';
+import { hasErrorCode, toWorkspaceError } from '@/services/errors';
+import { UrlPolicyViolation, setUrlPolicyBoard } from '@/services/urlPolicy';
+import { useCanvasStore } from './canvasStore';
 
 // ============================================================================
 // TYPES
 // ============================================================================
 
+// Vollbild: Canvas-Fläche ausfüllen oder echtes OS-Vollbild
+export type FullscreenMode = 'fill_canvas' | 'os';
+
 export interface TabBounds {
   x: number;
   y: number;
@@ -13,39 +30,115 @@ export interface TabBounds {
   height: number;
 }
 
+// Zurück/Vor-Verlauf eines nativen Tabs - `entries[index]` ist die aktuelle Seite
+export interface NavigationHistory {
+  entries: string[];
+  index: number;
+}
+
 export interface WebTab {
   id: string;
   itemId: string; // Referenz zum Canvas-Item
   url: string;
+  // Item-URL beim Erstellen (fehlt bei wiederhergestellten Tabs)
+  sourceUrl?: string;
+  backend: WebTabBackendKind;
   isFullscreen: boolean;
   isFocused: boolean;
   isVisible: boolean;
+  // false solange das Canvas-Layout in Rust den Tab ausblendet (nur native Tabs)
+  isOnScreen?: boolean;
   bounds: TabBounds;
+  // Browser-Profil (undefined = geteilter Standard-Speicher)
+  profile?: string;
+  // Seitenzustand (OSR über cef://* Events, native Tabs über web-tab-history)
+  title?: string;
+  isLoading?: boolean;
+  canGoBack?: boolean;
+  canGoForward?: boolean;
+  // Verlauf für das Zurück/Vor-Dropdown (nur native Tabs)
+  history?: NavigationHistory;
+  // Object-URL des letzten Vorschaubilds (angezeigt solange der Tab versteckt ist)
+  thumbnailUrl?: string;
+}
+
+// Payload der cef://* Lifecycle-Events aus dem cef-plugin
+interface CefBrowserEvent {
+  browser_id: string;
+  type: string;
+  url?: string;
+  title?: string;
+  http_status?: number;
+  error_text?: string;
+  can_go_back?: boolean;
+  can_go_forward?: boolean;
+  reason?: string;
+}
+
+// Tab aus dem Rust-Snapshot (restore_web_tabs)
+interface RestoredWebTab {
+  id: string;
+  url: string;
+  bounds: TabBounds;
+  is_fullscreen: boolean;
+  history?: NavigationHistory;
+  title?: string | null;
+  profile?: string | null;
+}
+
+// Payload von web-tab-navigated (Link-Klick, Redirect oder neuer Titel)
+interface WebTabNavigatedEvent {
+  tab_id: string;
+  url: string;
+  title: string | null;
+  navigated_at: number;
 }
 
 interface WebTabState {
   tabs: Map<string, WebTab>;
   focusedTabId: string | null;
+  defaultBackend: WebTabBackendKind;
+  // Letzte von der URL-Policy blockierte Navigation (für Hinweise in der UI)
+  lastPolicyViolation: UrlPolicyViolation | null;
 
   // Actions
-  createTab: (itemId: string, url: string, bounds: TabBounds) => Promise<string | null>;
+  createTab: (
+    itemId: string,
+    url: string,
+    bounds: TabBounds,
+    backend?: WebTabBackendKind,
+    profile?: string
+  ) => Promise<string | null>;
+  setDefaultBackend: (backend: WebTabBackendKind) => Promise<void>;
   updateTabBounds: (tabId: string, bounds: TabBounds) => Promise<void>;
+  setTabCanvasRect: (tabId: string, rect: CanvasRect) => Promise<void>;
+  setCanvasViewport: (viewport: CanvasLayoutViewport) => void;
   focusTab: (tabId: string) => Promise<void>;
   unfocusAllTabs: () => Promise<void>;
-  setTabFullscreen: (tabId: string, fullscreen: boolean) => Promise<void>;
+  setTabFullscreen: (tabId: string, fullscreen: boolean, mode?: FullscreenMode) => Promise<void>;
   closeTab: (tabId: string) => Promise<void>;
   closeAllTabs: () => Promise<void>;
+  restoreBoardTabs: (boardId: string) => Promise<void>;
   setTabVisible: (tabId: string, visible: boolean) => Promise<void>;
   navigateTab: (tabId: string, url: string) => Promise<void>;
+  goBack: (tabId: string, steps?: number) => Promise<void>;
+  goForward: (tabId: string, steps?: number) => Promise<void>;
+  reloadTab: (tabId: string, bypassCache?: boolean) => Promise<void>;
+  stopTab: (tabId: string) => Promise<void>;
   getTabByItemId: (itemId: string) => WebTab | undefined;
   bringWebviewsToFront: () => Promise<void>;
   focusMainWindow: () => Promise<void>;
   bringTabToFront: (tabId: string) => Promise<void>;
+  clearPolicyViolation: () => void;
 
   // Internal
   _setTabFocused: (tabId: string, focused: boolean) => void;
   _removeTab: (tabId: string) => void;
   _setTabFullscreenState: (tabId: string, fullscreen: boolean) => void;
+  _addRestoredTabs: (restored: RestoredWebTab[]) => void;
+  _setDefaultBackendState: (backend: WebTabBackendKind) => void;
+  _setPolicyViolation: (violation: UrlPolicyViolation) => void;
+  _updatePageState: (tabId: string, patch: Partial<WebTab>) => void;
 }
 
 // ============================================================================
@@ -56,9 +149,22 @@ interface WebTabState {
 const creatingTabs = new Set<string>();
 
 // RAF-based bounds updates for smooth webview tracking
-let batchedUpdates: Map<string, TabBounds> = new Map();
+let batchedUpdates: Map<string, { bounds: TabBounds; backend: WebTabBackendKind }> = new Map();
 let rafScheduled = false;
 
+// Viewport für das Rust-Layout - höchstens ein Aufruf pro Frame
+let pendingViewport: CanvasLayoutViewport | null = null;
+
+function flushViewport() {
+  const viewport = pendingViewport;
+  pendingViewport = null;
+  if (!viewport) return;
+
+  setCanvasViewport(viewport).catch((error) => {
+    console.error('Failed to set canvas viewport:', error);
+  });
+}
+
 // Flush all pending bounds updates in a single RAF
 function flushBoundsUpdates() {
   if (batchedUpdates.size === 0) {
@@ -71,9 +177,9 @@ function flushBoundsUpdates() {
   rafScheduled = false;
 
   // Process all updates
-  updates.forEach(async (bounds, tabId) => {
+  updates.forEach(async ({ bounds, backend }, tabId) => {
     try {
-      await invoke('update_web_tab_bounds', { tabId, bounds });
+      await getWebTabBackend(backend).updateBounds(tabId, bounds);
     } catch (error) {
       console.error('Failed to update tab bounds:', error);
     }
@@ -83,8 +189,10 @@ function flushBoundsUpdates() {
 export const useWebTabStore = create<WebTabState>((set, get) => ({
   tabs: new Map(),
   focusedTabId: null,
+  defaultBackend: 'native',
+  lastPolicyViolation: null,
 
-  createTab: async (itemId, url, bounds) => {
+  createTab: async (itemId, url, bounds, backend, profile) => {
     const tabId = `webtab-${itemId}`;
 
     // Prüfen ob Tab bereits existiert
@@ -112,22 +220,23 @@ export const useWebTabStore = create<WebTabState>((set, get) => ({
     // Lock setzen
     creatingTabs.add(tabId);
 
+    const tabBackend = backend ?? get().defaultBackend;
+
     try {
-      await invoke('create_web_tab', {
-        tabId,
-        url,
-        bounds: {
-          x: Math.round(bounds.x),
-          y: Math.round(bounds.y),
-          width: Math.round(bounds.width),
-          height: Math.round(bounds.height),
-        },
-      });
+      await getWebTabBackend(tabBackend).create(tabId, url, {
+        x: Math.round(bounds.x),
+        y: Math.round(bounds.y),
+        width: Math.round(bounds.width),
+        height: Math.round(bounds.height),
+      }, profile);
 
       const newTab: WebTab = {
         id: tabId,
         itemId,
         url,
+        sourceUrl: url,
+        backend: tabBackend,
+        profile,
         isFullscreen: false,
         isFocused: false,
         isVisible: true,
@@ -165,7 +274,7 @@ export const useWebTabStore = create<WebTabState>((set, get) => ({
     };
 
     // Add to batched updates (latest wins)
-    batchedUpdates.set(tabId, validBounds);
+    batchedUpdates.set(tabId, { bounds: validBounds, backend: tab.backend });
 
     // Schedule RAF if not already scheduled
     if (!rafScheduled) {
@@ -184,6 +293,22 @@ export const useWebTabStore = create<WebTabState>((set, get) => ({
     });
   },
 
+  // Native Tabs: Rust rechnet Canvas-Rect + Viewport in Fenster-Bounds um
+  setTabCanvasRect: async (tabId, rect) => {
+    try {
+      await setWebTabCanvasRect(tabId, rect);
+    } catch (error) {
+      console.error('Failed to set tab canvas rect:', error);
+    }
+  },
+
+  setCanvasViewport: (viewport) => {
+    if (!pendingViewport) {
+      requestAnimationFrame(flushViewport);
+    }
+    pendingViewport = viewport;
+  },
+
   focusTab: async (tabId) => {
     try {
       await invoke('focus_web_tab', { tabId });
@@ -202,32 +327,51 @@ export const useWebTabStore = create<WebTabState>((set, get) => ({
     }
   },
 
-  setTabFullscreen: async (tabId, fullscreen) => {
+  setTabFullscreen: async (tabId, fullscreen, mode) => {
     try {
-      await invoke('set_web_tab_fullscreen', { tabId, fullscreen });
+      await invoke('set_web_tab_fullscreen', { tabId, fullscreen, mode });
     } catch (error) {
       console.error('Failed to set fullscreen:', error);
     }
   },
 
   closeTab: async (tabId) => {
+    const tab = get().tabs.get(tabId);
     try {
-      await invoke('close_web_tab', { tabId });
+      await getWebTabBackend(tab?.backend ?? 'native').close(tabId);
       get()._removeTab(tabId);
     } catch (error) {
-      console.error('Failed to close tab:', error);
+      // Browser existiert schon nicht mehr (z.B. CEF neu gestartet) - trotzdem aufräumen
+      if (hasErrorCode(error, 'not_found')) {
+        get()._removeTab(tabId);
+        return;
+      }
+      console.error('Failed to close tab:', toWorkspaceError(error).message);
     }
   },
 
   setTabVisible: async (tabId, visible) => {
+    const tab = get().tabs.get(tabId);
+
+    // Vor dem Verstecken ein Vorschaubild holen - versteckte Tabs rendern nicht mehr
+    let thumbnailUrl = tab?.thumbnailUrl;
+    if (tab && tab.isVisible && !visible) {
+      try {
+        thumbnailUrl = await captureWebTabThumbnail(tabId);
+        if (tab.thumbnailUrl) URL.revokeObjectURL(tab.thumbnailUrl);
+      } catch (error) {
+        console.warn('Failed to capture thumbnail:', error);
+      }
+    }
+
     try {
-      await invoke('set_web_tab_visible', { tabId, visible });
-      
+      await getWebTabBackend(tab?.backend ?? 'native').setVisible(tabId, visible);
+
       set((state) => {
         const newTabs = new Map(state.tabs);
         const tab = newTabs.get(tabId);
         if (tab) {
-          newTabs.set(tabId, { ...tab, isVisible: visible });
+          newTabs.set(tabId, { ...tab, isVisible: visible, thumbnailUrl });
         }
         return { tabs: newTabs };
       });
@@ -237,8 +381,9 @@ export const useWebTabStore = create<WebTabState>((set, get) => ({
   },
 
   navigateTab: async (tabId, url) => {
+    const tab = get().tabs.get(tabId);
     try {
-      await invoke('navigate_web_tab', { tabId, url });
+      await getWebTabBackend(tab?.backend ?? 'native').navigate(tabId, url);
       
       set((state) => {
         const newTabs = new Map(state.tabs);
@@ -253,6 +398,43 @@ export const useWebTabStore = create<WebTabState>((set, get) => ({
     }
   },
 
+  // URL und Verlauf kommen danach per web-tab-history bzw. cef://* Event
+  goBack: async (tabId, steps) => {
+    const tab = get().tabs.get(tabId);
+    try {
+      await getWebTabBackend(tab?.backend ?? 'native').goBack(tabId, steps);
+    } catch (error) {
+      console.error('Failed to go back:', error);
+    }
+  },
+
+  goForward: async (tabId, steps) => {
+    const tab = get().tabs.get(tabId);
+    try {
+      await getWebTabBackend(tab?.backend ?? 'native').goForward(tabId, steps);
+    } catch (error) {
+      console.error('Failed to go forward:', error);
+    }
+  },
+
+  reloadTab: async (tabId, bypassCache) => {
+    const tab = get().tabs.get(tabId);
+    try {
+      await getWebTabBackend(tab?.backend ?? 'native').reload(tabId, bypassCache);
+    } catch (error) {
+      console.error('Failed to reload:', error);
+    }
+  },
+
+  stopTab: async (tabId) => {
+    const tab = get().tabs.get(tabId);
+    try {
+      await getWebTabBackend(tab?.backend ?? 'native').stop(tabId);
+    } catch (error) {
+      console.error('Failed to stop loading:', error);
+    }
+  },
+
   getTabByItemId: (itemId) => {
     const tabId = `webtab-${itemId}`;
     return get().tabs.get(tabId);
@@ -289,13 +471,38 @@ export const useWebTabStore = create<WebTabState>((set, get) => ({
   // Alle Tabs schließen
   closeAllTabs: async () => {
     try {
+      // OSR-Browser einzeln schließen, native Fenster gesammelt
+      const osrTabs = Array.from(get().tabs.values()).filter((tab) => tab.backend === 'osr');
+      await Promise.all(osrTabs.map((tab) => getWebTabBackend('osr').close(tab.id)));
       await invoke('close_all_web_tabs');
+      get().tabs.forEach((tab) => {
+        if (tab.thumbnailUrl) URL.revokeObjectURL(tab.thumbnailUrl);
+      });
       set({ tabs: new Map(), focusedTabId: null });
     } catch (error) {
       console.error('Failed to close all tabs:', error);
     }
   },
 
+  restoreBoardTabs: async (boardId) => {
+    // Der Rust-Abgleich übernimmt offene Fenster und schließt verwaiste
+    try {
+      const restored = await invoke<RestoredWebTab[]>('restore_web_tabs', { boardId });
+      get()._addRestoredTabs(restored);
+    } catch (error) {
+      console.error('Failed to restore web tabs:', error);
+    }
+  },
+
+  setDefaultBackend: async (backend) => {
+    try {
+      await setDefaultWebTabBackend(backend);
+      set({ defaultBackend: backend });
+    } catch (error) {
+      console.error('Failed to set web tab backend:', error);
+    }
+  },
+
   // Internal actions (called from event listeners)
   _setTabFocused: (tabId, focused) => {
     set((state) => {
@@ -313,6 +520,9 @@ export const useWebTabStore = create<WebTabState>((set, get) => ({
 
   _removeTab: (tabId) => {
     set((state) => {
+      const thumbnailUrl = state.tabs.get(tabId)?.thumbnailUrl;
+      if (thumbnailUrl) URL.revokeObjectURL(thumbnailUrl);
+
       const newTabs = new Map(state.tabs);
       newTabs.delete(tabId);
       return {
@@ -332,8 +542,98 @@ export const useWebTabStore = create<WebTabState>((set, get) => ({
       return { tabs: newTabs };
     });
   },
+
+  _addRestoredTabs: (restored) => {
+    set((state) => {
+      const newTabs = new Map(state.tabs);
+      restored.forEach((tab) => {
+        newTabs.set(tab.id, {
+          id: tab.id,
+          itemId: tab.id.replace(/^webtab-/, ''),
+          url: tab.url,
+          backend: 'native',
+          isFullscreen: tab.is_fullscreen,
+          isFocused: false,
+          // Fenster starten versteckt, die Canvas-Karte blendet sie ein
+          isVisible: false,
+          bounds: tab.bounds,
+          title: tab.title ?? undefined,
+          profile: tab.profile ?? undefined,
+          ...(tab.history ? historyToPatch(tab.history) : {}),
+        });
+      });
+      return { tabs: newTabs };
+    });
+  },
+
+  clearPolicyViolation: () => {
+    set({ lastPolicyViolation: null });
+  },
+
+  _setDefaultBackendState: (backend) => {
+    set({ defaultBackend: backend });
+  },
+
+  _setPolicyViolation: (violation) => {
+    set({ lastPolicyViolation: violation });
+  },
+
+  _updatePageState: (tabId, patch) => {
+    set((state) => {
+      const tab = state.tabs.get(tabId);
+      if (!tab) return {};
+      const newTabs = new Map(state.tabs);
+      newTabs.set(tabId, { ...tab, ...patch });
+      return { tabs: newTabs };
+    });
+  },
 }));
 
+// Verlauf eines nativen Tabs -> Änderung am Tab
+function historyToPatch(history: NavigationHistory): Partial<WebTab> {
+  return {
+    history,
+    url: history.entries[history.index],
+    canGoBack: history.index > 0,
+    canGoForward: history.index < history.entries.length - 1,
+  };
+}
+
+// cef://* Event -> Änderung am Tab
+function cefEventToPatch(event: CefBrowserEvent): Partial<WebTab> | null {
+  switch (event.type) {
+    case 'load-start':
+      return { url: event.url, isLoading: true };
+    case 'load-end':
+      return { isLoading: false };
+    case 'load-error':
+      console.warn(`OSR tab ${event.browser_id} failed to load ${event.url}: ${event.error_text}`);
+      return { isLoading: false };
+    case 'title-changed':
+      return { title: event.title };
+    case 'url-changed':
+      return { url: event.url };
+    case 'history-changed':
+      return { canGoBack: event.can_go_back, canGoForward: event.can_go_forward };
+    case 'crashed':
+      console.error(`OSR tab ${event.browser_id} crashed: ${event.reason}`);
+      return { isLoading: false };
+    default:
+      return null;
+  }
+}
+
+const CEF_EVENTS = [
+  'load-start',
+  'load-end',
+  'load-error',
+  'title-changed',
+  'url-changed',
+  'favicon-changed',
+  'history-changed',
+  'crashed',
+];
+
 // ============================================================================
 // EVENT LISTENERS (Setup on app init)
 // ============================================================================
@@ -346,29 +646,33 @@ export async function initWebTabListeners() {
 
   const store = useWebTabStore.getState();
 
-  // Schließe alle eventuell noch offenen Tabs vom letzten Session
+  // Standard-Backend für neue Tabs vom Rust-Host übernehmen
   try {
-    await invoke('close_all_web_tabs');
-    console.log('Cleaned up old web tabs');
+    store._setDefaultBackendState(await getDefaultWebTabBackend());
   } catch (e) {
-    // Ignorieren wenn keine alten Tabs da sind
+    console.error('Failed to load web tab backend:', e);
   }
 
-  // Schließe alle verwaisten Webview-Fenster (die nicht im State sind)
-  try {
-    const closedCount = await invoke<number>('close_all_orphaned_webviews');
-    if (closedCount > 0) {
-      console.log(`Cleaned up ${closedCount} orphaned webview windows`);
-    }
-  } catch (e) {
-    console.error('Failed to cleanup orphaned webviews:', e);
-  }
+  // Board-Überschreibungen der URL-Policy folgen dem geöffneten Board
+  let policyBoardId = useCanvasStore.getState().boardId;
+  setUrlPolicyBoard(policyBoardId).catch((e) => console.error('Failed to set URL policy board:', e));
+  useCanvasStore.subscribe((canvas) => {
+    if (canvas.boardId === policyBoardId) return;
+    policyBoardId = canvas.boardId;
+    setUrlPolicyBoard(policyBoardId).catch((e) => console.error('Failed to set URL policy board:', e));
+  });
 
   // Focus events
   await listen<{ tab_id: string; focused: boolean }>('web-tab-focus', (event) => {
     store._setTabFocused(event.payload.tab_id, event.payload.focused);
   });
 
+  // Von der URL-Policy blockierte Navigationen
+  await listen<UrlPolicyViolation>('web-tab-policy-violation', (event) => {
+    console.warn(`Blocked ${event.payload.url} (${event.payload.rule})`);
+    store._setPolicyViolation(event.payload);
+  });
+
   // Fullscreen events
   await listen<{ tab_id: string; fullscreen: boolean }>('web-tab-fullscreen', (event) => {
     store._setTabFullscreenState(event.payload.tab_id, event.payload.fullscreen);
@@ -379,6 +683,34 @@ export async function initWebTabListeners() {
     store._removeTab(event.payload);
   });
 
+  // Navigationen innerhalb nativer Tabs
+  await listen<WebTabNavigatedEvent>('web-tab-navigated', (event) => {
+    store._updatePageState(event.payload.tab_id, {
+      url: event.payload.url,
+      title: event.payload.title ?? undefined,
+    });
+  });
+
+  // Canvas-Layout blendet Tabs außerhalb des Bildes oder zu klein aus
+  await listen<{ tab_id: string; on_screen: boolean }>('web-tab-visibility', (event) => {
+    store._updatePageState(event.payload.tab_id, { isOnScreen: event.payload.on_screen });
+  });
+
+  // Verlauf der nativen Tabs (navigate / zurück / vor / Page-Load)
+  await listen<{ tab_id: string; history: NavigationHistory }>('web-tab-history', (event) => {
+    store._updatePageState(event.payload.tab_id, historyToPatch(event.payload.history));
+  });
+
+  // Lifecycle-Events der OSR-Browser (Browser-ID = Tab-ID)
+  for (const name of CEF_EVENTS) {
+    await listen<CefBrowserEvent>(`cef://${name}`, (event) => {
+      const patch = cefEventToPatch(event.payload);
+      if (patch) {
+        store._updatePageState(event.payload.browser_id, patch);
+      }
+    });
+  }
+
   // ESC key handler für Fullscreen-Exit
   document.addEventListener('keydown', async (e) => {
     if (e.key === 'Escape') {